@@ -0,0 +1,58 @@
+use criterion::{
+    Criterion,
+    criterion_group,
+    criterion_main,
+};
+use realme::prelude::*;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct Item {
+    pub id:      u32,
+    pub name:    String,
+    pub weight:  f64,
+    pub enabled: bool,
+    pub tags:    Vec<String>,
+}
+
+fn items(len: u32) -> Value {
+    Value::Array(
+        (0..len)
+            .map(|id| {
+                Value::from_dotted([
+                    ("id", Value::from(id)),
+                    ("name", Value::from(format!("item-{id}"))),
+                    ("weight", Value::from(f64::from(id) / 3.0)),
+                    ("enabled", Value::from(id % 2 == 0)),
+                    ("tags", Value::from(vec!["alpha", "beta", "gamma"])),
+                ])
+                .expect("build item")
+            })
+            .collect(),
+    )
+}
+
+fn bench_deserialize(c: &mut Criterion) {
+    let items = items(50_000);
+    let mut group = c.benchmark_group("deserialize_vec");
+    group.bench_function("sequential", |b| {
+        b.iter(|| {
+            items
+                .clone()
+                .try_deserialize::<Vec<Item>>()
+                .expect("deserialize items")
+        });
+    });
+    group.bench_function("parallel", |b| {
+        b.iter(|| {
+            items
+                .clone()
+                .try_deserialize_par::<Item>()
+                .expect("deserialize items")
+        });
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_deserialize);
+criterion_main!(benches);
@@ -0,0 +1,86 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::LitStr;
+
+/// Expands a dotted key literal into the `Expression` it parses to, failing
+/// to compile when the key is malformed.
+///
+/// The expansion names `::realme::Expression` by its full path, so the
+/// macro works without importing the prelude.
+pub fn key_expression(input: &LitStr) -> TokenStream {
+    let key = input.value();
+    let segments = match parse_key(&key) {
+        Ok(segments) => segments,
+        Err(cause) => {
+            return syn::Error::new(
+                input.span(),
+                format!("invalid key `{key}`: {cause}"),
+            )
+            .to_compile_error();
+        }
+    };
+
+    let mut exprs: Vec<TokenStream> = segments
+        .into_iter()
+        .map(|(name, index)| match index {
+            Some(index) => {
+                quote! {
+                    ::realme::Expression::Subscript(#name.to_string(), #index)
+                }
+            }
+            None => {
+                quote! { ::realme::Expression::Identifier(#name.to_string()) }
+            }
+        })
+        .collect();
+
+    if exprs.len() == 1 {
+        exprs.remove(0)
+    } else {
+        quote! { ::realme::Expression::Child(::std::vec![#(#exprs),*]) }
+    }
+}
+
+/// Splits a dotted key into its identifiers and optional subscripts.
+///
+/// Identifiers are made of alphanumeric characters, `_` and `-`, and each
+/// may be followed by a single integer subscript such as `hosts[0]`.
+fn parse_key(key: &str) -> Result<Vec<(&str, Option<isize>)>, String> {
+    if key.is_empty() {
+        return Err("the key is empty".to_string());
+    }
+    key.split('.')
+        .map(|segment| {
+            let (name, index) = match segment.split_once('[') {
+                Some((name, rest)) => {
+                    let index = rest.strip_suffix(']').ok_or_else(|| {
+                        format!("unbalanced `[` in `{segment}`")
+                    })?;
+                    if index.contains(['[', ']']) {
+                        return Err(format!(
+                            "only one subscript is allowed in `{segment}`"
+                        ));
+                    }
+                    let index = index.parse::<isize>().map_err(|_| {
+                        format!("invalid subscript `{index}` in `{segment}`")
+                    })?;
+                    (name, Some(index))
+                }
+                None if segment.contains(']') => {
+                    return Err(format!("unbalanced `]` in `{segment}`"));
+                }
+                None => (segment, None),
+            };
+            if name.is_empty() {
+                return Err("empty identifier".to_string());
+            }
+            if let Some(c) = name
+                .chars()
+                .find(|c| !(c.is_alphanumeric() || *c == '_' || *c == '-'))
+            {
+                return Err(format!("invalid character `{c}` in `{name}`"));
+            }
+            Ok((name, index))
+        })
+        .collect()
+}
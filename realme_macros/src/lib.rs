@@ -3,6 +3,7 @@ use proc_macro::TokenStream;
 use syn::parse_macro_input;
 
 mod adaptor;
+mod key;
 
 #[proc_macro]
 pub fn file(input: TokenStream) -> TokenStream {
@@ -16,3 +17,16 @@ pub fn toml(input: TokenStream) -> TokenStream {
     input.set_parser("TomlParser");
     adaptor::file_adaptor(input).into()
 }
+
+/// Builds the `Expression` of a dotted key, checking its syntax at compile
+/// time.
+///
+/// The key must be a string literal made of identifiers separated by `.`,
+/// each optionally followed by an integer subscript, e.g.
+/// `key!("servers[0].port")`. Whether the key exists is only known at
+/// runtime.
+#[proc_macro]
+pub fn key(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as syn::LitStr);
+    key::key_expression(&input).into()
+}
@@ -1,24 +1,46 @@
-use std::sync::Arc;
+use std::{
+    path::{
+        Path,
+        PathBuf,
+    },
+    sync::Arc,
+};
 
 use source::Source;
 
 use crate::{
     Error,
+    Map,
     Result,
     Value,
+    value::insert_nested,
 };
 
+mod include;
+mod merge;
 pub mod parser;
 pub mod source;
 
+pub use merge::MergeStrategy;
+
 /// Represents an adaptor that wraps a source of configuration data.
 #[derive(Debug, Clone)]
 pub struct Adaptor {
     /// The underlying source of configuration data.
-    source:       Arc<dyn Source<Error = Error, Value = Value>>,
-    pub priority: u8,
-    pub watch:    bool,
-    pub profile:  Option<String>,
+    source:        Arc<dyn Source<Error = Error, Value = Value>>,
+    pub priority:  i32,
+    pub watch:     bool,
+    pub profile:   Option<String>,
+    /// Optional (dotted) key under which the parsed value is nested.
+    pub root:      Option<String>,
+    /// Optional name identifying the adaptor, e.g. in a `MergeAudit`.
+    pub name:      Option<String>,
+    /// Whether parsing fails when the source contributes no keys.
+    pub non_empty: bool,
+    /// Whether the adaptor is skipped when its source can not be read.
+    pub optional:  bool,
+    /// How the parsed value is merged over the adaptors before it.
+    pub merge:     MergeStrategy,
 }
 
 impl Adaptor {
@@ -27,10 +49,15 @@ impl Adaptor {
         source: T,
     ) -> Self {
         Self {
-            source:   Arc::new(source),
-            priority: 0,
-            watch:    false,
-            profile:  None,
+            source:    Arc::new(source),
+            priority:  0,
+            watch:     false,
+            profile:   None,
+            root:      None,
+            name:      None,
+            non_empty: false,
+            optional:  false,
+            merge:     MergeStrategy::default(),
         }
     }
 
@@ -41,20 +68,117 @@ impl Adaptor {
     /// Returns a `Result` containing either the parsed `Value` or a
     /// `Error`.
     pub fn parse(&self) -> Result<Value> {
-        self.source.parse()
+        let Some(value) = self.read_source()? else {
+            return Ok(Value::Null);
+        };
+        self.check_non_empty(&value)?;
+        Ok(self.nest(value))
+    }
+
+    /// Parses the configuration data from the source, merging the files it
+    /// includes, see `RealmeBuilder::enable_includes`.
+    pub(crate) fn parse_with_includes(&self) -> Result<Value> {
+        let Some(value) = self.read_source()? else {
+            return Ok(Value::Null);
+        };
+        let value = match self.source.path() {
+            Some(path) => include::resolve(&*self.source, path, value)?,
+            None => value,
+        };
+        self.check_non_empty(&value)?;
+        Ok(self.nest(value))
+    }
+
+    /// Parses the source, returning `None` when the adaptor is optional and
+    /// the source could not be read, see `optional`.
+    fn read_source(&self) -> Result<Option<Value>> {
+        match self.source.parse_merged(self.merge) {
+            Ok(value) => Ok(Some(value)),
+            Err(e) if self.optional && is_unavailable(&e) => {
+                #[cfg(feature = "tracing")]
+                tracing::debug!(
+                    source = %self.source_name(),
+                    error = %e,
+                    "skipping optional source"
+                );
+                Ok(None)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Fails if the adaptor requires its source to contribute keys and
+    /// `value` has none, see `require_non_empty`.
+    fn check_non_empty(&self, value: &Value) -> Result<()> {
+        let empty = match value {
+            Value::Null => true,
+            Value::Table(table) => table.is_empty(),
+            _ => false,
+        };
+        if self.non_empty && empty {
+            return Err(Error::new_build_error(format!(
+                "{} contributed no keys",
+                self.source_name()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Nests `value` under the root key of the adaptor, if any.
+    fn nest(&self, value: Value) -> Value {
+        match &self.root {
+            Some(root) if value != Value::Null => {
+                let mut map = Map::new();
+                let parts: Vec<&str> = root.split('.').collect();
+                insert_nested(&mut map, &parts, value);
+                Value::Table(map)
+            }
+            _ => value,
+        }
+    }
+
+    /// Returns whether the values of the source of the adaptor are
+    /// sensitive, see `Source::is_sensitive`.
+    pub fn is_sensitive(&self) -> bool {
+        self.source.is_sensitive()
+    }
+
+    /// Returns whether the source of the adaptor changed since it was last
+    /// parsed, see `Source::has_changed`.
+    pub fn has_changed(&self) -> Option<bool> {
+        self.source.has_changed()
+    }
+
+    /// Returns the path of the file the source of the adaptor reads, if
+    /// any, see `Source::path`.
+    pub fn path(&self) -> Option<&Path> {
+        self.source.path()
+    }
+
+    /// Returns the path of the file that provided `key`, a key of the
+    /// parsed value nested under `root`, see `Source::key_path`.
+    pub(crate) fn key_path(&self, key: &str) -> Option<PathBuf> {
+        let key = match &self.root {
+            Some(root) => key.strip_prefix(root.as_str())?.strip_prefix('.')?,
+            None => key,
+        };
+        self.source.key_path(key)
     }
 
     /// Set the priority of the adaptor.
     ///
+    /// Adaptors are merged in increasing order of priority, so an adaptor
+    /// overrides the keys set by the adaptors of lower priority whatever the
+    /// order they were loaded in. Adaptors of the same priority are merged
+    /// in the order they were loaded. The default priority is 0, so a
+    /// negative priority places the adaptor below the adaptors without one,
+    /// e.g. for fallbacks loaded from another code path.
+    ///
     /// # Returns
     ///
     /// Returns the adaptor with the priority set.
-    /// The larger the priority, the later it will be parsed.
-    /// If the priority is not set, it will be parsed first.
-    /// If all adaptors are not set priority, it will be parsed from the last
-    /// one you loaded.
     #[must_use]
-    pub const fn priority(mut self, priority: u8) -> Self {
+    pub const fn priority(mut self, priority: i32) -> Self {
         self.priority = priority;
         self
     }
@@ -70,6 +194,88 @@ impl Adaptor {
         self
     }
 
+    /// Nest the parsed value of the adaptor under the given key.
+    ///
+    /// The key may be dotted, e.g. `app.secrets`, in which case the
+    /// intermediate tables are created. This keeps the content of the source
+    /// out of the root namespace.
+    ///
+    /// # Returns
+    ///
+    /// Returns the adaptor with the root key set.
+    #[must_use]
+    pub fn under(mut self, root: impl Into<String>) -> Self {
+        self.root = Some(root.into());
+        self
+    }
+
+    /// Name the adaptor.
+    ///
+    /// The name identifies the adaptor in a `MergeAudit`. Unnamed adaptors
+    /// are identified by the type of their source, e.g. `FileSource`.
+    ///
+    /// # Returns
+    ///
+    /// Returns the adaptor with the name set.
+    #[must_use]
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Set how the parsed value is merged over the adaptors before it.
+    ///
+    /// # Returns
+    ///
+    /// Returns the adaptor with the merge strategy set.
+    #[must_use]
+    pub const fn with_merge_strategy(mut self, merge: MergeStrategy) -> Self {
+        self.merge = merge;
+        self
+    }
+
+    /// Require the source to contribute at least one key.
+    ///
+    /// Building fails, naming the source, when it parses to an empty table.
+    /// This catches sources silently contributing nothing, like an empty
+    /// file or an `EnvSource` whose prefix has a typo.
+    ///
+    /// # Returns
+    ///
+    /// Returns the adaptor requiring a non-empty source.
+    #[must_use]
+    pub const fn require_non_empty(mut self) -> Self {
+        self.non_empty = true;
+        self
+    }
+
+    /// Make the source optional.
+    ///
+    /// When the source is unavailable, such as a missing file or archive
+    /// entry, a refused connection, an HTTP 404 or a missing secret, the
+    /// adaptor is skipped as if it contributed no keys instead of failing the
+    /// build or reload, see `Error::Unavailable`. Any other failure still
+    /// fails, so a corrupt archive, a permission error or a malformed file is
+    /// not silently ignored. An `EnvSource` whose prefix matches no variable
+    /// already contributes no keys without failing.
+    ///
+    /// # Returns
+    ///
+    /// Returns the adaptor skipped when its source can not be read.
+    #[must_use]
+    pub const fn optional(mut self) -> Self {
+        self.optional = true;
+        self
+    }
+
+    /// Returns the name of the adaptor, or the type of its source when it
+    /// is unnamed.
+    pub fn source_name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("{:?}", self.source))
+    }
+
     /// Set the watch for the adaptor.
     ///
     /// With watch set, the adaptor will watch the source file and reload the
@@ -99,3 +305,130 @@ impl Adaptor {
         }
     }
 }
+
+/// Returns whether `e` means a source does not exist or can not be reached,
+/// rather than read and found invalid.
+const fn is_unavailable(e: &Error) -> bool {
+    matches!(e, Error::Unavailable(_))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn test_require_non_empty() -> anyhow::Result<()> {
+        let empty: HashMap<String, i64> = HashMap::new();
+        let err = Realme::builder()
+            .load(
+                Adaptor::new(SerSource::<SerParser, _>::new(empty))
+                    .name("defaults")
+                    .require_non_empty(),
+            )
+            .build()
+            .expect_err("the source is empty");
+        assert!(err.to_string().contains("defaults contributed no keys"));
+
+        let realme = Realme::builder()
+            .load(
+                Adaptor::new(SerSource::<SerParser, _>::new(HashMap::from([
+                    ("port", 8080),
+                ])))
+                .require_non_empty(),
+            )
+            .build()?;
+        assert_eq!(realme.get_as::<i64, _>("port"), Some(8080));
+        Ok(())
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_optional() -> anyhow::Result<()> {
+        let realme = Realme::builder()
+            .load(Adaptor::new(StringSource::<TomlParser>::new("port = 8080")))
+            .load(
+                Adaptor::new(FileSource::<TomlParser>::new("missing.toml"))
+                    .optional(),
+            )
+            .build()?;
+        assert_eq!(realme.get_as::<i64, _>("port"), Some(8080));
+
+        let err = Realme::builder()
+            .load(
+                Adaptor::new(StringSource::<TomlParser>::new("port = "))
+                    .optional(),
+            )
+            .build()
+            .expect_err("the source is malformed");
+        assert!(matches!(err, Error::ParseError(_)), "{err}");
+        Ok(())
+    }
+
+    #[cfg(feature = "env")]
+    #[test]
+    fn test_require_non_empty_env_prefix() {
+        let err = Realme::builder()
+            .load(
+                Adaptor::new(EnvSource::<EnvParser>::new("REALME_TYPO_"))
+                    .require_non_empty(),
+            )
+            .build()
+            .expect_err("no variable has the prefix");
+        assert!(err.to_string().contains("contributed no keys"));
+    }
+
+    #[test]
+    fn test_merge_strategy() -> anyhow::Result<()> {
+        let build = |strategy: MergeStrategy| {
+            Realme::builder()
+                .load(Adaptor::new(SerSource::<SerParser, _>::new(
+                    serde_json::json!({
+                        "server": { "host": "localhost", "port": 8080 },
+                        "hosts": ["a"],
+                        "debug": true,
+                    }),
+                )))
+                .load(
+                    Adaptor::new(SerSource::<SerParser, _>::new(
+                        serde_json::json!({
+                            "server": { "port": 9090 },
+                            "hosts": ["b"],
+                            "debug": null,
+                        }),
+                    ))
+                    .with_merge_strategy(strategy),
+                )
+                .build()
+        };
+
+        let realme = build(MergeStrategy::default())?;
+        assert_eq!(
+            realme.get_as::<String, _>("server.host").as_deref(),
+            Some("localhost")
+        );
+        assert_eq!(
+            realme.get_as::<Vec<String>, _>("hosts"),
+            Some(vec!["b".to_string()])
+        );
+        assert_eq!(realme.get("debug"), Some(&Value::Null));
+
+        let realme = build(MergeStrategy::Replace { null_erases: false })?;
+        assert_eq!(realme.get("server.host"), None);
+        assert_eq!(realme.get_as::<u16, _>("server.port"), Some(9090));
+
+        let realme = build(MergeStrategy::Deep {
+            append_arrays: true,
+            null_erases:   true,
+        })?;
+        assert_eq!(realme.get_as::<u16, _>("server.port"), Some(9090));
+        assert_eq!(
+            realme.get_as::<Vec<String>, _>("hosts"),
+            Some(vec!["a".to_string(), "b".to_string()])
+        );
+        assert_eq!(realme.get("debug"), None);
+        Ok(())
+    }
+}
@@ -0,0 +1,96 @@
+use std::path::{
+    Path,
+    PathBuf,
+};
+
+use super::source::Source;
+use crate::{
+    Error,
+    Map,
+    Result,
+    Value,
+};
+
+/// Key listing the files included by a configuration file.
+const INCLUDE_KEY: &str = "include";
+
+/// Resolves the includes of `value`, read by `source` from the file at
+/// `path`, see `RealmeBuilder::enable_includes`.
+pub(super) fn resolve(
+    source: &dyn Source<Error = Error, Value = Value>,
+    path: &Path,
+    value: Value,
+) -> Result<Value> {
+    let mut chain = vec![canonical(path)];
+    resolve_in(source, value, &mut chain)
+}
+
+/// Resolves the includes of `value`, read from the last file of `chain`.
+///
+/// `chain` holds the files being included, from the outermost one, so that
+/// a file including one of them is reported as a cycle.
+fn resolve_in(
+    source: &dyn Source<Error = Error, Value = Value>,
+    mut value: Value,
+    chain: &mut Vec<PathBuf>,
+) -> Result<Value> {
+    let Value::Table(table) = &mut value else {
+        return Ok(value);
+    };
+    let Some(include) = table.remove(INCLUDE_KEY) else {
+        return Ok(value);
+    };
+    let current = chain.last().cloned().unwrap_or_default();
+    let invalid = || {
+        Error::new_build_error(format!(
+            "`{INCLUDE_KEY}` in {} must be a string or an array of strings",
+            current.display()
+        ))
+    };
+    let includes = match include {
+        Value::String(include) => vec![include],
+        Value::Array(includes) => includes
+            .into_iter()
+            .map(|include| match include {
+                Value::String(include) => Ok(include),
+                _ => Err(invalid()),
+            })
+            .collect::<Result<_>>()?,
+        _ => return Err(invalid()),
+    };
+
+    let dir = current.parent().unwrap_or_else(|| Path::new(""));
+    let mut merged = Value::Table(Map::new());
+    for include in includes {
+        let path = canonical(&dir.join(include));
+        if let Some(start) = chain.iter().position(|p| *p == path) {
+            let cycle = chain[start..]
+                .iter()
+                .chain(std::iter::once(&path))
+                .map(|p| p.display().to_string())
+                .collect::<Vec<_>>()
+                .join(" -> ");
+            return Err(Error::new_build_error(format!(
+                "include cycle: {cycle}"
+            )));
+        }
+        let file_value = source.parse_path(&path).unwrap_or_else(|| {
+            Err(Error::new_build_error(format!(
+                "can not include {}: the source does not read files",
+                path.display()
+            )))
+        })?;
+        chain.push(path);
+        let file_value = resolve_in(source, file_value, chain)?;
+        chain.pop();
+        merged.merge(&file_value);
+    }
+    merged.merge(&value);
+    Ok(merged)
+}
+
+/// Returns the canonical form of `path`, or `path` itself when it can not
+/// be resolved, e.g. because the file does not exist.
+fn canonical(path: &Path) -> PathBuf {
+    std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
@@ -0,0 +1,94 @@
+use crate::{
+    Map,
+    Value,
+};
+
+/// How the values parsed by an adaptor are merged over the values of the
+/// adaptors before it, see `Adaptor::with_merge_strategy`.
+///
+/// The default strategy deep-merges tables, replaces arrays and keeps a
+/// `Value::Null` as a value, which is how adaptors have always been merged.
+///
+/// # Examples
+///
+/// ```rust
+/// use realme::MergeStrategy;
+///
+/// let strategy = MergeStrategy::Deep {
+///     append_arrays: true,
+///     null_erases:   true,
+/// };
+/// assert_ne!(strategy, MergeStrategy::default());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeStrategy {
+    /// Merges tables recursively, so a layer only overrides the keys it
+    /// sets, however deeply nested.
+    Deep {
+        /// Whether arrays are appended to rather than replaced.
+        append_arrays: bool,
+        /// Whether a `Value::Null` removes the key rather than being stored.
+        null_erases:   bool,
+    },
+    /// Replaces every top-level key the layer sets as a whole, dropping the
+    /// nested keys the layers below set under it.
+    Replace {
+        /// Whether a `Value::Null` removes the key rather than being stored.
+        null_erases: bool,
+    },
+}
+
+impl Default for MergeStrategy {
+    fn default() -> Self {
+        Self::Deep {
+            append_arrays: false,
+            null_erases:   false,
+        }
+    }
+}
+
+impl MergeStrategy {
+    /// Merges the table `source` into `target` according to the strategy.
+    pub(crate) fn merge(
+        self,
+        target: &mut Map<String, Value>,
+        source: &Map<String, Value>,
+    ) {
+        let null_erases = match self {
+            Self::Deep { null_erases, .. } | Self::Replace { null_erases } => {
+                null_erases
+            }
+        };
+        for (key, value) in source {
+            if null_erases && *value == Value::Null {
+                target.remove(key);
+                continue;
+            }
+            match target.get_mut(key) {
+                Some(existing) => self.merge_value(existing, value),
+                None => {
+                    target.insert(key.clone(), value.clone());
+                }
+            }
+        }
+    }
+
+    fn merge_value(self, target: &mut Value, source: &Value) {
+        match (self, target, source) {
+            (Self::Deep { .. }, Value::Table(target), Value::Table(source)) => {
+                self.merge(target, source);
+            }
+            (
+                Self::Deep {
+                    append_arrays: true,
+                    ..
+                },
+                Value::Array(target),
+                Value::Array(source),
+            ) => {
+                target.extend(source.iter().cloned());
+            }
+            (_, target, source) => *target = source.clone(),
+        }
+    }
+}
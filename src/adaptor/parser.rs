@@ -1,13 +1,27 @@
 #[cfg(feature = "cmd")]
 pub mod cmd;
+#[cfg(feature = "dotenv")]
+pub mod dotenv;
 #[cfg(feature = "env")]
 pub mod env;
+#[cfg(feature = "hcl")]
+pub mod hcl;
+#[cfg(feature = "hocon")]
+pub mod hocon;
 #[cfg(feature = "ini")]
 pub mod ini;
 #[cfg(feature = "json")]
 pub mod json;
 #[cfg(feature = "json5")]
 pub mod json5;
+#[cfg(feature = "kdl")]
+pub mod kdl;
+#[cfg(feature = "msgpack")]
+pub mod msgpack;
+#[cfg(feature = "nginx")]
+pub mod nginx;
+#[cfg(feature = "properties")]
+pub mod properties;
 #[cfg(feature = "ron")]
 pub mod ron;
 pub mod ser;
@@ -16,13 +30,21 @@ pub mod toml;
 #[cfg(feature = "yaml")]
 pub mod yaml;
 
+use std::path::Path;
+
 use serde::Serialize;
 
+use crate::Value;
+
 /// A trait for parsing input of type `T` into a serializable item.
 ///
 /// This trait defines a generic parser that can convert input of type `T`
 /// into a serializable item, with the possibility of encountering errors
 /// during the parsing process.
+///
+/// Text formats implement `Parser<T>` for `T: AsRef<str>` and are read by
+/// `FileSource` or `StringSource`, while binary formats implement it for
+/// `T: AsRef<[u8]>` and are read by `BytesSource`.
 pub trait Parser<T> {
     /// The type of item produced by the parser.
     ///
@@ -46,4 +68,115 @@ pub trait Parser<T> {
     /// A `Result` containing either the successfully parsed `Item` or an
     /// `Error`.
     fn parse(args: T) -> Result<Self::Item, Self::Error>;
+
+    /// Parses `args`, the content of the file at `path`.
+    ///
+    /// `FileSource` parses its file with this, so formats referring to
+    /// other files can resolve them relative to `path`. By default the path
+    /// is ignored and `args` is parsed with `Parser::parse`.
+    fn parse_at(args: T, _path: &Path) -> Result<Self::Item, Self::Error> {
+        Self::parse(args)
+    }
+}
+
+/// A trait for writing a `Value` back in the format of a parser.
+///
+/// This is the reverse of `Parser`, used by `Realme::try_serialize_to` and
+/// `Realme::write_to_file` to persist a configuration.
+pub trait Emitter {
+    /// Serializes `value` into a string in the format.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::SerializeError` if the value can not be represented
+    /// in the format.
+    fn emit(value: &Value) -> crate::Result<String>;
+}
+
+/// How number literals that do not fit a 64-bit integer or float are
+/// handled by the parsers supporting it.
+///
+/// A `Value` holds integers as `i64` and floats as `f64`, so larger numbers
+/// would otherwise lose precision or fail with an opaque error.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LargeNumbers {
+    /// Reject the input with an error naming the number.
+    #[default]
+    Error,
+    /// Keep the number as a string holding its literal, so no digit is lost.
+    String,
+}
+
+impl LargeNumbers {
+    /// Checks a number literal, returning the string literal replacing it
+    /// when it is out of range, or `None` when it fits.
+    pub(crate) fn check(
+        self,
+        literal: &str,
+    ) -> std::result::Result<Option<String>, String> {
+        let Some(kind) = out_of_range(literal) else {
+            return Ok(None);
+        };
+        match self {
+            Self::Error => {
+                Err(format!("number `{literal}` is out of range for {kind}"))
+            }
+            Self::String => Ok(Some(format!("\"{literal}\""))),
+        }
+    }
+}
+
+/// How floats are written when a `Realme` is serialized, set with
+/// `RealmeBuilder::float_format`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FloatFormat {
+    /// The shortest decimal that reads back as the same float, so a loaded
+    /// `1.1` is written as `1.1`.
+    #[default]
+    Shortest,
+    /// The float rounded to at most this many decimal places, trailing
+    /// zeros left out.
+    Precision(usize),
+}
+
+impl FloatFormat {
+    /// Rounds the finite floats of `value` as configured.
+    pub(crate) fn apply(self, value: &mut Value) {
+        let Self::Precision(precision) = self else {
+            return;
+        };
+        match value {
+            Value::Float(f) if f.is_finite() => {
+                if let Ok(rounded) = format!("{f:.precision$}").parse() {
+                    *f = rounded;
+                }
+            }
+            Value::Array(array) => {
+                for v in array {
+                    self.apply(v);
+                }
+            }
+            Value::Table(table) => {
+                for (_, v) in table {
+                    self.apply(v);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Returns the type a decimal number literal does not fit in, if any.
+fn out_of_range(literal: &str) -> Option<&'static str> {
+    let digits = literal.replace('_', "");
+    if digits.contains(['.', 'e', 'E']) {
+        let float = digits.parse::<f64>().ok()?;
+        float.is_infinite().then_some("a 64-bit float")
+    } else {
+        let unsigned = digits.trim_start_matches(['+', '-']);
+        let decimal = !unsigned.is_empty() &&
+            unsigned.bytes().all(|b| b.is_ascii_digit());
+        (decimal && digits.parse::<i64>().is_err())
+            .then_some("a 64-bit integer")
+    }
 }
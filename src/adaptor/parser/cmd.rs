@@ -1,13 +1,19 @@
 use nom::{
     IResult,
     branch::alt,
-    bytes::complete::take_while1,
+    bytes::complete::{
+        take_while,
+        take_while1,
+    },
     character::complete::{
         char,
         multispace0,
+        multispace1,
+        satisfy,
         space0,
     },
     combinator::map,
+    error::ErrorKind,
     multi::separated_list0,
     sequence::{
         delimited,
@@ -20,25 +26,69 @@ use nom::{
 use crate::{
     Error,
     prelude::*,
+    value::insert_nested,
 };
 
 /// A parser for command-line style key-value pairs.
+///
+/// Unquoted values are typed from their literal: `true` and `false` are
+/// booleans, `null` is `Value::Null`, and decimal numbers are integers or
+/// floats, so `age=30` holds `Value::Integer(30)`. Anything else, including
+/// numbers with leading zeros such as `zip=01001` or out of the range of
+/// `i64`, is a string. Quoting a value keeps it a string, as in `age="30"`.
 #[derive(Debug, Default)]
 pub struct CmdParser;
 
 impl CmdParser {
+    /// How deeply arrays may be nested by default, see `parse_with_depth`.
+    pub const DEFAULT_MAX_DEPTH: usize = 128;
+
+    /// Parses `args` like `Parser::parse`, allowing arrays to be nested at
+    /// most `max_depth` levels deep.
+    ///
+    /// Arrays are parsed recursively, so bounding their nesting keeps input
+    /// from untrusted callers from overflowing the stack.
+    ///
+    /// # Errors
+    ///
+    /// Returns a parse error if `args` is not valid cmd input or nests
+    /// arrays deeper than `max_depth`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use realme::prelude::*;
+    /// assert!(CmdParser::parse_with_depth("list=[[a]]", 2).is_ok());
+    /// assert!(CmdParser::parse_with_depth("list=[[a]]", 1).is_err());
+    /// ```
+    pub fn parse_with_depth(
+        args: &str,
+        max_depth: usize,
+    ) -> Result<Value, Error> {
+        Self::parse_args(args, false, false, max_depth)
+    }
+
     /// Parses a key-value pair separated by an '=' character.
     ///
     /// # Arguments
     ///
     /// * `input` - A string slice that holds the input to be parsed.
+    /// * `stop` - Returns whether a character ends an unquoted value.
+    /// * `commas` - Whether array elements may be separated by commas.
+    /// * `depth` - How deeply arrays may be nested in the value.
     ///
     /// # Returns
     ///
     /// * `IResult` - A result containing the remaining input and a tuple of the
     ///   parsed key and value.
-    fn parse_pair(input: &str) -> IResult<&str, (String, Value)> {
-        separated_pair(Self::parse_key, char('='), Self::parse_value)(input)
+    fn parse_pair(
+        input: &str,
+        stop: fn(char) -> bool,
+        commas: bool,
+        depth: usize,
+    ) -> IResult<&str, (String, Value)> {
+        separated_pair(Self::parse_key, char('='), |input| {
+            Self::parse_value(input, stop, commas, depth)
+        })(input)
     }
 
     /// Parses a key which can contain alphanumeric characters, dots, and
@@ -60,54 +110,125 @@ impl CmdParser {
     }
 
     /// Parses a value which can be an array, a quoted string, or an unquoted
-    /// string.
+    /// literal typed by `parse_literal`.
     ///
     /// # Arguments
     ///
     /// * `input` - A string slice that holds the input to be parsed.
+    /// * `stop` - Returns whether a character ends an unquoted value.
+    /// * `commas` - Whether array elements may be separated by commas.
+    /// * `depth` - How deeply arrays may be nested in the value.
     ///
     /// # Returns
     ///
     /// * `IResult` - A result containing the remaining input and the parsed
     ///   value.
-    fn parse_value(input: &str) -> IResult<&str, Value> {
+    fn parse_value(
+        input: &str,
+        stop: fn(char) -> bool,
+        commas: bool,
+        depth: usize,
+    ) -> IResult<&str, Value> {
         alt((
-            Self::parse_array,
+            |input| Self::parse_array(input, commas, depth),
             // CHECK: is this necessary?
             // for something like "He said, Hello, World!" it is needed
-            map(
-                delimited(char('"'), take_while1(|c| c != '"'), char('"')),
-                |s: &str| Value::String(s.trim().to_string()),
-            ),
-            map(take_while1(|c| c != ',' && c != ']'), |s: &str| {
-                Value::String(s.trim().to_string())
-            }),
+            Self::parse_quoted,
+            map(take_while1(|c| !stop(c)), Self::parse_literal),
         ))(input)
     }
 
+    /// Parses a string enclosed in double quotes, which is never typed.
+    fn parse_quoted(input: &str) -> IResult<&str, Value> {
+        map(
+            preceded(
+                space0,
+                delimited(char('"'), take_while(|c| c != '"'), char('"')),
+            ),
+            |s: &str| Value::String(s.trim().to_string()),
+        )(input)
+    }
+
+    /// Types an unquoted literal: booleans, `null`, integers and floats are
+    /// recognized, anything else is a string.
+    ///
+    /// Integers with leading zeros or out of the range of `i64` are kept as
+    /// strings, so no digit is lost. Likewise floats are only typed when
+    /// they are written as they are displayed, so `1.10` and `1e3` are kept
+    /// as strings, as are floats that are not finite.
+    fn parse_literal(literal: &str) -> Value {
+        let literal = literal.trim();
+        match literal {
+            "true" => return Value::Boolean(true),
+            "false" => return Value::Boolean(false),
+            "null" => return Value::Null,
+            _ => {}
+        }
+        let unsigned = literal.strip_prefix(['+', '-']).unwrap_or(literal);
+        let numeric = unsigned.starts_with(|c: char| c.is_ascii_digit()) &&
+            unsigned.ends_with(|c: char| c.is_ascii_digit()) &&
+            unsigned.chars().all(|c| {
+                c.is_ascii_digit() || matches!(c, '.' | 'e' | 'E' | '+' | '-')
+            });
+        let leading_zero = unsigned.len() > 1 &&
+            unsigned.starts_with('0') &&
+            !unsigned[1..].starts_with(['.', 'e', 'E']);
+        if !numeric || leading_zero {
+            return Value::String(literal.to_string());
+        }
+        if unsigned.bytes().all(|b| b.is_ascii_digit()) {
+            return literal.parse().map_or_else(
+                |_| Value::String(literal.to_string()),
+                Value::Integer,
+            );
+        }
+        literal
+            .parse::<f64>()
+            .ok()
+            .filter(|f| f.is_finite() && f.to_string() == literal)
+            .map_or_else(|| Value::String(literal.to_string()), Value::Float)
+    }
+
     /// Parses an array of values separated by semicolons and enclosed in square
     /// brackets.
     ///
     /// # Arguments
     ///
     /// * `input` - A string slice that holds the input to be parsed.
+    /// * `commas` - Whether elements may also be separated by commas, which the
+    ///   brackets tell apart from the commas separating pairs.
+    /// * `depth` - How deeply arrays may be nested, this array included.
     ///
     /// # Returns
     ///
     /// * `IResult` - A result containing the remaining input and the parsed
-    ///   array as a `Value::Array`.
-    fn parse_array(input: &str) -> IResult<&str, Value> {
+    ///   array as a `Value::Array`. Nesting deeper than `depth` is a
+    ///   `nom::Err::Failure`, so that the brackets are not parsed as a string
+    ///   instead.
+    fn parse_array(
+        input: &str,
+        commas: bool,
+        depth: usize,
+    ) -> IResult<&str, Value> {
+        let separator = move |c: char| c == ';' || (commas && c == ',');
         let (input, _) = multispace0(input)?;
+        if depth == 0 && input.starts_with('[') {
+            return Err(nom::Err::Failure(nom::error::Error::new(
+                input,
+                ErrorKind::TooLarge,
+            )));
+        }
         delimited(
             char('['),
             map(
                 separated_list0(
-                    preceded(space0, char(';')),
+                    preceded(space0, satisfy(separator)),
                     alt((
-                        Self::parse_array,
+                        |input| Self::parse_array(input, commas, depth - 1),
+                        Self::parse_quoted,
                         map(
-                            take_while1(|c| c != ';' && c != ']'),
-                            |s: &str| Value::String(s.trim().to_string()),
+                            take_while1(move |c| !separator(c) && c != ']'),
+                            Self::parse_literal,
                         ),
                     )),
                 ),
@@ -122,45 +243,69 @@ impl CmdParser {
     /// # Arguments
     ///
     /// * `input` - A string slice that holds the input to be parsed.
+    /// * `spaced` - Whether pairs are separated by whitespace rather than
+    ///   commas.
+    /// * `commas` - Whether array elements may be separated by commas.
+    /// * `depth` - How deeply arrays may be nested in values.
     ///
     /// # Returns
     ///
     /// * `IResult` - A result containing the remaining input and the parsed
     ///   map.
-    fn parse_cmd(input: &str) -> IResult<&str, Map<String, Value>> {
-        let (input, pairs) = separated_list0(
-            terminated(char(','), multispace0),
-            Self::parse_pair,
-        )(input)?;
+    fn parse_cmd(
+        input: &str,
+        spaced: bool,
+        commas: bool,
+        depth: usize,
+    ) -> IResult<&str, Map<String, Value>> {
+        let (input, pairs) = if spaced {
+            separated_list0(multispace1, |input| {
+                Self::parse_pair(
+                    input,
+                    |c| c.is_whitespace() || c == ']',
+                    commas,
+                    depth,
+                )
+            })(input)?
+        } else {
+            separated_list0(terminated(char(','), multispace0), |input| {
+                Self::parse_pair(input, |c| c == ',' || c == ']', commas, depth)
+            })(input)?
+        };
 
         let map =
             pairs.into_iter().fold(Map::new(), |mut acc, (key, value)| {
                 let parts: Vec<&str> = key.split('.').collect();
-                Self::insert_nested(&mut acc, &parts, value);
+                insert_nested(&mut acc, &parts, value);
                 acc
             });
 
         Ok((input, map))
     }
 
-    fn insert_nested(
-        map: &mut Map<String, Value>,
-        parts: &[&str],
-        value: Value,
-    ) {
-        match parts {
-            [head] => {
-                map.insert((*head).to_string(), value);
-            }
-            [head, tail @ ..] => {
-                let entry = map
-                    .entry((*head).to_string())
-                    .or_insert_with(|| Value::Table(Map::new()));
-                if let Value::Table(ref mut nested_map) = entry {
-                    Self::insert_nested(nested_map, tail, value);
-                }
+    /// Parses `args` into a `Value::Table`, see `parse_cmd`.
+    fn parse_args(
+        args: &str,
+        spaced: bool,
+        commas: bool,
+        max_depth: usize,
+    ) -> Result<Value, Error> {
+        let args = args.trim();
+        if args.is_empty() {
+            return Ok(Value::Table(Map::new()));
+        }
+        match Self::parse_cmd(args, spaced, commas, max_depth) {
+            Ok((_, map)) => Ok(Value::Table(map)),
+            Err(nom::Err::Failure(e)) if e.code == ErrorKind::TooLarge => {
+                Err(Error::new_parse_error(
+                    args.to_string(),
+                    format!("arrays are nested deeper than {max_depth} levels"),
+                ))
             }
-            [] => {}
+            Err(_) => Err(Error::new_parse_error(
+                args.to_string(),
+                "Failed to parse from cmd".to_string(),
+            )),
         }
     }
 }
@@ -183,21 +328,76 @@ impl<T: AsRef<str>> Parser<T> for CmdParser {
     /// ```rust
     /// use realme::prelude::*;
     /// let cmd_str = "age=30, name.first=John, name.last=Doe";
-    /// let result = CmdParser::parse(cmd_str);
-    /// assert!(result.is_ok());
+    /// let value = CmdParser::parse(cmd_str).expect("parse cmd");
+    /// assert_eq!(value.get("age"), Some(&Value::Integer(30)));
+    /// assert_eq!(value.get("name.first"), Some(&Value::from("John")));
     /// ```
     fn parse(args: T) -> Result<Self::Item, Self::Error> {
-        let args = args.as_ref().trim();
-        if args.is_empty() {
-            return Ok(Value::Table(Map::new()));
-        }
-        match Self::parse_cmd(args) {
-            Ok((_, map)) => Ok(Value::Table(map)),
-            Err(_) => Err(Error::new_parse_error(
-                args.to_string(),
-                "Failed to parse from cmd".to_string(),
-            )),
-        }
+        Self::parse_args(args.as_ref(), false, false, Self::DEFAULT_MAX_DEPTH)
+    }
+}
+
+/// A parser for command-line style key-value pairs separated by whitespace,
+/// such as `key=value key2=value2`.
+///
+/// Values are parsed as with `CmdParser`: values containing spaces must be
+/// quoted, and arrays are enclosed in square brackets with their elements
+/// separated by semicolons.
+#[derive(Debug, Default)]
+pub struct SpaceSeparatedCmdParser;
+
+impl<T: AsRef<str>> Parser<T> for SpaceSeparatedCmdParser {
+    type Item = Value;
+    type Error = Error;
+
+    /// Parses the input string into a `Value` item.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use realme::prelude::*;
+    /// let value = SpaceSeparatedCmdParser::parse("age=30 name=\"John Doe\"")
+    ///     .expect("parse cmd");
+    /// assert_eq!(value.get("name"), Some(&Value::from("John Doe")));
+    /// ```
+    fn parse(args: T) -> Result<Self::Item, Self::Error> {
+        CmdParser::parse_args(
+            args.as_ref(),
+            true,
+            false,
+            CmdParser::DEFAULT_MAX_DEPTH,
+        )
+    }
+}
+
+/// A parser for command-line style key-value pairs whose arrays separate
+/// their elements with commas, such as `skills=[Go, Rust, Python]`.
+///
+/// Pairs are separated by commas as with `CmdParser`: within square
+/// brackets, a comma separates array elements instead, including in nested
+/// arrays. Semicolons keep separating elements too.
+#[derive(Debug, Default)]
+pub struct CommaArrayCmdParser;
+
+impl<T: AsRef<str>> Parser<T> for CommaArrayCmdParser {
+    type Item = Value;
+    type Error = Error;
+
+    /// Parses the input string into a `Value` item.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use realme::prelude::*;
+    /// let value = CommaArrayCmdParser::parse("skills=[Go, Rust], age=30")
+    ///     .expect("parse cmd");
+    /// assert_eq!(value.get("skills"), Some(&Value::from(vec!["Go", "Rust"])));
+    /// ```
+    fn parse(args: T) -> Result<Self::Item, Self::Error> {
+        CmdParser::parse_args(
+            args.as_ref(),
+            false,
+            true,
+            CmdParser::DEFAULT_MAX_DEPTH,
+        )
     }
 }
 
@@ -239,7 +439,7 @@ mod tests {
         let result = CmdParser::parse(FINAL_CMD.to_string())?;
 
         let expected = Value::Table(Map::from_iter([
-            ("age".to_string(), Value::String("30".to_string())),
+            ("age".to_string(), Value::Integer(30)),
             (
                 "name".to_string(),
                 Value::Table(Map::from_iter([
@@ -252,7 +452,7 @@ mod tests {
                 "address".to_string(),
                 Value::Table(Map::from_iter([
                     ("city".to_string(), Value::String("New York".to_string())),
-                    ("zip".to_string(), Value::String("10001".to_string())),
+                    ("zip".to_string(), Value::Integer(10001)),
                 ])),
             ),
             (
@@ -296,7 +496,7 @@ mod tests {
         let result = CmdParser::parse("age=30".to_string())?;
         let expected = Value::Table(Map::from_iter([(
             "age".to_string(),
-            Value::String("30".to_string()),
+            Value::Integer(30),
         )]));
 
         assert_eq!(result, expected);
@@ -342,14 +542,11 @@ mod tests {
         let expected = Value::Table(Map::from_iter([(
             "nested_array".to_string(),
             Value::Array(vec![
-                Value::Array(vec![Value::String("12".to_string())]),
+                Value::Array(vec![Value::Integer(12)]),
                 Value::Array(vec![
-                    Value::String("3".to_string()),
+                    Value::Integer(3),
                     Value::String("four".to_string()),
-                    Value::Array(vec![
-                        Value::String("5".to_string()),
-                        Value::String("6".to_string()),
-                    ]),
+                    Value::Array(vec![Value::Integer(5), Value::Integer(6)]),
                 ]),
             ]),
         )]));
@@ -357,6 +554,83 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_parse_deeply_nested_array() {
+        let nested = |depth: usize| {
+            format!("list=[{}a{}]", "[".repeat(depth), "]".repeat(depth))
+        };
+        assert!(
+            CmdParser::parse(nested(CmdParser::DEFAULT_MAX_DEPTH - 1)).is_ok()
+        );
+
+        let result = CmdParser::parse(nested(100_000));
+        assert!(matches!(result, Err(Error::ParseError { .. })));
+        let result = CmdParser::parse_with_depth(&nested(2), 2);
+        let message = result
+            .expect_err("the input is nested too deeply")
+            .to_string();
+        assert!(message.contains("deeper than 2 levels"), "{message}");
+    }
+
+    #[test]
+    fn test_parse_comma_array() -> anyhow::Result<()> {
+        let result = CommaArrayCmdParser::parse(
+            "skills=[Go, Rust; Python], name=realme",
+        )?;
+        let expected = Value::Table(Map::from_iter([
+            (
+                "skills".to_string(),
+                Value::from(vec!["Go", "Rust", "Python"]),
+            ),
+            ("name".to_string(), Value::from("realme")),
+        ]));
+        assert_eq!(result, expected);
+
+        let result = CommaArrayCmdParser::parse("nested=[[1, 2], [3]], x=y")?;
+        assert_eq!(
+            result.get("nested"),
+            Some(&Value::Array(vec![
+                Value::from(vec![1, 2]),
+                Value::from(vec![3]),
+            ]))
+        );
+        assert_eq!(result.get("x"), Some(&Value::from("y")));
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_typed_literals() -> anyhow::Result<()> {
+        let result = CmdParser::parse(
+            "port=8080, ratio=-0.5, big=1e3, rate=1.10, debug=true, \
+             cache=false, proxy=null, zip=01001, huge=99999999999999999999, \
+             version=1.2.3, inf=inf, id=\"42\", ports=[80; \"443\"; on]",
+        )?;
+        let expected = Value::Table(Map::from_iter([
+            ("port".to_string(), Value::Integer(8080)),
+            ("ratio".to_string(), Value::Float(-0.5)),
+            ("big".to_string(), Value::from("1e3")),
+            ("rate".to_string(), Value::from("1.10")),
+            ("debug".to_string(), Value::Boolean(true)),
+            ("cache".to_string(), Value::Boolean(false)),
+            ("proxy".to_string(), Value::Null),
+            ("zip".to_string(), Value::from("01001")),
+            ("huge".to_string(), Value::from("99999999999999999999")),
+            ("version".to_string(), Value::from("1.2.3")),
+            ("inf".to_string(), Value::from("inf")),
+            ("id".to_string(), Value::from("42")),
+            (
+                "ports".to_string(),
+                Value::Array(vec![
+                    Value::Integer(80),
+                    Value::from("443"),
+                    Value::from("on"),
+                ]),
+            ),
+        ]));
+        assert_eq!(result, expected);
+        Ok(())
+    }
+
     #[test]
     fn test_parse_table() -> anyhow::Result<()> {
         let result =
@@ -467,4 +741,47 @@ mod tests {
         assert_eq!(result, expected);
         Ok(())
     }
+
+    #[test]
+    fn test_parse_space_separated() -> anyhow::Result<()> {
+        let result =
+            SpaceSeparatedCmdParser::parse("a=1 b=2  name.first=John")?;
+        let expected = Value::Table(Map::from_iter([
+            ("a".to_string(), Value::Integer(1)),
+            ("b".to_string(), Value::Integer(2)),
+            (
+                "name".to_string(),
+                Value::Table(Map::from_iter([(
+                    "first".to_string(),
+                    Value::String("John".to_string()),
+                )])),
+            ),
+        ]));
+        assert_eq!(result, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_space_separated_quoted_and_array() -> anyhow::Result<()> {
+        let result = SpaceSeparatedCmdParser::parse(
+            "quote=\"Life is beautiful\" skills=[Go; Bash Scripting] \
+             city=Paris",
+        )?;
+        let expected = Value::Table(Map::from_iter([
+            (
+                "quote".to_string(),
+                Value::String("Life is beautiful".to_string()),
+            ),
+            (
+                "skills".to_string(),
+                Value::Array(vec![
+                    Value::String("Go".to_string()),
+                    Value::String("Bash Scripting".to_string()),
+                ]),
+            ),
+            ("city".to_string(), Value::String("Paris".to_string())),
+        ]));
+        assert_eq!(result, expected);
+        Ok(())
+    }
 }
@@ -0,0 +1,209 @@
+use crate::{
+    Error,
+    prelude::*,
+    value::insert_nested,
+};
+
+/// A parser for `.env` files.
+///
+/// Keys are kept verbatim, so `DB__HOST=localhost` becomes the key
+/// `DB__HOST`. See `NestedDotenvParser` to map such keys into nested tables.
+#[derive(Debug)]
+pub struct DotenvParser;
+
+/// A parser for `.env` files mapping `DB__HOST=x` style keys into nested
+/// tables.
+///
+/// Keys are lowercased and split on `__`, so `DB__HOST=localhost` becomes
+/// the key `host` of the table `db`, like `db.host`.
+#[derive(Debug)]
+pub struct NestedDotenvParser;
+
+impl<T: AsRef<str>> Parser<T> for DotenvParser {
+    type Item = Value;
+    type Error = Error;
+
+    /// Parses the content of a `.env` file into a `Value::Table`.
+    ///
+    /// Each `KEY=value` line becomes a `Value::String`, and a key repeated
+    /// later in the file overrides the earlier one. Lines may start with
+    /// `export`, and blank lines and lines starting with `#` are skipped.
+    ///
+    /// Values can be:
+    /// * unquoted, trimmed and ending at a ` #` comment;
+    /// * single quoted, kept literally;
+    /// * double quoted, where `\n`, `\r`, `\t`, `\"`, `\\` and `\$` are
+    ///   escapes.
+    ///
+    /// Quoted values may span several lines.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the line if a line has no `=`, a key is
+    /// empty or invalid, a quoted value is not terminated or is followed by
+    /// anything but a comment.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use realme::prelude::*;
+    ///
+    /// let env = "export NAME='my app' # the name\nPORT=8080";
+    /// let value = DotenvParser::parse(env).expect("parse dotenv");
+    /// assert_eq!(value.get("NAME"), Some(&Value::String("my app".into())));
+    /// assert_eq!(value.get("PORT"), Some(&Value::String("8080".into())));
+    /// ```
+    fn parse(args: T) -> Result<Self::Item, Self::Error> {
+        let pairs = parse_pairs(args.as_ref()).map_err(line_error)?;
+        let mut map = Map::new();
+        for (key, value) in pairs {
+            map.insert(key, Value::String(value));
+        }
+        Ok(Value::Table(map))
+    }
+}
+
+impl<T: AsRef<str>> Parser<T> for NestedDotenvParser {
+    type Item = Value;
+    type Error = Error;
+
+    /// Parses the content of a `.env` file into nested `Value::Table`s.
+    ///
+    /// The content is parsed as by `DotenvParser`, then keys are lowercased
+    /// and split on `__` into nested tables. A key nesting under a key
+    /// holding a string is dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error under the same conditions as `DotenvParser`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use realme::prelude::*;
+    ///
+    /// let env = "DB__HOST=localhost\nDB__PORT=5432";
+    /// let value = NestedDotenvParser::parse(env).expect("parse dotenv");
+    /// assert_eq!(
+    ///     value.get("db.host"),
+    ///     Some(&Value::String("localhost".into()))
+    /// );
+    /// ```
+    fn parse(args: T) -> Result<Self::Item, Self::Error> {
+        let pairs = parse_pairs(args.as_ref()).map_err(line_error)?;
+        let mut map = Map::new();
+        for (key, value) in pairs {
+            let key = key.to_lowercase();
+            let path: Vec<&str> =
+                key.split("__").filter(|part| !part.is_empty()).collect();
+            insert_nested(&mut map, &path, Value::String(value));
+        }
+        Ok(Value::Table(map))
+    }
+}
+
+/// Error of a `.env` file: the number of the line and the cause.
+type LineError = (usize, String);
+
+/// Turns a `LineError` into a parse error naming the line, leaving out the
+/// content of the file since it often holds secrets.
+fn line_error((line_no, cause): LineError) -> Error {
+    Error::new_parse_error(format!("line {line_no}"), cause)
+}
+
+/// Parses the `KEY=value` pairs of a `.env` file in order.
+fn parse_pairs(input: &str) -> Result<Vec<(String, String)>, LineError> {
+    let mut pairs = Vec::new();
+    let mut lines = input.lines().enumerate();
+    while let Some((index, line)) = lines.next() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line_no = index + 1;
+        let line = line
+            .strip_prefix("export")
+            .filter(|rest| rest.starts_with([' ', '\t']))
+            .map_or(line, str::trim_start);
+        let (key, rest) = line
+            .split_once('=')
+            .ok_or_else(|| (line_no, "expected `KEY=value`".to_owned()))?;
+        let key = key.trim();
+        if key.is_empty() ||
+            !key.chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.'))
+        {
+            return Err((line_no, format!("invalid key `{key}`")));
+        }
+        let rest = rest.trim_start();
+        let value = match rest.chars().next() {
+            Some(quote @ ('\'' | '"')) => {
+                parse_quoted(&rest[1..], quote, line_no, &mut lines)?
+            }
+            _ => strip_comment(rest).trim_end().to_owned(),
+        };
+        pairs.push((key.to_owned(), value));
+    }
+    Ok(pairs)
+}
+
+/// Parses a quoted value starting after its opening `quote`, reading the
+/// following lines until the closing quote.
+fn parse_quoted<'a>(
+    first: &'a str,
+    quote: char,
+    line_no: usize,
+    lines: &mut impl Iterator<Item = (usize, &'a str)>,
+) -> Result<String, LineError> {
+    let mut value = String::new();
+    let mut line = first;
+    loop {
+        let mut chars = line.char_indices();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '\\' if quote == '"' => match chars.next() {
+                    Some((_, 'n')) => value.push('\n'),
+                    Some((_, 'r')) => value.push('\r'),
+                    Some((_, 't')) => value.push('\t'),
+                    Some((_, escaped @ ('"' | '\\' | '$'))) => {
+                        value.push(escaped);
+                    }
+                    Some((_, other)) => {
+                        value.push('\\');
+                        value.push(other);
+                    }
+                    None => value.push('\\'),
+                },
+                c if c == quote => {
+                    let trailing = line[i + 1..].trim_start();
+                    if !trailing.is_empty() && !trailing.starts_with('#') {
+                        return Err((
+                            line_no,
+                            format!(
+                                "unexpected `{trailing}` after quoted value"
+                            ),
+                        ));
+                    }
+                    return Ok(value);
+                }
+                c => value.push(c),
+            }
+        }
+        let Some((_, next)) = lines.next() else {
+            return Err((
+                line_no,
+                format!("unterminated quoted value, expected `{quote}`"),
+            ));
+        };
+        value.push('\n');
+        line = next;
+    }
+}
+
+/// Strips a ` #` comment from an unquoted value.
+fn strip_comment(value: &str) -> &str {
+    value
+        .char_indices()
+        .find(|&(i, c)| c == '#' && value[..i].ends_with(char::is_whitespace))
+        .map_or(value, |(i, _)| &value[..i])
+}
@@ -0,0 +1,55 @@
+use crate::{
+    Error,
+    prelude::*,
+};
+
+/// A parser for HCL, the `HashiCorp` Configuration Language of Terraform,
+/// Nomad or Consul.
+///
+/// Attributes become keys, and blocks become tables nested under their
+/// identifier and each of their labels, so `service "http" "web" { ... }`
+/// is read into `service.http.web`. Blocks without labels repeating an
+/// identifier are collected into an array of tables.
+///
+/// Expressions are not evaluated: anything but a literal, such as
+/// `"${var.host}"` or `1 + 2`, is kept as a string in template form, here
+/// `"${var.host}"` and `"${1 + 2}"`.
+#[derive(Debug)]
+pub struct HclParser;
+
+impl<T: AsRef<str>> Parser<T> for HclParser {
+    type Item = hcl::Value;
+    type Error = Error;
+
+    /// Parses an HCL string into an `hcl::Value`.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A string-like type that can be converted to a string slice.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Self::Item, Self::Error>` - A Result containing either the
+    ///   parsed `hcl::Value` or a `Error`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use realme::prelude::*;
+    /// let hcl_str = r#"
+    /// name = "John"
+    /// server "web" {
+    ///   port = 8080
+    /// }
+    /// "#;
+    /// let value =
+    ///     Value::try_serialize(&HclParser::parse(hcl_str).expect("parse hcl"))
+    ///         .expect("serialize hcl");
+    /// assert_eq!(value.get("server.web.port"), Some(&Value::Integer(8080)));
+    /// ```
+    fn parse(args: T) -> Result<Self::Item, Self::Error> {
+        let args = args.as_ref().trim();
+        hcl::from_str(args).map_err(|e| {
+            Error::new_parse_error(args.to_string(), e.to_string())
+        })
+    }
+}
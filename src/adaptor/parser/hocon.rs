@@ -0,0 +1,131 @@
+use std::path::Path;
+
+use hocon::{
+    Hocon,
+    HoconLoader,
+};
+
+use crate::{
+    Error,
+    prelude::*,
+    value::join_key,
+};
+
+/// A parser for HOCON, the configuration format of Akka and Play
+/// `application.conf` files.
+///
+/// The document is resolved before it is turned into a `Value::Table`:
+/// * objects defined several times are merged, later fields winning;
+/// * `${path}` substitutions are replaced by the value at `path`, falling back
+///   to the environment variable of that name, and optional `${?path}`
+///   substitutions left unresolved are `Value::Null`;
+/// * `include "file.conf"` statements are replaced by the content of the file.
+///   Includes are only supported in files, read with `FileSource` or
+///   `HoconParser::parse_file`, and relative paths are resolved against the
+///   including file.
+#[derive(Debug)]
+pub struct HoconParser;
+
+impl HoconParser {
+    /// Parses the HOCON file at `path`, resolving its relative includes
+    /// against the directory of the file.
+    ///
+    /// # Errors
+    ///
+    /// Returns a parse error if the file can not be read, is not valid
+    /// HOCON, or a substitution can not be resolved.
+    pub fn parse_file<P: AsRef<Path>>(path: P) -> Result<Value, Error> {
+        let path = path.as_ref();
+        let origin = path.display().to_string();
+        HoconLoader::new()
+            .load_file(path)
+            .and_then(HoconLoader::hocon)
+            .map_err(|e| Error::new_parse_error(origin.clone(), e.to_string()))
+            .and_then(|hocon| {
+                to_value(hocon, "")
+                    .map_err(|e| Error::new_parse_error(origin, e))
+            })
+    }
+}
+
+impl<T: AsRef<str>> Parser<T> for HoconParser {
+    type Item = Value;
+    type Error = Error;
+
+    /// Parses a HOCON string into a `Value::Table`.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - A string-like type that can be converted to a string slice.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Self::Item, Self::Error>` - A Result containing either the
+    ///   parsed `Value` or a `Error`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use realme::prelude::*;
+    /// let hocon_str = r#"
+    /// defaults { timeout = 30, retries = 3 }
+    /// server = ${defaults} { timeout = 10 }
+    /// "#;
+    /// let value = HoconParser::parse(hocon_str).expect("parse hocon");
+    /// assert_eq!(value.get("server.timeout"), Some(&Value::Integer(10)));
+    /// assert_eq!(value.get("server.retries"), Some(&Value::Integer(3)));
+    /// ```
+    fn parse(args: T) -> Result<Self::Item, Self::Error> {
+        let args = args.as_ref().trim();
+        HoconLoader::new()
+            .load_str(args)
+            .and_then(HoconLoader::hocon)
+            .map_err(|e| e.to_string())
+            .and_then(|hocon| to_value(hocon, ""))
+            .map_err(|e| Error::new_parse_error(args.to_string(), e))
+    }
+
+    /// Parses the content of the HOCON file at `path`.
+    ///
+    /// A file with `include` statements is read again with
+    /// `HoconParser::parse_file`, so its includes are resolved against the
+    /// directory of the file.
+    fn parse_at(args: T, path: &Path) -> Result<Self::Item, Self::Error> {
+        let includes = args
+            .as_ref()
+            .lines()
+            .any(|line| line.trim_start().starts_with("include "));
+        if includes {
+            Self::parse_file(path)
+        } else {
+            Self::parse(args)
+        }
+    }
+}
+
+/// Converts a resolved HOCON document into a `Value`, failing on the first
+/// value that could not be resolved, named by its `path`.
+fn to_value(hocon: Hocon, path: &str) -> Result<Value, String> {
+    Ok(match hocon {
+        Hocon::Real(f) => Value::Float(f),
+        Hocon::Integer(i) => Value::Integer(i),
+        Hocon::String(s) => Value::String(s),
+        Hocon::Boolean(b) => Value::Boolean(b),
+        Hocon::Null => Value::Null,
+        Hocon::Array(values) => Value::Array(
+            values
+                .into_iter()
+                .enumerate()
+                .map(|(i, value)| to_value(value, &format!("{path}[{i}]")))
+                .collect::<Result<_, _>>()?,
+        ),
+        Hocon::Hash(fields) => {
+            let mut map = Map::new();
+            for (key, value) in fields {
+                let path = join_key(path, &key);
+                map.insert(key, to_value(value, &path)?);
+            }
+            Value::Table(map)
+        }
+        Hocon::BadValue(e) => return Err(format!("`{path}`: {e}")),
+    })
+}
@@ -15,6 +15,10 @@ impl<T: AsRef<str>> Parser<T> for IniParser {
     /// This function takes a string-like input, parses it as an INI format,
     /// and converts it into a nested structure of `Value::Table`.
     ///
+    /// Section headers are kept verbatim as keys, so headers such as
+    /// `[*.rs]` or `[Unit]` are preserved. Keys repeated within a section are
+    /// collected into a `Value::Array`.
+    ///
     /// # Arguments
     ///
     /// * `args` - A string-like type that represents the INI format content to
@@ -53,13 +57,32 @@ impl<T: AsRef<str>> Parser<T> for IniParser {
         let mut map = Map::new();
         for (sec, prop) in &i {
             if let Some(sec) = sec {
-                let mut inner: Map<String, Value> = Map::new();
-                for (k, v) in prop {
-                    inner.insert(k.to_owned(), Value::String(v.to_owned()));
+                if let Value::Table(inner) = map
+                    .entry(sec.to_owned())
+                    .or_insert_with(|| Value::Table(Map::new()))
+                {
+                    for (k, v) in prop {
+                        insert_property(inner, k, v);
+                    }
                 }
-                map.insert(sec.to_owned(), Value::Table(inner));
             }
         }
         Ok(Value::Table(map))
     }
 }
+
+/// Inserts a property into a section, collecting repeated keys into a
+/// `Value::Array` in the order they appear.
+fn insert_property(section: &mut Map<String, Value>, key: &str, value: &str) {
+    let value = Value::String(value.to_owned());
+    match section.get_mut(key) {
+        Some(Value::Array(values)) => values.push(value),
+        Some(existing) => {
+            let first = std::mem::replace(existing, Value::Null);
+            *existing = Value::Array(vec![first, value]);
+        }
+        None => {
+            section.insert(key.to_owned(), value);
+        }
+    }
+}
@@ -2,6 +2,19 @@
 ///
 /// This struct implements the `Parser` trait for parsing JSON strings into
 /// `serde_json::Value`.
+use std::{
+    borrow::Cow,
+    fmt,
+};
+
+use serde::de::{
+    self,
+    DeserializeSeed,
+    MapAccess,
+    SeqAccess,
+    Visitor,
+};
+
 use crate::{
     Error,
     prelude::*,
@@ -31,10 +44,282 @@ impl<T: AsRef<str>> Parser<T> for JsonParser {
     /// let result = JsonParser::parse(json_str);
     /// assert!(result.is_ok());
     /// ```
+    ///
+    /// Numbers out of the range of a `Value` are rejected, see
+    /// `LargeNumbers::Error`.
     fn parse(args: T) -> Result<Self::Item, Self::Error> {
         let args = args.as_ref().trim();
-        serde_json::from_str(args).map_err(|e| {
+        let json = replace_large_numbers(args, LargeNumbers::Error)?;
+        serde_json::from_str(&json).map_err(|e| {
             Error::new_parse_error(args.to_string(), e.to_string())
         })
     }
 }
+
+/// How duplicate keys within a JSON object are handled.
+///
+/// RFC 8259 leaves the meaning of duplicate keys undefined, and `JsonParser`
+/// silently keeps the last value like `serde_json` does.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DuplicateKeys {
+    /// Reject objects with duplicate keys.
+    Error,
+    /// Keep the value of the first occurrence.
+    KeepFirst,
+    /// Keep the value of the last occurrence.
+    #[default]
+    KeepLast,
+}
+
+impl Emitter for JsonParser {
+    /// Serializes a value into pretty printed JSON, see `Value::to_json`.
+    fn emit(value: &Value) -> crate::Result<String> {
+        value.to_json(JsonFormat::Pretty)
+    }
+}
+
+impl JsonParser {
+    /// Parses a JSON string, handling duplicate object keys according to
+    /// `duplicates`.
+    ///
+    /// With the `tracing` feature, a warning is emitted for every duplicate
+    /// key that is kept or dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the JSON is invalid, or if an object contains a
+    /// duplicate key and `duplicates` is `DuplicateKeys::Error`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use realme::prelude::*;
+    ///
+    /// let json = r#"{"port": 8080, "port": 9090}"#;
+    /// let value = JsonParser::parse_with(json, DuplicateKeys::KeepFirst)
+    ///     .expect("parse json");
+    /// assert_eq!(value["port"], 8080);
+    /// assert!(JsonParser::parse_with(json, DuplicateKeys::Error).is_err());
+    /// ```
+    pub fn parse_with(
+        args: &str,
+        duplicates: DuplicateKeys,
+    ) -> Result<serde_json::Value, Error> {
+        Self::parse_with_numbers(args, duplicates, LargeNumbers::Error)
+    }
+
+    /// Parses a JSON string, handling duplicate object keys according to
+    /// `duplicates` and numbers that do not fit a `Value` according to
+    /// `numbers`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the JSON is invalid, if an object contains a
+    /// duplicate key and `duplicates` is `DuplicateKeys::Error`, or if a
+    /// number is out of range and `numbers` is `LargeNumbers::Error`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use realme::prelude::*;
+    ///
+    /// let json = r#"{"id": 18446744073709551616}"#;
+    /// let value = JsonParser::parse_with_numbers(
+    ///     json,
+    ///     DuplicateKeys::KeepLast,
+    ///     LargeNumbers::String,
+    /// )
+    /// .expect("parse json");
+    /// assert_eq!(value["id"], "18446744073709551616");
+    /// ```
+    pub fn parse_with_numbers(
+        args: &str,
+        duplicates: DuplicateKeys,
+        numbers: LargeNumbers,
+    ) -> Result<serde_json::Value, Error> {
+        let args = args.trim();
+        let json = replace_large_numbers(args, numbers)?;
+        let mut deserializer = serde_json::Deserializer::from_str(&json);
+        JsonSeed(duplicates)
+            .deserialize(&mut deserializer)
+            .and_then(|value| deserializer.end().map(|()| value))
+            .map_err(|e| {
+                Error::new_parse_error(args.to_string(), e.to_string())
+            })
+    }
+}
+
+/// A parser for JSON data that rejects objects with duplicate keys.
+#[derive(Debug)]
+pub struct StrictJsonParser;
+
+impl<T: AsRef<str>> Parser<T> for StrictJsonParser {
+    type Item = serde_json::Value;
+    type Error = Error;
+
+    /// Parses a JSON string into a `serde_json::Value`, see
+    /// `JsonParser::parse_with` with `DuplicateKeys::Error`.
+    fn parse(args: T) -> Result<Self::Item, Self::Error> {
+        JsonParser::parse_with(args.as_ref(), DuplicateKeys::Error)
+    }
+}
+
+/// A parser for JSON data that keeps numbers out of the range of a `Value`
+/// as strings holding their literal, see `LargeNumbers::String`.
+#[derive(Debug)]
+pub struct LosslessJsonParser;
+
+impl<T: AsRef<str>> Parser<T> for LosslessJsonParser {
+    type Item = serde_json::Value;
+    type Error = Error;
+
+    /// Parses a JSON string into a `serde_json::Value`, see
+    /// `JsonParser::parse_with_numbers` with `LargeNumbers::String`.
+    fn parse(args: T) -> Result<Self::Item, Self::Error> {
+        JsonParser::parse_with_numbers(
+            args.as_ref(),
+            DuplicateKeys::KeepLast,
+            LargeNumbers::String,
+        )
+    }
+}
+
+/// Applies a `LargeNumbers` policy to the number literals of a JSON string,
+/// which `serde_json` would otherwise round to a float or reject.
+fn replace_large_numbers(
+    args: &str,
+    numbers: LargeNumbers,
+) -> Result<Cow<'_, str>, Error> {
+    let bytes = args.as_bytes();
+    let mut json = String::new();
+    let mut copied = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut i = 0;
+    while i < bytes.len() {
+        match (in_string, bytes[i]) {
+            (true, _) if escaped => escaped = false,
+            (true, b'\\') => escaped = true,
+            (_, b'"') => in_string = !in_string,
+            (false, b'-' | b'0'..=b'9') => {
+                let end = bytes[i..]
+                    .iter()
+                    .position(|b| {
+                        !matches!(
+                            b,
+                            b'0'..=b'9' | b'+' | b'-' | b'.' | b'e' | b'E'
+                        )
+                    })
+                    .map_or(bytes.len(), |len| i + len);
+                let replaced = numbers
+                    .check(&args[i..end])
+                    .map_err(|e| Error::new_parse_error(args.to_string(), e))?;
+                if let Some(replaced) = replaced {
+                    json.push_str(&args[copied..i]);
+                    json.push_str(&replaced);
+                    copied = end;
+                }
+                i = end;
+                continue;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    if copied == 0 {
+        return Ok(Cow::Borrowed(args));
+    }
+    json.push_str(&args[copied..]);
+    Ok(Cow::Owned(json))
+}
+
+/// Deserializes a `serde_json::Value`, applying a `DuplicateKeys` policy to
+/// every object.
+#[derive(Clone, Copy)]
+struct JsonSeed(DuplicateKeys);
+
+impl<'de> DeserializeSeed<'de> for JsonSeed {
+    type Value = serde_json::Value;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for JsonSeed {
+    type Value = serde_json::Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("any valid JSON value")
+    }
+
+    fn visit_bool<E>(self, value: bool) -> Result<Self::Value, E> {
+        Ok(value.into())
+    }
+
+    fn visit_i64<E>(self, value: i64) -> Result<Self::Value, E> {
+        Ok(value.into())
+    }
+
+    fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E> {
+        Ok(value.into())
+    }
+
+    fn visit_f64<E>(self, value: f64) -> Result<Self::Value, E> {
+        Ok(value.into())
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E> {
+        Ok(value.into())
+    }
+
+    fn visit_string<E>(self, value: String) -> Result<Self::Value, E> {
+        Ok(value.into())
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E> {
+        Ok(serde_json::Value::Null)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut array = Vec::new();
+        while let Some(value) = seq.next_element_seed(self)? {
+            array.push(value);
+        }
+        Ok(serde_json::Value::Array(array))
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut object = serde_json::Map::new();
+        while let Some(key) = map.next_key::<String>()? {
+            let value = map.next_value_seed(self)?;
+            if !object.contains_key(&key) {
+                object.insert(key, value);
+                continue;
+            }
+            #[cfg(feature = "tracing")]
+            tracing::warn!(key = %key, policy = ?self.0, "duplicate JSON key");
+            match self.0 {
+                DuplicateKeys::Error => {
+                    return Err(de::Error::custom(format!(
+                        "duplicate key `{key}`"
+                    )));
+                }
+                DuplicateKeys::KeepFirst => {}
+                DuplicateKeys::KeepLast => {
+                    object.insert(key, value);
+                }
+            }
+        }
+        Ok(serde_json::Value::Object(object))
+    }
+}
@@ -0,0 +1,177 @@
+use std::collections::HashSet;
+
+use kdl::{
+    KdlDocument,
+    KdlNode,
+    KdlValue,
+};
+
+use crate::{
+    Error,
+    prelude::*,
+    value::join_key,
+};
+
+/// Key holding the arguments of a node that is read into a table, and name
+/// of the children read as the elements of an array.
+const DASH: &str = "-";
+
+/// A parser for KDL documents, version 2 of the language.
+///
+/// Nodes are read into a `Value::Table` keyed by their names, with the
+/// following convention:
+/// * a node without arguments, properties or children is `Value::Null`;
+/// * a node with only arguments is its argument, or an array of them when it
+///   has several, so `port 8080` is `{ port: 8080 }` and `tags a b` is `{ tags:
+///   ["a", "b"] }`;
+/// * a node with properties or children is a table of its properties and
+///   children, its arguments, if any, being kept under the key `-`, so `server
+///   web host=localhost { port 80 }` is `{ server: { "-": "web", host:
+///   "localhost", port: 80 } }`;
+/// * a node whose children are all named `-` is an array of its arguments
+///   followed by the values of the children, so `hosts { - a; - b }` is `{
+///   hosts: ["a", "b"] }`;
+/// * nodes repeating a name are collected into an array, in order.
+///
+/// A property repeated on a node keeps its last value, and type annotations
+/// such as `(date)"2024-01-01"` are ignored.
+#[derive(Debug)]
+pub struct KdlParser;
+
+impl<T: AsRef<str>> Parser<T> for KdlParser {
+    type Item = Value;
+    type Error = Error;
+
+    /// Parses a KDL document into a `Value::Table`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a parse error if the document is not valid KDL, or holds an
+    /// integer out of the range of `i64`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use realme::prelude::*;
+    /// let kdl_str = r#"
+    /// name realme
+    /// server web host=localhost {
+    ///     port 8080
+    /// }
+    /// "#;
+    /// let value = KdlParser::parse(kdl_str).expect("parse kdl");
+    /// assert_eq!(value.get("server.-"), Some(&Value::from("web")));
+    /// assert_eq!(value.get("server.port"), Some(&Value::Integer(8080)));
+    /// ```
+    fn parse(args: T) -> Result<Self::Item, Self::Error> {
+        let args = args.as_ref();
+        KdlDocument::parse_v2(args)
+            .map_err(|e| e.to_string())
+            .and_then(|document| read_document(&document, ""))
+            .map(Value::Table)
+            .map_err(|e| Error::new_parse_error(args.to_string(), e))
+    }
+}
+
+/// Reads the nodes of `document`, found at `path`, into a table.
+fn read_document(
+    document: &KdlDocument,
+    path: &str,
+) -> Result<Map<String, Value>, String> {
+    let mut map = Map::new();
+    let mut repeated = HashSet::new();
+    for node in document.nodes() {
+        let name = node.name().value();
+        let value = read_node(node, &join_key(path, name))?;
+        insert(&mut map, &mut repeated, name, value);
+    }
+    Ok(map)
+}
+
+/// Reads a node, found at `path`, following the convention of `KdlParser`.
+fn read_node(node: &KdlNode, path: &str) -> Result<Value, String> {
+    let mut args = Vec::new();
+    let mut props = Vec::new();
+    for entry in node.entries() {
+        let value = read_value(entry.value(), path)?;
+        match entry.name() {
+            Some(name) => props.push((name.value(), value)),
+            None => args.push(value),
+        }
+    }
+    let children = node.children().map(KdlDocument::nodes).unwrap_or_default();
+
+    if props.is_empty() &&
+        !children.is_empty() &&
+        children.iter().all(|child| child.name().value() == DASH)
+    {
+        for (i, child) in children.iter().enumerate() {
+            args.push(read_node(child, &format!("{path}[{i}]"))?);
+        }
+        return Ok(Value::Array(args));
+    }
+    if props.is_empty() && children.is_empty() {
+        return Ok(collapse(args).unwrap_or(Value::Null));
+    }
+
+    let mut map = Map::new();
+    if let Some(args) = collapse(args) {
+        map.insert(DASH.to_string(), args);
+    }
+    for (name, value) in props {
+        map.insert(name.to_string(), value);
+    }
+    if let Some(children) = node.children() {
+        let mut repeated = HashSet::new();
+        for (name, value) in read_document(children, path)? {
+            insert(&mut map, &mut repeated, &name, value);
+        }
+    }
+    Ok(Value::Table(map))
+}
+
+/// Returns the only argument of a node, an array of its arguments when it
+/// has several, or `None` without any.
+fn collapse(mut args: Vec<Value>) -> Option<Value> {
+    match args.len() {
+        0 => None,
+        1 => args.pop(),
+        _ => Some(Value::Array(args)),
+    }
+}
+
+/// Converts a KDL value, found at `path`, into a `Value`.
+fn read_value(value: &KdlValue, path: &str) -> Result<Value, String> {
+    Ok(match value {
+        KdlValue::String(s) => Value::String(s.clone()),
+        KdlValue::Integer(i) => {
+            Value::Integer(i64::try_from(*i).map_err(|_e| {
+                format!("`{path}`: integer {i} is out of range for i64")
+            })?)
+        }
+        KdlValue::Float(f) => Value::Float(*f),
+        KdlValue::Bool(b) => Value::Boolean(*b),
+        KdlValue::Null => Value::Null,
+    })
+}
+
+/// Inserts `value` at `name`, collecting the values of a repeated name into
+/// an array.
+fn insert(
+    map: &mut Map<String, Value>,
+    repeated: &mut HashSet<String>,
+    name: &str,
+    value: Value,
+) {
+    let Some(existing) = map.get_mut(name) else {
+        map.insert(name.to_string(), value);
+        return;
+    };
+    match existing {
+        Value::Array(values) if repeated.contains(name) => values.push(value),
+        _ => {
+            repeated.insert(name.to_string());
+            let first = std::mem::replace(existing, Value::Null);
+            *existing = Value::Array(vec![first, value]);
+        }
+    }
+}
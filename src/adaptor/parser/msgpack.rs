@@ -0,0 +1,46 @@
+use crate::{
+    Error,
+    prelude::*,
+};
+
+/// A parser for `MessagePack`, a binary serialization format.
+///
+/// Unlike the text parsers, it parses bytes, so it is used with
+/// `BytesSource` rather than `FileSource` or `StringSource`. Maps must be
+/// keyed by strings, so snapshots written with `rmp_serde` should use
+/// `rmp_serde::to_vec_named`, which keeps the field names of structs.
+/// Binary and extension values are not supported.
+#[derive(Debug)]
+pub struct MsgPackParser;
+
+impl<T: AsRef<[u8]>> Parser<T> for MsgPackParser {
+    type Item = Value;
+    type Error = Error;
+
+    /// Parses `MessagePack` bytes into a `Value`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a parse error if the bytes are not valid `MessagePack`, or hold
+    /// a map key that is not a string, an integer out of the range of `i64`
+    /// or a binary value.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use realme::prelude::*;
+    /// // {"name": "realme", "port": 8080}
+    /// let bytes = b"\x82\xa4name\xa6realme\xa4port\xcd\x1f\x90";
+    /// let value = MsgPackParser::parse(bytes).expect("parse msgpack");
+    /// assert_eq!(value.get("name"), Some(&Value::from("realme")));
+    /// assert_eq!(value.get("port"), Some(&Value::Integer(8080)));
+    /// ```
+    fn parse(args: T) -> Result<Self::Item, Self::Error> {
+        let args = args.as_ref();
+        rmp_serde::from_slice(args).map_err(|e| {
+            Error::new_parse_error(
+                format!("{} bytes of MessagePack", args.len()),
+                e.to_string(),
+            )
+        })
+    }
+}
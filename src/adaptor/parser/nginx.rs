@@ -0,0 +1,224 @@
+use crate::{
+    Error,
+    prelude::*,
+};
+
+/// A parser for Nginx and Apache style directive configuration.
+#[derive(Debug)]
+pub struct NginxParser;
+
+impl<T: AsRef<str>> Parser<T> for NginxParser {
+    type Item = Value;
+    type Error = Error;
+
+    /// Parses directive configuration into a `Value::Table`.
+    ///
+    /// Each directive `name arg...;` becomes a key holding its arguments: a
+    /// single argument is kept as a `Value::String`, several arguments form a
+    /// `Value::Array` and a directive without argument is a flag set to
+    /// `true`. Blocks `name { ... }` become nested tables, and blocks with
+    /// arguments such as `location /api { ... }` are nested under their
+    /// arguments. Directives or blocks repeated at the same level are
+    /// collected into a `Value::Array`. Comments start with `#`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a directive is not terminated by `;`, a block is
+    /// not closed or a quoted string is not terminated.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use realme::prelude::*;
+    ///
+    /// let conf = "http { server { listen 80; } }";
+    /// let value = NginxParser::parse(conf).expect("parse nginx config");
+    /// assert_eq!(
+    ///     value.get("http.server.listen"),
+    ///     Some(&Value::String("80".to_string()))
+    /// );
+    /// ```
+    fn parse(args: T) -> Result<Self::Item, Self::Error> {
+        let args = args.as_ref();
+        let parse_error =
+            |cause: String| Error::new_parse_error(args.to_string(), cause);
+        let mut tokens = tokenize(args).map_err(parse_error)?.into_iter();
+        let map = parse_block(&mut tokens, None).map_err(parse_error)?;
+        Ok(Value::Table(map))
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Word(String),
+    Semicolon,
+    Open,
+    Close,
+}
+
+/// Splits the input into tokens, each paired with its line number.
+fn tokenize(input: &str) -> Result<Vec<(usize, Token)>, String> {
+    let mut tokens = Vec::new();
+    let mut line = 1;
+    let mut chars = input.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\n' => line += 1,
+            c if c.is_whitespace() => {}
+            '#' => while chars.next_if(|c| *c != '\n').is_some() {},
+            ';' => tokens.push((line, Token::Semicolon)),
+            '{' => tokens.push((line, Token::Open)),
+            '}' => tokens.push((line, Token::Close)),
+            '"' | '\'' => {
+                let start = line;
+                let mut word = String::new();
+                loop {
+                    match chars.next() {
+                        Some(q) if q == c => break,
+                        Some('\\') => word.extend(chars.next()),
+                        Some(ch) => {
+                            if ch == '\n' {
+                                line += 1;
+                            }
+                            word.push(ch);
+                        }
+                        None => {
+                            return Err(format!(
+                                "unterminated string starting at line {start}"
+                            ));
+                        }
+                    }
+                }
+                tokens.push((start, Token::Word(word)));
+            }
+            c => {
+                let mut word = c.to_string();
+                while let Some(ch) = chars.next_if(|ch| {
+                    !ch.is_whitespace() && !matches!(ch, ';' | '{' | '}' | '#')
+                }) {
+                    word.push(ch);
+                }
+                tokens.push((line, Token::Word(word)));
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+/// Occurrences of the directives of a block, collected before repeated
+/// directives are turned into arrays.
+#[derive(Default)]
+struct Directives {
+    /// Values of directives and blocks without arguments.
+    plain:     Map<String, Vec<Value>>,
+    /// Blocks with arguments, by name then by arguments.
+    arguments: Map<String, Map<String, Vec<Value>>>,
+}
+
+impl Directives {
+    fn into_table(self) -> Result<Map<String, Value>, String> {
+        let mut map: Map<String, Value> = self
+            .plain
+            .into_iter()
+            .map(|(name, values)| (name, collapse(values)))
+            .collect();
+        for (name, blocks) in self.arguments {
+            if map.contains_key(&name) {
+                return Err(format!(
+                    "`{name}` is used both with and without block arguments"
+                ));
+            }
+            let blocks = blocks
+                .into_iter()
+                .map(|(args, values)| (args, collapse(values)))
+                .collect();
+            map.insert(name, Value::Table(blocks));
+        }
+        Ok(map)
+    }
+}
+
+/// Turns the occurrences of a directive into a single value, or an array
+/// when it is repeated.
+fn collapse(mut values: Vec<Value>) -> Value {
+    if values.len() == 1 {
+        values.remove(0)
+    } else {
+        Value::Array(values)
+    }
+}
+
+/// Parses directives until the end of the input, or until the closing brace
+/// of the block opened at line `opened_at`.
+fn parse_block<I>(
+    tokens: &mut I,
+    opened_at: Option<usize>,
+) -> Result<Map<String, Value>, String>
+where
+    I: Iterator<Item = (usize, Token)>,
+{
+    let mut directives = Directives::default();
+    loop {
+        let (line, name) = match tokens.next() {
+            Some((line, Token::Word(name))) => (line, name),
+            Some((_, Token::Close)) if opened_at.is_some() => {
+                return directives.into_table();
+            }
+            Some((line, token)) => {
+                return Err(format!("unexpected {token:?} at line {line}"));
+            }
+            None => {
+                return match opened_at {
+                    Some(line) => Err(format!(
+                        "block opened at line {line} is not closed"
+                    )),
+                    None => directives.into_table(),
+                };
+            }
+        };
+        let mut args = Vec::new();
+        loop {
+            match tokens.next() {
+                Some((_, Token::Word(arg))) => args.push(arg),
+                Some((_, Token::Semicolon)) => {
+                    directives
+                        .plain
+                        .entry(name)
+                        .or_default()
+                        .push(directive_value(args));
+                    break;
+                }
+                Some((open, Token::Open)) => {
+                    let block = Value::Table(parse_block(tokens, Some(open))?);
+                    if args.is_empty() {
+                        directives.plain.entry(name).or_default().push(block);
+                    } else {
+                        directives
+                            .arguments
+                            .entry(name)
+                            .or_default()
+                            .entry(args.join(" "))
+                            .or_default()
+                            .push(block);
+                    }
+                    break;
+                }
+                Some((_, Token::Close)) | None => {
+                    return Err(format!(
+                        "directive `{name}` at line {line} is not terminated \
+                         by `;`"
+                    ));
+                }
+            }
+        }
+    }
+}
+
+/// Converts the arguments of a directive into its value.
+fn directive_value(mut args: Vec<String>) -> Value {
+    match args.len() {
+        0 => Value::Boolean(true),
+        1 => Value::String(args.remove(0)),
+        _ => Value::Array(args.into_iter().map(Value::String).collect()),
+    }
+}
@@ -0,0 +1,198 @@
+use crate::{
+    Error,
+    prelude::*,
+    value::insert_nested,
+};
+
+/// A parser for Java `.properties` files.
+///
+/// Keys are split on `.` into nested tables, so `server.port=8080` becomes
+/// the key `port` of the table `server`, and every value is a
+/// `Value::String`. A key nesting under a key holding a string is dropped.
+#[derive(Debug)]
+pub struct PropertiesParser;
+
+impl<T: AsRef<str>> Parser<T> for PropertiesParser {
+    type Item = Value;
+    type Error = Error;
+
+    /// Parses the content of a `.properties` file into nested
+    /// `Value::Table`s.
+    ///
+    /// The format follows `java.util.Properties`:
+    /// * lines whose first non-blank character is `#` or `!` are comments;
+    /// * a key ends at the first unescaped `=`, `:` or whitespace, and the
+    ///   value is the rest of the line, leading whitespace trimmed;
+    /// * a line ending with an odd number of `\` continues on the next line,
+    ///   whose leading whitespace is skipped;
+    /// * `\t`, `\n`, `\r`, `\f` and `\uXXXX` are escapes, and a `\` before any
+    ///   other character keeps that character, as in `\=` or `\ `.
+    ///
+    /// A key repeated later in the file overrides the earlier one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the line if a `\uXXXX` escape is malformed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use realme::prelude::*;
+    ///
+    /// let properties = "# server\nserver.host = localhost\nserver.port: 8080";
+    /// let value = PropertiesParser::parse(properties).expect("parse");
+    /// assert_eq!(
+    ///     value.get("server.host"),
+    ///     Some(&Value::String("localhost".into()))
+    /// );
+    /// assert_eq!(
+    ///     value.get("server.port"),
+    ///     Some(&Value::String("8080".into()))
+    /// );
+    /// ```
+    fn parse(args: T) -> Result<Self::Item, Self::Error> {
+        let args = args.as_ref();
+        let pairs = parse_pairs(args)
+            .map_err(|e| Error::new_parse_error(args.to_string(), e))?;
+        let mut map = Map::new();
+        for (key, value) in pairs {
+            let path: Vec<&str> =
+                key.split('.').filter(|part| !part.is_empty()).collect();
+            insert_nested(&mut map, &path, Value::String(value));
+        }
+        Ok(Value::Table(map))
+    }
+}
+
+/// Parses the key-value pairs of a `.properties` file in order.
+fn parse_pairs(input: &str) -> Result<Vec<(String, String)>, String> {
+    let mut pairs = Vec::new();
+    let mut lines = input.lines().enumerate();
+    while let Some((index, line)) = lines.next() {
+        let line = line.trim_start_matches(is_blank);
+        if line.is_empty() || line.starts_with(['#', '!']) {
+            continue;
+        }
+        let line_no = index + 1;
+        let mut logical = String::new();
+        let mut current = line;
+        while let Some(stripped) = continued(current) {
+            logical.push_str(stripped);
+            let Some((_, next)) = lines.next() else {
+                current = "";
+                break;
+            };
+            current = next.trim_start_matches(is_blank);
+        }
+        logical.push_str(current);
+        pairs.push(parse_pair(&logical, line_no)?);
+    }
+    Ok(pairs)
+}
+
+/// Returns `line` without its trailing `\` if it continues on the next
+/// line, that is if it ends with an odd number of `\`.
+fn continued(line: &str) -> Option<&str> {
+    let backslashes = line.len() - line.trim_end_matches('\\').len();
+    (backslashes % 2 == 1).then(|| &line[..line.len() - 1])
+}
+
+/// Splits a logical line into its key and value.
+fn parse_pair(line: &str, line_no: usize) -> Result<(String, String), String> {
+    let chars = unescape(line, line_no)?;
+    let mut rest = chars.as_slice();
+    let key_len = rest
+        .iter()
+        .position(|&(c, escaped)| {
+            !escaped && (c == '=' || c == ':' || is_blank(c))
+        })
+        .unwrap_or(rest.len());
+    let key = rest[..key_len].iter().map(|&(c, _)| c).collect();
+    rest = &rest[key_len..];
+    rest = skip_blanks(rest);
+    if let Some(((':' | '=', false), tail)) = rest.split_first() {
+        rest = skip_blanks(tail);
+    }
+    let value = rest.iter().map(|&(c, _)| c).collect();
+    Ok((key, value))
+}
+
+/// Skips the unescaped whitespace at the start of `chars`.
+fn skip_blanks(chars: &[(char, bool)]) -> &[(char, bool)] {
+    let start = chars
+        .iter()
+        .position(|&(c, escaped)| escaped || !is_blank(c))
+        .unwrap_or(chars.len());
+    &chars[start..]
+}
+
+/// Resolves the escapes of a logical line, returning each character with
+/// whether it was escaped.
+fn unescape(line: &str, line_no: usize) -> Result<Vec<(char, bool)>, String> {
+    let mut chars = Vec::with_capacity(line.len());
+    let mut iter = line.chars();
+    while let Some(c) = iter.next() {
+        if c != '\\' {
+            chars.push((c, false));
+            continue;
+        }
+        let escaped = match iter.next() {
+            Some('t') => '\t',
+            Some('n') => '\n',
+            Some('r') => '\r',
+            Some('f') => '\u{c}',
+            Some('u') => {
+                let unit = parse_unit(&mut iter, line_no)?;
+                decode_unit(unit, &mut iter, line_no)?
+            }
+            Some(other) => other,
+            None => break,
+        };
+        chars.push((escaped, true));
+    }
+    Ok(chars)
+}
+
+/// Parses the four hex digits of a `\uXXXX` escape.
+fn parse_unit(
+    iter: &mut std::str::Chars<'_>,
+    line_no: usize,
+) -> Result<u16, String> {
+    let digits: String = iter.by_ref().take(4).collect();
+    if digits.len() == 4 && digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        if let Ok(unit) = u16::from_str_radix(&digits, 16) {
+            return Ok(unit);
+        }
+    }
+    Err(format!("line {line_no}: malformed \\u{digits} escape"))
+}
+
+/// Decodes a UTF-16 code unit, reading the low surrogate of a pair from a
+/// following `\uXXXX` escape.
+fn decode_unit(
+    unit: u16,
+    iter: &mut std::str::Chars<'_>,
+    line_no: usize,
+) -> Result<char, String> {
+    if let Some(c) = char::from_u32(u32::from(unit)) {
+        return Ok(c);
+    }
+    let low = if iter.as_str().starts_with("\\u") {
+        iter.nth(1);
+        Some(parse_unit(iter, line_no)?)
+    } else {
+        None
+    };
+    char::decode_utf16(std::iter::once(unit).chain(low))
+        .next()
+        .and_then(Result::ok)
+        .ok_or_else(|| {
+            format!("line {line_no}: unpaired surrogate \\u{unit:04X}")
+        })
+}
+
+/// Returns whether `c` separates a key from its value, as in
+/// `java.util.Properties`.
+const fn is_blank(c: char) -> bool {
+    matches!(c, ' ' | '\t' | '\u{c}')
+}
@@ -2,8 +2,13 @@
 ///
 /// This struct implements the `Parser` trait for parsing TOML strings into
 /// `toml::Value` objects.
+use std::borrow::Cow;
+
+use serde::ser::Error as _;
+
 use crate::{
     Error,
+    errors::SerializeError,
     prelude::*,
 };
 
@@ -32,10 +37,143 @@ impl<T: AsRef<str>> Parser<T> for TomlParser {
     /// let result = TomlParser::parse(toml_str);
     /// assert!(result.is_ok());
     /// ```
+    ///
+    /// Numbers out of the range of a `Value` are rejected, see
+    /// `LargeNumbers::Error`.
+    fn parse(args: T) -> Result<Self::Item, Self::Error> {
+        Self::parse_with(args.as_ref(), LargeNumbers::Error)
+    }
+}
+
+impl Emitter for TomlParser {
+    /// Serializes a table into a TOML document, with its keys sorted.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::SerializeError` if the value is not a table, or
+    /// naming the key of the first null found, since TOML has no null.
+    fn emit(value: &Value) -> crate::Result<String> {
+        if let Some((path, ..)) =
+            value.leaves().find(|(_, kind, _)| *kind == ValueKind::Null)
+        {
+            return Err(SerializeError::custom(format!(
+                "`{path}` is null and can not be written as TOML"
+            ))
+            .into());
+        }
+        if !matches!(value, Value::Table(_)) {
+            return Err(SerializeError::custom(format!(
+                "a TOML document must be a table, got {}",
+                value.value_type()
+            ))
+            .into());
+        }
+        toml::to_string_pretty(&to_toml(value)?)
+            .map_err(|e| SerializeError::custom(e.to_string()).into())
+    }
+}
+
+/// Converts a `Value` without null into a TOML value, keeping datetimes.
+fn to_toml(value: &Value) -> crate::Result<toml::Value> {
+    Ok(match value {
+        Value::Null => {
+            return Err(SerializeError::custom("TOML has no null").into());
+        }
+        Value::Boolean(b) => toml::Value::Boolean(*b),
+        Value::Integer(i) => toml::Value::Integer(*i),
+        Value::Float(f) => toml::Value::Float(*f),
+        Value::String(s) => toml::Value::String(s.clone()),
+        Value::Datetime(d) => toml::Value::Datetime(
+            d.as_str()
+                .parse()
+                .map_err(|e| SerializeError::custom(format!("{e}")))?,
+        ),
+        Value::Array(array) => toml::Value::Array(
+            array.iter().map(to_toml).collect::<crate::Result<_>>()?,
+        ),
+        Value::Table(table) => toml::Value::Table(
+            table
+                .iter()
+                .map(|(key, value)| Ok((key.clone(), to_toml(value)?)))
+                .collect::<crate::Result<_>>()?,
+        ),
+    })
+}
+
+impl TomlParser {
+    /// Parses a TOML string, handling numbers that do not fit a `Value`
+    /// according to `numbers`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the TOML is invalid, or if a number is out of
+    /// range and `numbers` is `LargeNumbers::Error`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use realme::prelude::*;
+    ///
+    /// let toml = "id = 18446744073709551616";
+    /// let value =
+    ///     TomlParser::parse_with(toml, LargeNumbers::String).expect("parse toml");
+    /// assert_eq!(value["id"].as_str(), Some("18446744073709551616"));
+    /// assert!(TomlParser::parse_with(toml, LargeNumbers::Error).is_err());
+    /// ```
+    pub fn parse_with(
+        args: &str,
+        numbers: LargeNumbers,
+    ) -> Result<toml::Value, Error> {
+        let args = args.trim();
+        let mut toml = Cow::Borrowed(args);
+        loop {
+            let err = match toml::from_str(&toml) {
+                Ok(value) => return Ok(value),
+                Err(err) => err,
+            };
+            // `toml` rejects out of range numbers, pointing at the literal,
+            // which is replaced before parsing again.
+            let Some(start) = err.span().map(|span| span.start) else {
+                return Err(Error::new_parse_error(
+                    args.to_string(),
+                    err.to_string(),
+                ));
+            };
+            let end = toml[start..]
+                .find(|c: char| {
+                    !matches!(c, '0'..='9' | '+' | '-' | '.' | '_' | 'e' | 'E')
+                })
+                .map_or(toml.len(), |len| start + len);
+            let replaced = numbers
+                .check(&toml[start..end])
+                .map_err(|e| Error::new_parse_error(args.to_string(), e))?;
+            let Some(replaced) = replaced else {
+                return Err(Error::new_parse_error(
+                    args.to_string(),
+                    err.to_string(),
+                ));
+            };
+            toml = Cow::Owned(format!(
+                "{}{replaced}{}",
+                &toml[..start],
+                &toml[end..]
+            ));
+        }
+    }
+}
+
+/// A parser for TOML data that keeps numbers out of the range of a `Value`
+/// as strings holding their literal, see `LargeNumbers::String`.
+#[derive(Debug)]
+pub struct LosslessTomlParser;
+
+impl<T: AsRef<str>> Parser<T> for LosslessTomlParser {
+    type Item = toml::Value;
+    type Error = Error;
+
+    /// Parses a TOML string into a `toml::Value`, see
+    /// `TomlParser::parse_with` with `LargeNumbers::String`.
     fn parse(args: T) -> Result<Self::Item, Self::Error> {
-        let args = args.as_ref().trim();
-        toml::from_str(args).map_err(|e| {
-            Error::new_parse_error(args.to_string(), e.to_string())
-        })
+        TomlParser::parse_with(args.as_ref(), LargeNumbers::String)
     }
 }
@@ -1,8 +1,16 @@
 /// A parser for YAML format.
 ///
 /// This struct implements the `Parser` trait for YAML parsing.
+use serde::ser::Error as _;
+use yaml_rust2::{
+    Yaml,
+    YamlEmitter,
+    yaml::Hash,
+};
+
 use crate::{
     Error,
+    errors::SerializeError,
     prelude::*,
 };
 
@@ -38,3 +46,49 @@ impl<T: AsRef<str>> Parser<T> for YamlParser {
         })
     }
 }
+
+impl Emitter for YamlParser {
+    /// Serializes a value into a YAML document, with the keys of tables
+    /// sorted.
+    fn emit(value: &Value) -> crate::Result<String> {
+        let mut yaml = String::new();
+        YamlEmitter::new(&mut yaml)
+            .dump(&to_yaml(value))
+            .map_err(|e| SerializeError::custom(format!("{e:?}")))?;
+        yaml.push('\n');
+        Ok(yaml)
+    }
+}
+
+/// Converts a `Value` into a YAML node, sorting the keys of tables.
+fn to_yaml(value: &Value) -> Yaml {
+    match value {
+        Value::Null => Yaml::Null,
+        Value::Boolean(b) => Yaml::Boolean(*b),
+        Value::Integer(i) => Yaml::Integer(*i),
+        Value::Float(f) if f.is_nan() => Yaml::Real(".nan".to_string()),
+        Value::Float(f) if f.is_infinite() => Yaml::Real(
+            if f.is_sign_positive() {
+                ".inf"
+            } else {
+                "-.inf"
+            }
+            .to_string(),
+        ),
+        Value::Float(f) => Yaml::Real(format!("{f:?}")),
+        Value::String(s) => Yaml::String(s.clone()),
+        // The emitter writes reals verbatim, which keeps the datetime a plain
+        // timestamp scalar where a string holding `:` would be quoted.
+        Value::Datetime(d) => Yaml::Real(d.to_string()),
+        Value::Array(arr) => Yaml::Array(arr.iter().map(to_yaml).collect()),
+        Value::Table(table) => {
+            let mut entries: Vec<_> = table.iter().collect();
+            entries.sort_by_key(|(key, _)| *key);
+            let mut hash = Hash::new();
+            for (key, value) in entries {
+                hash.insert(Yaml::String(key.clone()), to_yaml(value));
+            }
+            Yaml::Hash(hash)
+        }
+    }
+}
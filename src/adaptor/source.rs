@@ -1,20 +1,56 @@
+/// Module for archive-related functionality
+#[cfg(feature = "archive")]
+pub mod archive;
+/// Module for byte-related functionality
+pub mod bytes;
+/// Module for caching source wrapper
+pub mod cached;
 /// Module for command-related functionality
 #[cfg(feature = "cmd")]
 pub mod cmd;
+/// Module for directory-related functionality
+pub mod dir;
 /// Module for environment-related functionality
 #[cfg(feature = "env")]
 pub mod env;
 /// Module for file-related functionality
 pub mod file;
+/// Module for git-related functionality
+#[cfg(feature = "git")]
+pub mod git;
+/// Module for HTTP-related functionality
+#[cfg(feature = "http")]
+pub mod http;
+/// Module for keyring-related functionality
+#[cfg(feature = "keyring")]
+pub mod keyring;
+/// Module for Kubernetes volume-related functionality
+#[cfg(feature = "kube")]
+pub mod kube;
 /// Module for ser-related functionality
 pub mod ser;
 /// Module for string-related functionality
 pub mod string;
+/// Module for Vault-related functionality
+#[cfg(feature = "vault")]
+pub mod vault;
 
-use std::fmt::Debug;
+use std::{
+    fmt::Debug,
+    path::{
+        Path,
+        PathBuf,
+    },
+};
 
 use serde::Serialize;
 
+use crate::{
+    Error,
+    MergeStrategy,
+    Value,
+};
+
 /// Trait representing a source of configuration or data
 pub trait Source: Send + Sync + Debug {
     type Error;
@@ -26,6 +62,63 @@ pub trait Source: Send + Sync + Debug {
     /// - `Err(Error)` if an error occurs during parsing
     fn parse(&self) -> Result<Self::Value, Self::Error>;
 
+    /// Parses the source, merging the files it reads with `merge`.
+    ///
+    /// Adaptors parse their source with this and their `MergeStrategy`.
+    /// Only sources reading several files, such as `DirSource`, use it; the
+    /// others are parsed with `Source::parse`.
+    fn parse_merged(
+        &self,
+        _merge: MergeStrategy,
+    ) -> Result<Self::Value, Self::Error> {
+        self.parse()
+    }
+
+    /// Returns the path of the file that provided `key` in the last parse,
+    /// for sources reading several files such as `DirSource`.
+    ///
+    /// This is what `Realme::origin` reports as the path of the key, rather
+    /// than `Source::path`.
+    fn key_path(&self, _key: &str) -> Option<PathBuf> {
+        None
+    }
+
+    /// Returns whether the values of the source are sensitive.
+    ///
+    /// The keys provided by a sensitive source can be read with `get` and
+    /// `try_deserialize`, but are left out of every export of the
+    /// configuration, see `Realme::redacted`.
+    fn is_sensitive(&self) -> bool {
+        false
+    }
+
+    /// Returns whether the source changed since it was last parsed.
+    ///
+    /// `None` means the source can not tell, in which case
+    /// `Realme::reload_if_changed` assumes it changed.
+    fn has_changed(&self) -> Option<bool> {
+        None
+    }
+
+    /// Returns the path of the file the source reads, if any.
+    ///
+    /// Files included by the source, see `RealmeBuilder::enable_includes`,
+    /// are resolved relative to the directory of this path.
+    fn path(&self) -> Option<&Path> {
+        None
+    }
+
+    /// Parses the file at `path` the way the source parses its own file.
+    ///
+    /// This is used to load included files. Sources that do not read files
+    /// return `None`.
+    fn parse_path(
+        &self,
+        _path: &Path,
+    ) -> Option<Result<Self::Value, Self::Error>> {
+        None
+    }
+
     #[cfg(feature = "watch")]
     /// Watch the source for changes
     fn watcher(
@@ -34,6 +127,64 @@ pub trait Source: Send + Sync + Debug {
     ) -> Result<(), Self::Error>;
 }
 
+/// Watches the file or directory at `path`, sending `()` to `s` on every
+/// change from a new thread. Directories are watched without their
+/// subdirectories.
+///
+/// The watch is registered before returning, so changes made right after
+/// are not missed. Failing to register it is logged, not returned.
+#[cfg(feature = "watch")]
+pub(crate) fn watch_path(path: &Path, s: crossbeam::channel::Sender<()>) {
+    let (tx, rx) = crossbeam::channel::unbounded();
+
+    let watcher = notify::recommended_watcher(
+        move |res: notify::Result<notify::Event>| {
+            if let Ok(event) = res {
+                #[allow(unused_variables)]
+                if let Err(e) = tx.send(event) {
+                    #[cfg(feature = "tracing")]
+                    tracing::error!("Send event error: {:?}", e);
+                }
+            }
+        },
+    )
+    .and_then(|mut watcher| {
+        notify::Watcher::watch(
+            &mut watcher,
+            path,
+            notify::RecursiveMode::NonRecursive,
+        )
+        .map(|()| watcher)
+    });
+    #[allow(unused_variables)]
+    let watcher = match watcher {
+        Ok(watcher) => watcher,
+        Err(e) => {
+            #[cfg(feature = "tracing")]
+            tracing::error!("Watcher error: {:?}", e);
+            return;
+        }
+    };
+
+    std::thread::spawn(move || {
+        // Keeps the watcher alive as long as events are forwarded
+        let _watcher = watcher;
+        while let Ok(_event) = rx.recv() {
+            #[allow(unused_variables)]
+            if let Err(e) = s.send(()) {
+                #[cfg(feature = "tracing")]
+                tracing::error!("Send event error: {:?}", e);
+            }
+        }
+    });
+}
+
+/// Ties a source to the parser `P` used to parse its content.
+///
+/// This allows `RealmeBuilder::layer` to name the parser of each layer
+/// explicitly, e.g. `layer::<TomlParser>(FileSource::new("base.toml"))`.
+pub trait ParsedBy<P>: Source<Error = Error, Value = Value> {}
+
 #[macro_export]
 macro_rules! source_debug {
     ($source_type:ident < $($gen:ident),+ >) => {
@@ -51,3 +202,13 @@ macro_rules! source_debug {
         }
     };
 }
+
+/// Returns whether the transport error `e` means the host could not be
+/// reached, rather than a failure while talking to it.
+#[cfg(any(feature = "http", feature = "vault"))]
+pub(crate) fn is_unreachable(e: &ureq::Transport) -> bool {
+    matches!(
+        e.kind(),
+        ureq::ErrorKind::Dns | ureq::ErrorKind::ConnectionFailed
+    )
+}
@@ -0,0 +1,186 @@
+use std::{
+    io::{
+        Cursor,
+        Read,
+    },
+    marker::PhantomData,
+    path::{
+        Path,
+        PathBuf,
+    },
+};
+
+use crate::{
+    Error,
+    prelude::*,
+    source_debug,
+};
+
+/// Largest entry read by default, see `ArchiveSource::max_size`.
+const DEFAULT_MAX_SIZE: u64 = 16 * 1024 * 1024;
+
+/// A `Source` that reads a file stored inside an archive.
+///
+/// Zip archives, tar archives and gzip compressed tar archives are
+/// supported. The format is recognized from the content of the archive, not
+/// from its extension. The archive is opened again on every `parse`.
+pub struct ArchiveSource<T> {
+    /// The path to the archive.
+    archive:  PathBuf,
+    /// The path of the file within the archive.
+    entry:    String,
+    /// The largest entry read, in bytes.
+    max_size: u64,
+    /// Phantom data to hold the parser type.
+    _marker:  PhantomData<T>,
+}
+
+source_debug!(ArchiveSource<T>);
+
+impl<T> ArchiveSource<T> {
+    /// Constructs a new `ArchiveSource`.
+    ///
+    /// # Arguments
+    ///
+    /// * `archive` - A path to the archive that will be read.
+    /// * `entry` - The path of the file within the archive, such as
+    ///   `config/app.toml`.
+    pub fn new<P, E>(archive: P, entry: E) -> Self
+    where
+        P: Into<PathBuf>,
+        E: Into<String>,
+    {
+        Self {
+            archive:  archive.into(),
+            entry:    entry.into(),
+            max_size: DEFAULT_MAX_SIZE,
+            _marker:  PhantomData,
+        }
+    }
+
+    /// Sets the largest entry read, in bytes, 16 MiB by default.
+    ///
+    /// Entries are decompressed while they are read, so a small archive
+    /// may expand to a large entry. `parse` fails with
+    /// `Error::ArchiveError` once the entry exceeds the limit.
+    #[must_use]
+    pub const fn max_size(mut self, max_size: u64) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    fn error(&self, cause: impl std::fmt::Display) -> Error {
+        Error::ArchiveError(format!(
+            "failed to read `{}` from {}: {cause}",
+            self.entry,
+            self.archive.display()
+        ))
+    }
+
+    /// The error of an archive without the entry.
+    fn missing(&self) -> Error {
+        Error::Unavailable(format!(
+            "failed to read `{}` from {}: no such entry in the archive",
+            self.entry,
+            self.archive.display()
+        ))
+    }
+
+    /// Opens the archive and returns the content of the entry.
+    fn extract(&self) -> Result<String, Error> {
+        let bytes = std::fs::read(&self.archive).map_err(|e| {
+            Error::new_read_error(
+                format!(
+                    "Failed to read file: {}, error: {}",
+                    self.archive.display(),
+                    e
+                ),
+                &e,
+            )
+        })?;
+        let content = if bytes.starts_with(b"PK") {
+            self.extract_zip(bytes)?
+        } else if bytes.starts_with(&[0x1f, 0x8b]) {
+            self.extract_tar(flate2::read::GzDecoder::new(bytes.as_slice()))?
+        } else {
+            self.extract_tar(bytes.as_slice())?
+        };
+        String::from_utf8(content).map_err(|e| self.error(e))
+    }
+
+    fn extract_zip(&self, bytes: Vec<u8>) -> Result<Vec<u8>, Error> {
+        let mut archive = zip::ZipArchive::new(Cursor::new(bytes))
+            .map_err(|e| self.error(e))?;
+        let file = match archive.by_name(&self.entry) {
+            Ok(file) => file,
+            Err(zip::result::ZipError::FileNotFound) => {
+                return Err(self.missing());
+            }
+            Err(e) => return Err(self.error(e)),
+        };
+        self.read_entry(file)
+    }
+
+    fn extract_tar<R: Read>(&self, reader: R) -> Result<Vec<u8>, Error> {
+        let mut archive = tar::Archive::new(reader);
+        for file in archive.entries().map_err(|e| self.error(e))? {
+            let file = file.map_err(|e| self.error(e))?;
+            let path = file.path().map_err(|e| self.error(e))?;
+            if path.strip_prefix("./").unwrap_or(&path) ==
+                Path::new(&self.entry)
+            {
+                return self.read_entry(file);
+            }
+        }
+        Err(self.missing())
+    }
+
+    /// Reads `entry`, failing once it exceeds `max_size`.
+    fn read_entry<R: Read>(&self, entry: R) -> Result<Vec<u8>, Error> {
+        let mut content = Vec::new();
+        entry
+            .take(self.max_size.saturating_add(1))
+            .read_to_end(&mut content)
+            .map_err(|e| self.error(e))?;
+        if u64::try_from(content.len()).unwrap_or(u64::MAX) > self.max_size {
+            return Err(self.error(format!(
+                "the entry exceeds the limit of {} bytes",
+                self.max_size
+            )));
+        }
+        Ok(content)
+    }
+}
+
+impl<T> Source for ArchiveSource<T>
+where
+    T: for<'a> Parser<&'a str> + Send + Sync,
+{
+    type Error = Error;
+    type Value = Value;
+
+    fn parse(&self) -> Result<Value, Error> {
+        let content = self.extract()?;
+        T::parse(&content)
+            .map_err(|e| {
+                Error::new_parse_error(
+                    format!("{}:{}", self.archive.display(), self.entry),
+                    e.to_string(),
+                )
+            })
+            .and_then(|v| Value::try_serialize(&v))
+    }
+
+    #[cfg(feature = "watch")]
+    fn watcher(
+        &self,
+        _s: crossbeam::channel::Sender<()>,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl<T> ParsedBy<T> for ArchiveSource<T> where
+    T: for<'a> Parser<&'a str> + Send + Sync
+{
+}
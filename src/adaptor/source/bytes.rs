@@ -0,0 +1,124 @@
+use std::{
+    borrow::Cow,
+    marker::PhantomData,
+    path::{
+        Path,
+        PathBuf,
+    },
+};
+
+use crate::{
+    Error,
+    prelude::*,
+    source_debug,
+};
+
+/// A `Source` implementation that reads binary data, for parsers of binary
+/// formats such as `MsgPackParser`.
+///
+/// The bytes are either held in memory, see `BytesSource::new`, or read
+/// from a file on every parse, see `BytesSource::file`. Only the file can be
+/// watched, bytes held in memory never change. The generic type `T` is the
+/// parser, which must implement `Parser<&[u8]>`.
+pub struct BytesSource<T> {
+    bytes:   Bytes,
+    _marker: PhantomData<T>,
+}
+
+/// Where a `BytesSource` reads its bytes from.
+enum Bytes {
+    Buffer(Vec<u8>),
+    File(PathBuf),
+}
+
+source_debug!(BytesSource<T>);
+
+impl<T> BytesSource<T> {
+    /// Constructs a new `BytesSource` parsing the given bytes.
+    ///
+    /// # Arguments
+    /// * `bytes` - The bytes to parse.
+    pub fn new<U: Into<Vec<u8>>>(bytes: U) -> Self {
+        Self {
+            bytes:   Bytes::Buffer(bytes.into()),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Constructs a new `BytesSource` parsing the content of the file at
+    /// `path`, read again on every parse.
+    ///
+    /// # Arguments
+    /// * `path` - The path of the file to parse.
+    ///
+    /// # Examples
+    ///
+    /// ```rust ignore
+    /// let realme = Realme::builder()
+    ///     .load(Adaptor::new(BytesSource::<MsgPackParser>::file(
+    ///         "snapshot.msgpack",
+    ///     )))
+    ///     .build()?;
+    /// ```
+    pub fn file<P: Into<PathBuf>>(path: P) -> Self {
+        Self {
+            bytes:   Bytes::File(path.into()),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Source for BytesSource<T>
+where
+    T: for<'a> Parser<&'a [u8]> + Send + Sync,
+{
+    type Error = Error;
+    type Value = Value;
+
+    fn parse(&self) -> Result<Value, Error> {
+        let (bytes, origin) = match &self.bytes {
+            Bytes::Buffer(buffer) => {
+                (Cow::Borrowed(buffer), format!("{} bytes", buffer.len()))
+            }
+            Bytes::File(path) => {
+                let bytes = std::fs::read(path).map_err(|e| {
+                    Error::new_read_error(
+                        format!(
+                            "Failed to read file: {}, error: {}",
+                            path.display(),
+                            e
+                        ),
+                        &e,
+                    )
+                })?;
+                (Cow::Owned(bytes), path.display().to_string())
+            }
+        };
+        T::parse(&bytes)
+            .map_err(|e| Error::new_parse_error(origin, e.to_string()))
+            .and_then(|v| Value::try_serialize(&v))
+    }
+
+    fn path(&self) -> Option<&Path> {
+        match &self.bytes {
+            Bytes::Buffer(_) => None,
+            Bytes::File(path) => Some(path),
+        }
+    }
+
+    #[cfg(feature = "watch")]
+    fn watcher(
+        &self,
+        s: crossbeam::channel::Sender<()>,
+    ) -> Result<(), Self::Error> {
+        if let Bytes::File(path) = &self.bytes {
+            super::watch_path(path, s);
+        }
+        Ok(())
+    }
+}
+
+impl<T> ParsedBy<T> for BytesSource<T> where
+    T: for<'a> Parser<&'a [u8]> + Send + Sync
+{
+}
@@ -0,0 +1,202 @@
+use std::{
+    path::{
+        Path,
+        PathBuf,
+    },
+    sync::Mutex,
+    time::{
+        Duration,
+        Instant,
+    },
+};
+
+use crate::{
+    Error,
+    prelude::*,
+    source_debug,
+};
+
+/// A `Source` wrapper that caches the `Value` loaded by its inner source.
+///
+/// The cached value is returned until `ttl` expires, after which the inner
+/// source is loaded again. This keeps frequent reloads from hitting slow or
+/// remote sources every time.
+pub struct CachedSource<S> {
+    inner:       S,
+    ttl:         Duration,
+    serve_stale: bool,
+    /// The last loaded value and the instant it was loaded at.
+    cached:      Mutex<Option<(Instant, Value)>>,
+}
+
+source_debug!(CachedSource<S>);
+
+impl<S> CachedSource<S> {
+    /// Constructs a new `CachedSource` caching `inner` for `ttl`.
+    ///
+    /// # Arguments
+    ///
+    /// * `inner` - The source to cache.
+    /// * `ttl` - How long a loaded value is served before reloading.
+    pub const fn new(inner: S, ttl: Duration) -> Self {
+        Self {
+            inner,
+            ttl,
+            serve_stale: false,
+            cached: Mutex::new(None),
+        }
+    }
+
+    /// Sets whether the last loaded value is served when a refresh fails.
+    ///
+    /// Disabled by default, in which case the refresh error is returned.
+    #[must_use]
+    pub const fn serve_stale(mut self, serve_stale: bool) -> Self {
+        self.serve_stale = serve_stale;
+        self
+    }
+}
+
+impl<S> Source for CachedSource<S>
+where
+    S: Source<Error = Error, Value = Value>,
+{
+    type Error = Error;
+    type Value = Value;
+
+    fn parse(&self) -> Result<Value, Error> {
+        let mut cached = self
+            .cached
+            .lock()
+            .map_err(|e| Error::LockError(e.to_string()))?;
+        if let Some((loaded_at, value)) = cached.as_ref() {
+            if loaded_at.elapsed() < self.ttl {
+                return Ok(value.clone());
+            }
+        }
+        match self.inner.parse() {
+            Ok(value) => {
+                *cached = Some((Instant::now(), value.clone()));
+                Ok(value)
+            }
+            Err(e) => match cached.as_ref() {
+                Some((_, value)) if self.serve_stale => Ok(value.clone()),
+                _ => Err(e),
+            },
+        }
+    }
+
+    fn is_sensitive(&self) -> bool {
+        self.inner.is_sensitive()
+    }
+
+    fn has_changed(&self) -> Option<bool> {
+        self.inner.has_changed()
+    }
+
+    fn path(&self) -> Option<&Path> {
+        self.inner.path()
+    }
+
+    fn key_path(&self, key: &str) -> Option<PathBuf> {
+        self.inner.key_path(key)
+    }
+
+    fn parse_path(&self, path: &Path) -> Option<Result<Value, Error>> {
+        self.inner.parse_path(path)
+    }
+
+    #[cfg(feature = "watch")]
+    fn watcher(
+        &self,
+        s: crossbeam::channel::Sender<()>,
+    ) -> Result<(), Self::Error> {
+        self.inner.watcher(s)
+    }
+}
+
+impl<S, P> ParsedBy<P> for CachedSource<S> where S: ParsedBy<P> {}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        Arc,
+        atomic::{
+            AtomicBool,
+            AtomicUsize,
+            Ordering,
+        },
+    };
+
+    use super::*;
+
+    #[derive(Debug, Default)]
+    struct MockSource {
+        loads: Arc<AtomicUsize>,
+        fail:  Arc<AtomicBool>,
+    }
+
+    impl Source for MockSource {
+        type Error = Error;
+        type Value = Value;
+
+        fn parse(&self) -> Result<Value, Error> {
+            if self.fail.load(Ordering::SeqCst) {
+                return Err(Error::Unknown("source unavailable".to_string()));
+            }
+            let loads = self.loads.fetch_add(1, Ordering::SeqCst) + 1;
+            Value::try_serialize(&loads)
+        }
+
+        #[cfg(feature = "watch")]
+        fn watcher(
+            &self,
+            _s: crossbeam::channel::Sender<()>,
+        ) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_cached_within_ttl() -> anyhow::Result<()> {
+        let mock = MockSource::default();
+        let loads = mock.loads.clone();
+        let source = CachedSource::new(mock, Duration::from_mins(1));
+
+        for _ in 0..3 {
+            assert_eq!(source.parse()?, Value::Integer(1));
+        }
+        assert_eq!(loads.load(Ordering::SeqCst), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_reload_after_ttl() -> anyhow::Result<()> {
+        let mock = MockSource::default();
+        let loads = mock.loads.clone();
+        let source = CachedSource::new(mock, Duration::ZERO);
+
+        assert_eq!(source.parse()?, Value::Integer(1));
+        assert_eq!(source.parse()?, Value::Integer(2));
+        assert_eq!(loads.load(Ordering::SeqCst), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_failed_refresh() -> anyhow::Result<()> {
+        let mock = MockSource::default();
+        let fail = mock.fail.clone();
+        let source = CachedSource::new(mock, Duration::ZERO);
+        source.parse()?;
+        fail.store(true, Ordering::SeqCst);
+        assert!(source.parse().is_err());
+
+        let mock = MockSource::default();
+        let fail = mock.fail.clone();
+        let source = CachedSource::new(mock, Duration::ZERO).serve_stale(true);
+        source.parse()?;
+        fail.store(true, Ordering::SeqCst);
+        assert_eq!(source.parse()?, Value::Integer(1));
+        Ok(())
+    }
+}
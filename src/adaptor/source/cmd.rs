@@ -52,3 +52,8 @@ where
         Ok(())
     }
 }
+
+impl<T> ParsedBy<T> for CmdSource<T> where
+    T: for<'a> Parser<&'a str> + Send + Sync
+{
+}
@@ -0,0 +1,240 @@
+use std::{
+    marker::PhantomData,
+    path::{
+        Path,
+        PathBuf,
+    },
+    sync::Mutex,
+};
+
+use crate::{
+    Error,
+    prelude::*,
+    source_debug,
+};
+
+/// A `Source` that loads every file of a directory, such as a `conf.d`
+/// drop-in directory.
+///
+/// The files are parsed with `T` in the lexicographic order of their names
+/// and merged as successive layers with the `MergeStrategy` of the adaptor,
+/// so `20-local.toml` overrides the keys it shares with `10-base.toml`.
+/// `Realme::origin` names the file that provided each key. Hidden files, whose
+/// name starts with `.`, and subdirectories are skipped. The directory is
+/// listed again on every parse, so files added or removed are picked up by
+/// `Realme::reload`.
+pub struct DirSource<T> {
+    /// The directory holding the files.
+    dir:     PathBuf,
+    /// The pattern file names must match, see `DirSource::pattern`.
+    pattern: Option<String>,
+    /// The files of the last parse with their values, in merge order.
+    layers:  Mutex<Vec<(PathBuf, Value)>>,
+    /// Phantom data to hold the parser type.
+    _marker: PhantomData<T>,
+}
+
+source_debug!(DirSource<T>);
+
+impl<T> DirSource<T> {
+    /// Constructs a new `DirSource` loading every file of `dir`.
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - The directory holding the files.
+    pub fn new<P: Into<PathBuf>>(dir: P) -> Self {
+        Self {
+            dir:     dir.into(),
+            pattern: None,
+            layers:  Mutex::new(Vec::new()),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Constructs a new `DirSource` from a glob such as `conf.d/*.toml`,
+    /// whose last component is the pattern of the file names, see
+    /// `DirSource::pattern`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust ignore
+    /// let realme = Realme::builder()
+    ///     .load(Adaptor::new(FileSource::<TomlParser>::new("app.toml")))
+    ///     .load(Adaptor::new(DirSource::<TomlParser>::glob("conf.d/*.toml")))
+    ///     .build()?;
+    /// ```
+    pub fn glob<P: AsRef<Path>>(glob: P) -> Self {
+        let glob = glob.as_ref();
+        let dir = glob.parent().unwrap_or_else(|| Path::new(""));
+        let dir = if dir.as_os_str().is_empty() {
+            Path::new(".")
+        } else {
+            dir
+        };
+        let source = Self::new(dir);
+        match glob.file_name().and_then(|name| name.to_str()) {
+            Some(pattern) => source.pattern(pattern),
+            None => source,
+        }
+    }
+
+    /// Only loads the files whose name matches `pattern`, in which `*`
+    /// matches any run of characters and `?` any single character, as in
+    /// `*.toml` or `??-*.yaml`.
+    #[must_use]
+    pub fn pattern<S: Into<String>>(mut self, pattern: S) -> Self {
+        self.pattern = Some(pattern.into());
+        self
+    }
+
+    /// Lists the files to load, sorted by name.
+    fn files(&self) -> Result<Vec<PathBuf>, Error> {
+        let error = |e: std::io::Error| {
+            Error::new_read_error(
+                format!(
+                    "Failed to read directory: {}, error: {e}",
+                    self.dir.display()
+                ),
+                &e,
+            )
+        };
+        let mut files = Vec::new();
+        for entry in std::fs::read_dir(&self.dir).map_err(error)? {
+            let path = entry.map_err(error)?.path();
+            let Some(name) = path.file_name().and_then(|name| name.to_str())
+            else {
+                continue;
+            };
+            let matched = self
+                .pattern
+                .as_deref()
+                .is_none_or(|pattern| matches(pattern, name));
+            if !name.starts_with('.') && matched && path.is_file() {
+                files.push(path);
+            }
+        }
+        files.sort();
+        Ok(files)
+    }
+}
+
+impl<T> Source for DirSource<T>
+where
+    T: for<'a> Parser<&'a str> + Send + Sync,
+{
+    type Error = Error;
+    type Value = Value;
+
+    fn parse(&self) -> Result<Value, Error> {
+        self.parse_merged(MergeStrategy::default())
+    }
+
+    fn parse_merged(&self, merge: MergeStrategy) -> Result<Value, Error> {
+        let mut layers = Vec::new();
+        for path in self.files()? {
+            let value = FileSource::<T>::new(&path).parse()?;
+            layers.push((path, value));
+        }
+        let mut table = Map::new();
+        for (_, value) in &layers {
+            match value {
+                Value::Table(value) => merge.merge(&mut table, value),
+                Value::Null => {}
+                _ => {
+                    return Err(Error::new_build_error(
+                        "Adaptor parse result is not a table".to_string(),
+                    ));
+                }
+            }
+        }
+        *self
+            .layers
+            .lock()
+            .map_err(|e| Error::LockError(e.to_string()))? = layers;
+        Ok(Value::Table(table))
+    }
+
+    fn key_path(&self, key: &str) -> Option<PathBuf> {
+        let layers = self.layers.lock().ok()?;
+        layers
+            .iter()
+            .rev()
+            .find(|(_, value)| value.get(key).is_some())
+            .map(|(path, _)| path.clone())
+    }
+
+    fn parse_path(&self, path: &Path) -> Option<Result<Value, Error>> {
+        Some(FileSource::<T>::new(path).parse())
+    }
+
+    #[cfg(feature = "watch")]
+    fn watcher(
+        &self,
+        s: crossbeam::channel::Sender<()>,
+    ) -> Result<(), Self::Error> {
+        super::watch_path(&self.dir, s);
+        Ok(())
+    }
+}
+
+impl<T> ParsedBy<T> for DirSource<T> where
+    T: for<'a> Parser<&'a str> + Send + Sync
+{
+}
+
+/// Returns whether `name` matches `pattern`, in which `*` matches any run of
+/// characters and `?` any single character.
+fn matches(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    // Position of the last `*` and of the name where it started matching
+    let mut star = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match star {
+                Some((star_p, star_n)) => {
+                    p = star_p + 1;
+                    n = star_n + 1;
+                    star = Some((star_p, star_n + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches() {
+        for (pattern, name) in [
+            ("*", "app.toml"),
+            ("*.toml", "10-base.toml"),
+            ("??-*.toml", "20-local.toml"),
+            ("*-*", "a-b-c"),
+            ("app.toml", "app.toml"),
+        ] {
+            assert!(matches(pattern, name), "{pattern} {name}");
+        }
+        for (pattern, name) in [
+            ("*.toml", "app.yaml"),
+            ("*.toml", "app.toml.bak"),
+            ("?.toml", "10.toml"),
+            ("app", "app.toml"),
+        ] {
+            assert!(!matches(pattern, name), "{pattern} {name}");
+        }
+    }
+}
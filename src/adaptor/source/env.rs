@@ -11,11 +11,19 @@ use crate::{
 /// This struct is used to fetch and parse environment variables that start with
 /// a given prefix. It implements the `Source` trait, allowing environment
 /// variables to be parsed into a specified type `T` using a parser `U`.
+///
+/// The environment is read again on every parse rather than captured when
+/// the source is built, so `Realme::reload` reflects variables changed in
+/// the meantime.
 pub struct EnvSource<T> {
     /// The prefix used for filtering environment variables.
-    prefix:  String,
+    prefix:    String,
+    /// Dotted keys that may be read, all keys when `None`.
+    allowlist: Option<Vec<String>>,
+    /// Whether the variables hold secrets left out of exports.
+    sensitive: bool,
     /// Phantom data to hold the lifetime and parser type.
-    _marker: PhantomData<T>,
+    _marker:   PhantomData<T>,
 }
 
 source_debug!(EnvSource<T>);
@@ -23,10 +31,59 @@ source_debug!(EnvSource<T>);
 impl<T> EnvSource<T> {
     pub fn new<U: Into<String>>(prefix: U) -> Self {
         Self {
-            prefix:  prefix.into(),
-            _marker: PhantomData,
+            prefix:    prefix.into(),
+            allowlist: None,
+            sensitive: false,
+            _marker:   PhantomData,
         }
     }
+
+    /// Restricts the source to the given dotted keys.
+    ///
+    /// Each key is read from the variable named after it: the prefix
+    /// followed by the key with `.` replaced by `_`, so `server.port` is
+    /// read from `APP_SERVER_PORT` with the prefix `APP_`. The value is
+    /// placed at the dotted key, and every other variable with the prefix
+    /// is ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - The dotted keys that may be overridden.
+    #[must_use]
+    pub fn allowlist<K: AsRef<str>>(mut self, keys: &[K]) -> Self {
+        self.allowlist =
+            Some(keys.iter().map(|k| k.as_ref().to_string()).collect());
+        self
+    }
+
+    /// Sets whether the variables hold secrets.
+    ///
+    /// The keys read from a sensitive source are still returned by `get`
+    /// and `try_deserialize`, but never appear when the configuration is
+    /// serialized, printed with `Debug`, flattened or diffed, see
+    /// `Realme::redacted`.
+    ///
+    /// Disabled by default: environment variables commonly hold plain
+    /// settings such as ports and hosts, which exports are expected to
+    /// show, so sources holding secrets have to opt in.
+    #[must_use]
+    pub const fn sensitive(mut self, sensitive: bool) -> Self {
+        self.sensitive = sensitive;
+        self
+    }
+
+    /// Keeps the allowed keys of the parsed variables, nested at their
+    /// dotted path.
+    fn filter(&self, value: Value) -> Result<Value, Error> {
+        let (Some(allowlist), Value::Table(vars)) = (&self.allowlist, &value)
+        else {
+            return Ok(value);
+        };
+        Value::from_dotted(allowlist.iter().filter_map(|key| {
+            vars.get(&key.replace('.', "_").to_ascii_lowercase())
+                .map(|value| (key, value.clone()))
+        }))
+    }
 }
 
 impl<T> Source for EnvSource<T>
@@ -41,6 +98,11 @@ where
                 Error::new_parse_error(self.prefix.clone(), e.to_string())
             })
             .and_then(|v| Value::try_serialize(&v))
+            .and_then(|v| self.filter(v))
+    }
+
+    fn is_sensitive(&self) -> bool {
+        self.sensitive
     }
 
     #[cfg(feature = "watch")]
@@ -51,3 +113,82 @@ where
         Ok(())
     }
 }
+
+impl<T> ParsedBy<T> for EnvSource<T> where
+    T: for<'a> Parser<&'a str> + Send + Sync
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reload_reads_live_environment() -> anyhow::Result<()> {
+        std::env::set_var("REALME_DYNAMIC_TEST_PORT", "8080");
+        let source = EnvSource::<EnvParser>::new("REALME_DYNAMIC_TEST_");
+
+        let mut realme =
+            Realme::builder().load(Adaptor::new(source)).build()?;
+        assert_eq!(realme.get_as::<u16, _>("port"), Some(8080));
+
+        std::env::set_var("REALME_DYNAMIC_TEST_PORT", "9090");
+        realme.reload()?;
+        assert_eq!(realme.get_as::<u16, _>("port"), Some(9090));
+        Ok(())
+    }
+
+    #[test]
+    fn test_allowlist() -> anyhow::Result<()> {
+        std::env::set_var("REALME_ALLOW_TEST_SERVER_PORT", "9090");
+        std::env::set_var("REALME_ALLOW_TEST_SERVER_HOST", "evil.com");
+        std::env::set_var("REALME_ALLOW_TEST_DEBUG", "true");
+
+        let realme = Realme::builder()
+            .defaults(&Value::from_dotted([
+                ("server.port", Value::from(8080)),
+                ("server.host", Value::from("localhost")),
+            ])?)
+            .load(Adaptor::new(
+                EnvSource::<EnvParser>::new("REALME_ALLOW_TEST_")
+                    .allowlist(&["server.port", "database.url"]),
+            ))
+            .build()?;
+
+        assert_eq!(realme.get_as::<u16, _>("server.port"), Some(9090));
+        assert_eq!(
+            realme.get_as::<String, _>("server.host").as_deref(),
+            Some("localhost")
+        );
+        assert_eq!(realme.get("debug"), None);
+        assert_eq!(realme.get("database"), None);
+        assert_eq!(realme.get("server_host"), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_sensitive_keys_are_redacted() -> anyhow::Result<()> {
+        std::env::set_var("REALME_SECRET_TEST_TOKEN", "hunter2");
+        let realme = Realme::builder()
+            .defaults(&Value::from_dotted([("name", "realme")])?)
+            .load(Adaptor::new(
+                EnvSource::<EnvParser>::new("REALME_SECRET_TEST_")
+                    .sensitive(true),
+            ))
+            .build()?;
+
+        assert_eq!(
+            realme.get_as::<String, _>("token").as_deref(),
+            Some("hunter2")
+        );
+        assert!(realme.is_sensitive("token"));
+
+        let dump = serde_json::to_string(&realme)?;
+        assert_eq!(dump, r#"{"name":"realme"}"#);
+        assert!(!format!("{realme:?}").contains("hunter2"));
+        assert!(!realme.flatten_to_strings().contains_key("token"));
+        assert!(realme.leaves().all(|(path, ..)| path != "token"));
+        assert_eq!(realme.diff_from_defaults().get("token"), None);
+        Ok(())
+    }
+}
@@ -1,6 +1,11 @@
 use std::{
     marker::PhantomData,
-    path::PathBuf,
+    path::{
+        Path,
+        PathBuf,
+    },
+    sync::Mutex,
+    time::SystemTime,
 };
 
 #[cfg(feature = "placeholder")]
@@ -24,11 +29,22 @@ use crate::{
 /// * `T`: The parser type that implements the `Parser` trait for parsing file
 ///   contents.
 /// * `U`: The path type that implements `AsRef<Path>`, defaults to `PathBuf`.
+///
+/// With the `gzip` feature, gzip compressed files such as `config.json.gz`
+/// are decompressed before being handed to the parser. With the `encoding`
+/// feature, files in other encodings than UTF-8 can be read, see
+/// `with_encoding`.
 pub struct FileSource<T> {
     /// The path to the configuration file.
-    path:    PathBuf,
+    path:     PathBuf,
+    /// The modification time and value of the last parse, kept when
+    /// polling for changes.
+    polled:   Option<Mutex<Option<(SystemTime, Value)>>>,
+    /// Label of the encoding of the file, UTF-8 when `None`.
+    #[cfg(feature = "encoding")]
+    encoding: Option<String>,
     /// Phantom data to hold the parser type.
-    _marker: PhantomData<T>,
+    _marker:  PhantomData<T>,
 }
 
 source_debug!(FileSource<T>);
@@ -41,20 +57,86 @@ impl<T> FileSource<T> {
     /// * `path` - A path to the file that will be read.
     pub fn new<P: Into<PathBuf>>(path: P) -> Self {
         Self {
-            path:    path.into(),
+            path: path.into(),
+            polled: None,
+            #[cfg(feature = "encoding")]
+            encoding: None,
             _marker: PhantomData,
         }
     }
 
+    /// Reads the file in the encoding named `label`, such as `latin1` or
+    /// `shift_jis`, transcoding it to UTF-8 before parsing.
+    ///
+    /// Labels are those of the WHATWG Encoding Standard, as recognized by
+    /// `encoding_rs`. A byte order mark at the start of the file takes
+    /// precedence over `label`. Parsing fails with `Error::ReadFileError`
+    /// if the label is unknown or the file is not valid in the encoding.
+    #[cfg(feature = "encoding")]
+    #[must_use]
+    pub fn with_encoding<L: Into<String>>(mut self, label: L) -> Self {
+        self.encoding = Some(label.into());
+        self
+    }
+
+    /// Skips reading and parsing the file while it is unchanged.
+    ///
+    /// The modification time of the file is remembered on every `parse`,
+    /// and the last parsed value is returned as long as it stays the same.
+    /// This is a cheap alternative to watching the file: combined with
+    /// `Realme::reload_if_changed`, reloading an untouched file does no
+    /// work at all.
+    #[must_use]
+    pub fn poll_changes(mut self) -> Self {
+        self.polled = Some(Mutex::new(None));
+        self
+    }
+
+    /// Returns the modification time of the file, if it can be read.
+    fn modified(&self) -> Option<SystemTime> {
+        std::fs::metadata(&self.path)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+    }
+
     fn get_buffer(&self) -> Result<String> {
-        let buffer =
-            std::fs::read_to_string(self.path.clone()).map_err(|e| {
-                Error::ReadFileError(format!(
+        let read_error = |e: std::io::Error| {
+            Error::new_read_error(
+                format!(
                     "Failed to read file: {}, error: {}",
                     self.path.display(),
                     e
+                ),
+                &e,
+            )
+        };
+        let bytes = std::fs::read(&self.path).map_err(read_error)?;
+        #[cfg(feature = "gzip")]
+        let bytes = if self.is_gzip(&bytes) {
+            let mut decoded = Vec::new();
+            std::io::Read::read_to_end(
+                &mut flate2::read::GzDecoder::new(bytes.as_slice()),
+                &mut decoded,
+            )
+            .map_err(read_error)?;
+            decoded
+        } else {
+            bytes
+        };
+        #[cfg(feature = "encoding")]
+        let buffer = match &self.encoding {
+            Some(label) => self.decode(label, &bytes)?,
+            None => String::from_utf8(bytes).map_err(|e| {
+                read_error(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    e,
                 ))
-            })?;
+            })?,
+        };
+        #[cfg(not(feature = "encoding"))]
+        let buffer = String::from_utf8(bytes).map_err(|e| {
+            read_error(std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+        })?;
 
         #[cfg(feature = "placeholder")]
         {
@@ -81,6 +163,58 @@ impl<T> FileSource<T> {
             Ok(buffer)
         }
     }
+
+    /// Transcodes `bytes` from the encoding named `label` to UTF-8.
+    #[cfg(feature = "encoding")]
+    fn decode(&self, label: &str, bytes: &[u8]) -> Result<String> {
+        let encoding = encoding_rs::Encoding::for_label(label.as_bytes())
+            .ok_or_else(|| {
+                Error::ReadFileError(format!(
+                    "Failed to read file: {}, error: unknown encoding \
+                     `{label}`",
+                    self.path.display()
+                ))
+            })?;
+        let (text, encoding, malformed) = encoding.decode(bytes);
+        if malformed {
+            return Err(Error::ReadFileError(format!(
+                "Failed to read file: {}, error: invalid {} content",
+                self.path.display(),
+                encoding.name()
+            )));
+        }
+        Ok(text.into_owned())
+    }
+
+    /// Returns whether the file is gzip compressed, judging by its `.gz`
+    /// extension or its magic bytes.
+    #[cfg(feature = "gzip")]
+    fn is_gzip(&self, bytes: &[u8]) -> bool {
+        self.path.extension().is_some_and(|ext| ext == "gz") ||
+            bytes.starts_with(&[0x1f, 0x8b])
+    }
+}
+
+#[cfg(feature = "dotenv")]
+impl FileSource<NestedDotenvParser> {
+    /// Constructs a `FileSource` reading a `.env` file, mapping
+    /// `DB__HOST=x` style keys into nested tables.
+    ///
+    /// Keys are lowercased and split on `__`, so `DB__HOST` is read as
+    /// `db.host`. Use `FileSource::<DotenvParser>::new` to keep the keys
+    /// verbatim.
+    ///
+    /// # Examples
+    ///
+    /// ```rust ignore
+    /// let realme = Realme::builder()
+    ///     .load(Adaptor::new(FileSource::dotenv(".env")))
+    ///     .build()?;
+    /// let host = realme.get_string("db.host")?;
+    /// ```
+    pub fn dotenv<P: Into<PathBuf>>(path: P) -> Self {
+        Self::new(path)
+    }
 }
 
 impl<T> Source for FileSource<T>
@@ -91,10 +225,62 @@ where
     type Value = Value;
 
     fn parse(&self) -> Result<Value> {
+        let Some(polled) = &self.polled else {
+            return self.parse_file();
+        };
+        let mut polled =
+            polled.lock().map_err(|e| Error::LockError(e.to_string()))?;
+        let modified = self.modified();
+        if let (Some(modified), Some((seen, value))) = (modified, &*polled) {
+            if modified == *seen {
+                return Ok(value.clone());
+            }
+        }
+        let value = self.parse_file()?;
+        *polled = modified.map(|modified| (modified, value.clone()));
+        Ok(value)
+    }
+
+    fn has_changed(&self) -> Option<bool> {
+        let polled = self.polled.as_ref()?.lock().ok()?;
+        let seen = polled.as_ref().map(|(seen, _)| *seen);
+        Some(seen.is_none() || self.modified() != seen)
+    }
+
+    fn path(&self) -> Option<&Path> {
+        Some(&self.path)
+    }
+
+    fn parse_path(&self, path: &Path) -> Option<Result<Value>> {
+        #[allow(unused_mut)]
+        let mut source = Self::new(path);
+        #[cfg(feature = "encoding")]
+        {
+            source.encoding.clone_from(&self.encoding);
+        }
+        Some(source.parse())
+    }
+
+    #[cfg(feature = "watch")]
+    fn watcher(
+        &self,
+        s: crossbeam::channel::Sender<()>,
+    ) -> std::result::Result<(), Self::Error> {
+        super::watch_path(&self.path, s);
+        Ok(())
+    }
+}
+
+impl<T> FileSource<T>
+where
+    T: for<'a> Parser<&'a str> + Send + Sync,
+{
+    /// Reads and parses the file.
+    fn parse_file(&self) -> Result<Value> {
         let buffer = self.get_buffer()?;
 
         // Parse the rendered content
-        T::parse(&buffer)
+        T::parse_at(&buffer, &self.path)
             .map_err(|e| {
                 Error::new_parse_error(
                     self.path.display().to_string(),
@@ -103,55 +289,9 @@ where
             })
             .and_then(|v| Value::try_serialize(&v))
     }
+}
 
-    #[cfg(feature = "watch")]
-    fn watcher(
-        &self,
-        s: crossbeam::channel::Sender<()>,
-    ) -> std::result::Result<(), Self::Error> {
-        let path = std::sync::Arc::new(self.path.clone());
-
-        std::thread::spawn(move || -> std::result::Result<(), Self::Error> {
-            let (tx, rx) = crossbeam::channel::unbounded();
-
-            let mut watcher = notify::recommended_watcher(
-                move |res: notify::Result<notify::Event>| {
-                    if let Ok(event) = res {
-                        #[allow(unused_variables)]
-                        if let Err(e) = tx.send(event) {
-                            #[cfg(feature = "tracing")]
-                            tracing::error!("Send event error: {:?}", e);
-                        }
-                    }
-                },
-            )
-            .map_err(|e| {
-                #[cfg(feature = "tracing")]
-                tracing::error!("Watcher error: {:?}", e);
-                Error::WatcherError(e.to_string())
-            })?;
-
-            notify::Watcher::watch(
-                &mut watcher,
-                &path,
-                notify::RecursiveMode::NonRecursive,
-            )
-            .map_err(|e| {
-                #[cfg(feature = "tracing")]
-                tracing::error!("Watcher error: {:?}", e);
-                Error::WatcherError(e.to_string())
-            })?;
-
-            while let Ok(_event) = rx.recv() {
-                #[allow(unused_variables)]
-                if let Err(e) = s.send(()) {
-                    #[cfg(feature = "tracing")]
-                    tracing::error!("Send event error: {:?}", e);
-                }
-            }
-
-            Ok(())
-        });
-        Ok(())
-    }
+impl<T> ParsedBy<T> for FileSource<T> where
+    T: for<'a> Parser<&'a str> + Send + Sync
+{
 }
@@ -0,0 +1,233 @@
+use std::{
+    marker::PhantomData,
+    path::{
+        Path,
+        PathBuf,
+    },
+    process::Command,
+    sync::OnceLock,
+};
+
+use tempfile::TempDir;
+
+use crate::{
+    Error,
+    prelude::*,
+    source_debug,
+};
+
+/// A `Source` that reads a file from a Git repository.
+///
+/// The repository is fetched at the given ref into a local cache directory
+/// with the `git` command line tool, and the file is read from the fetched
+/// commit without checking out a work tree. Every `parse` fetches again, so
+/// `reload` picks up new commits.
+pub struct GitSource<T> {
+    url:       String,
+    reference: String,
+    path:      String,
+    shallow:   bool,
+    cache_dir: Option<PathBuf>,
+    temp_dir:  OnceLock<TempDir>,
+    token:     Option<String>,
+    ssh_key:   Option<PathBuf>,
+    _marker:   PhantomData<T>,
+}
+
+source_debug!(GitSource<T>);
+
+impl<T> GitSource<T> {
+    /// Constructs a new `GitSource`.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL or local path of the repository.
+    /// * `reference` - The branch, tag or commit to read from.
+    /// * `path` - The path of the file within the repository.
+    pub fn new<U, R, P>(url: U, reference: R, path: P) -> Self
+    where
+        U: Into<String>,
+        R: Into<String>,
+        P: Into<String>,
+    {
+        Self {
+            url:       url.into(),
+            reference: reference.into(),
+            path:      path.into(),
+            shallow:   false,
+            cache_dir: None,
+            temp_dir:  OnceLock::new(),
+            token:     None,
+            ssh_key:   None,
+            _marker:   PhantomData,
+        }
+    }
+
+    /// Sets whether only the commit at the ref is fetched, without history.
+    #[must_use]
+    pub const fn shallow(mut self, shallow: bool) -> Self {
+        self.shallow = shallow;
+        self
+    }
+
+    /// Sets the directory the repository is fetched into.
+    ///
+    /// Defaults to a private directory created under the system temporary
+    /// directory on the first fetch and removed when the source is dropped.
+    #[must_use]
+    pub fn cache_dir<P: Into<PathBuf>>(mut self, cache_dir: P) -> Self {
+        self.cache_dir = Some(cache_dir.into());
+        self
+    }
+
+    /// Authenticates HTTP(S) fetches with a bearer token.
+    ///
+    /// The token is passed to `git` through the environment, not on its
+    /// command line.
+    #[must_use]
+    pub fn token<S: Into<String>>(mut self, token: S) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    /// Authenticates SSH fetches with the given private key.
+    #[must_use]
+    pub fn ssh_key<P: Into<PathBuf>>(mut self, ssh_key: P) -> Self {
+        self.ssh_key = Some(ssh_key.into());
+        self
+    }
+
+    fn repo_dir(&self) -> Result<PathBuf, Error> {
+        if let Some(cache_dir) = &self.cache_dir {
+            return Ok(cache_dir.clone());
+        }
+        if let Some(temp_dir) = self.temp_dir.get() {
+            return Ok(temp_dir.path().to_path_buf());
+        }
+        let temp_dir = tempfile::Builder::new()
+            .prefix("realme-git-")
+            .tempdir()
+            .map_err(|e| {
+                Error::GitError(format!("failed to create cache dir: {e}"))
+            })?;
+        Ok(self.temp_dir.get_or_init(|| temp_dir).path().to_path_buf())
+    }
+
+    fn git(&self, dir: &Path) -> Command {
+        let mut command = Command::new("git");
+        command.arg("-C").arg(dir).env("GIT_TERMINAL_PROMPT", "0");
+        if let Some(token) = &self.token {
+            command
+                .env("GIT_CONFIG_COUNT", "1")
+                .env("GIT_CONFIG_KEY_0", "http.extraHeader")
+                .env(
+                    "GIT_CONFIG_VALUE_0",
+                    format!("Authorization: Bearer {token}"),
+                );
+        }
+        if let Some(ssh_key) = &self.ssh_key {
+            command.env(
+                "GIT_SSH_COMMAND",
+                format!(
+                    "ssh -i {} -o IdentitiesOnly=yes",
+                    shell_quote(&ssh_key.to_string_lossy())
+                ),
+            );
+        }
+        command
+    }
+
+    /// Runs `command`, returning its standard output.
+    fn run(
+        &self,
+        mut command: Command,
+        action: &str,
+    ) -> Result<Vec<u8>, Error> {
+        let output = command
+            .output()
+            .map_err(|e| Error::GitError(format!("failed to run git: {e}")))?;
+        if output.status.success() {
+            Ok(output.stdout)
+        } else {
+            Err(Error::GitError(format!(
+                "failed to {action} `{}` at `{}` from {}: {}",
+                self.path,
+                self.reference,
+                self.url,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )))
+        }
+    }
+
+    /// Fetches the ref and returns the content of the file.
+    fn fetch(&self) -> Result<String, Error> {
+        let dir = self.repo_dir()?;
+        if !dir.join("HEAD").exists() {
+            std::fs::create_dir_all(&dir).map_err(|e| {
+                Error::GitError(format!(
+                    "failed to create cache dir {}: {e}",
+                    dir.display()
+                ))
+            })?;
+            let mut init = self.git(&dir);
+            init.args(["init", "--bare", "--quiet"]);
+            self.run(init, "initialize the cache for")?;
+        }
+
+        let mut fetch = self.git(&dir);
+        fetch.args(["fetch", "--quiet", "--no-tags"]);
+        if self.shallow {
+            fetch.arg("--depth=1");
+        }
+        fetch
+            .arg("--end-of-options")
+            .arg(&self.url)
+            .arg(&self.reference);
+        self.run(fetch, "fetch")?;
+
+        let mut show = self.git(&dir);
+        show.arg("show").arg(format!("FETCH_HEAD:{}", self.path));
+        let content = self.run(show, "read")?;
+        String::from_utf8(content).map_err(|e| {
+            Error::GitError(format!("`{}` is not valid UTF-8: {e}", self.path))
+        })
+    }
+}
+
+/// Quotes `s` as a single argument for the shell running `GIT_SSH_COMMAND`.
+fn shell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', r"'\''"))
+}
+
+impl<T> Source for GitSource<T>
+where
+    T: for<'a> Parser<&'a str> + Send + Sync,
+{
+    type Error = Error;
+    type Value = Value;
+
+    fn parse(&self) -> Result<Value, Error> {
+        let content = self.fetch()?;
+        T::parse(&content)
+            .map_err(|e| {
+                Error::new_parse_error(
+                    format!("{}:{}", self.url, self.path),
+                    e.to_string(),
+                )
+            })
+            .and_then(|v| Value::try_serialize(&v))
+    }
+
+    #[cfg(feature = "watch")]
+    fn watcher(
+        &self,
+        _s: crossbeam::channel::Sender<()>,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl<T> ParsedBy<T> for GitSource<T> where
+    T: for<'a> Parser<&'a str> + Send + Sync
+{
+}
@@ -0,0 +1,162 @@
+use std::{
+    marker::PhantomData,
+    time::Duration,
+};
+
+use crate::{
+    Error,
+    prelude::*,
+    source_debug,
+};
+
+/// A `Source` that fetches configuration from an HTTP(S) URL.
+///
+/// The body of the response is parsed by `T`, so a config server can serve
+/// any format with a parser. Every `parse` fetches again, so `reload` picks
+/// up changes on the server.
+///
+/// Requests failing with a transport error, `429 Too Many Requests` or a
+/// `5xx` status are retried according to `retries` and `retry_delay`; other
+/// statuses fail immediately.
+pub struct HttpSource<T> {
+    url:         String,
+    headers:     Vec<(String, String)>,
+    timeout:     Duration,
+    retries:     u32,
+    retry_delay: Duration,
+    _marker:     PhantomData<T>,
+}
+
+source_debug!(HttpSource<T>);
+
+impl<T> HttpSource<T> {
+    /// Constructs a new `HttpSource`.
+    ///
+    /// By default requests time out after 30 seconds and are not retried.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL the configuration is fetched from.
+    pub fn new<U: Into<String>>(url: U) -> Self {
+        Self {
+            url:         url.into(),
+            headers:     Vec::new(),
+            timeout:     Duration::from_secs(30),
+            retries:     0,
+            retry_delay: Duration::from_millis(500),
+            _marker:     PhantomData,
+        }
+    }
+
+    /// Adds a header sent with every request, e.g. `Authorization`.
+    #[must_use]
+    pub fn header<N, V>(mut self, name: N, value: V) -> Self
+    where
+        N: Into<String>,
+        V: Into<String>,
+    {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Sets how long a request may take, from connecting to reading the
+    /// whole body.
+    #[must_use]
+    pub const fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sets how many times a failed request is retried.
+    #[must_use]
+    pub const fn retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Sets the delay before the first retry, doubled before each of the
+    /// next ones.
+    #[must_use]
+    pub const fn retry_delay(mut self, retry_delay: Duration) -> Self {
+        self.retry_delay = retry_delay;
+        self
+    }
+
+    /// Fetches the URL, retrying on transient failures, and returns the body
+    /// of the response.
+    fn fetch(&self) -> Result<String, Error> {
+        let agent = ureq::AgentBuilder::new().timeout(self.timeout).build();
+        let mut delay = self.retry_delay;
+        let mut attempt = 0;
+        loop {
+            let request = self
+                .headers
+                .iter()
+                .fold(agent.get(&self.url), |request, (name, value)| {
+                    request.set(name, value)
+                });
+            let (err, transient, unavailable) = match request.call() {
+                Ok(response) => {
+                    return response.into_string().map_err(|e| {
+                        Error::HttpError(format!(
+                            "failed to read the response from {}: {e}",
+                            self.url
+                        ))
+                    });
+                }
+                Err(ureq::Error::Status(status, _)) => (
+                    format!("{} responded with status {status}", self.url),
+                    status == 429 || status >= 500,
+                    status == 404,
+                ),
+                Err(ureq::Error::Transport(e)) => (
+                    format!("failed to fetch {}: {e}", self.url),
+                    true,
+                    super::is_unreachable(&e),
+                ),
+            };
+            if !transient || attempt >= self.retries {
+                return Err(if unavailable {
+                    Error::Unavailable(err)
+                } else {
+                    Error::HttpError(err)
+                });
+            }
+            #[cfg(feature = "tracing")]
+            tracing::warn!(url = %self.url, attempt, "retrying: {err}");
+            std::thread::sleep(delay);
+            delay = delay.saturating_mul(2);
+            attempt += 1;
+        }
+    }
+}
+
+impl<T> Source for HttpSource<T>
+where
+    T: for<'a> Parser<&'a str> + Send + Sync,
+{
+    type Error = Error;
+    type Value = Value;
+
+    fn parse(&self) -> Result<Value, Error> {
+        let content = self.fetch()?;
+        T::parse(&content)
+            .map_err(|e| {
+                Error::new_parse_error(self.url.clone(), e.to_string())
+            })
+            .and_then(|v| Value::try_serialize(&v))
+    }
+
+    #[cfg(feature = "watch")]
+    fn watcher(
+        &self,
+        _s: crossbeam::channel::Sender<()>,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl<T> ParsedBy<T> for HttpSource<T> where
+    T: for<'a> Parser<&'a str> + Send + Sync
+{
+}
@@ -0,0 +1,100 @@
+use crate::{
+    Error,
+    prelude::*,
+    source_debug,
+};
+
+/// A `Source` that reads secrets from the system keyring.
+///
+/// Each key names an entry of the service, and the secret stored in the
+/// entry becomes a `Value::String` at that key, so the entry `db.password`
+/// is read into `{ db: { password: ... } }`.
+///
+/// Entries are read from the default store of `keyring-core`, which the
+/// application sets with `keyring_core::set_default_store`, e.g. to the
+/// native store of the platform. Secrets are read again on every parse, so
+/// `Realme::reload` picks up changes.
+pub struct KeyringSource {
+    /// The service the entries belong to.
+    service:  String,
+    /// The dotted keys, used as entry names.
+    keys:     Vec<String>,
+    /// Whether a missing entry is an error.
+    required: bool,
+}
+
+source_debug!(KeyringSource);
+
+impl KeyringSource {
+    /// Constructs a new `KeyringSource`.
+    ///
+    /// Missing entries are skipped, see `KeyringSource::required`.
+    ///
+    /// # Arguments
+    ///
+    /// * `service` - The service the entries belong to.
+    /// * `keys` - The dotted keys to read, which are also the entry names.
+    pub fn new<S, K>(service: S, keys: &[K]) -> Self
+    where
+        S: Into<String>,
+        K: AsRef<str>,
+    {
+        Self {
+            service:  service.into(),
+            keys:     keys.iter().map(|k| k.as_ref().to_string()).collect(),
+            required: false,
+        }
+    }
+
+    /// Sets whether a missing entry makes parsing fail instead of being
+    /// skipped.
+    #[must_use]
+    pub const fn required(mut self, required: bool) -> Self {
+        self.required = required;
+        self
+    }
+
+    /// Reads the secret of the entry `key`, `None` when it is missing and
+    /// not required.
+    fn read(&self, key: &str) -> Result<Option<String>, Error> {
+        let error = |e: keyring_core::Error| {
+            Error::KeyringError(format!(
+                "failed to read `{key}` of service `{}`: {e}",
+                self.service
+            ))
+        };
+        let entry =
+            keyring_core::Entry::new(&self.service, key).map_err(error)?;
+        match entry.get_password() {
+            Ok(secret) => Ok(Some(secret)),
+            Err(keyring_core::Error::NoEntry) if !self.required => Ok(None),
+            Err(e @ keyring_core::Error::NoEntry) => {
+                Err(Error::Unavailable(error(e).to_string()))
+            }
+            Err(e) => Err(error(e)),
+        }
+    }
+}
+
+impl Source for KeyringSource {
+    type Error = Error;
+    type Value = Value;
+
+    fn parse(&self) -> Result<Value, Error> {
+        let mut secrets = Vec::with_capacity(self.keys.len());
+        for key in &self.keys {
+            if let Some(secret) = self.read(key)? {
+                secrets.push((key, Value::String(secret)));
+            }
+        }
+        Value::from_dotted(secrets)
+    }
+
+    #[cfg(feature = "watch")]
+    fn watcher(
+        &self,
+        _s: crossbeam::channel::Sender<()>,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
@@ -0,0 +1,147 @@
+use std::{
+    path::{
+        Path,
+        PathBuf,
+    },
+    sync::Mutex,
+};
+
+use crate::{
+    Error,
+    prelude::*,
+    source_debug,
+};
+
+/// Name of the symlink Kubernetes swaps to update a mounted volume.
+const DATA_LINK: &str = "..data";
+
+/// A `Source` that reads a mounted Kubernetes `ConfigMap` or `Secret`.
+///
+/// Each file of the directory is a key holding the content of the file as
+/// a `Value::String`, so the file `log_level` is read into
+/// `{ log_level: ... }`. File names are kept verbatim, dots included.
+///
+/// Kubernetes updates mounted volumes atomically: the files are symlinks
+/// into the `..data` symlink, which points to a timestamped directory and
+/// is swapped on update. This source resolves `..data` once per parse and
+/// reads every file from the directory it points to, so a parse never mixes
+/// two versions, and hidden entries starting with `.` are skipped. A plain
+/// directory without `..data` is read as is.
+pub struct KubeDirSource {
+    /// The directory the volume is mounted at.
+    dir:       PathBuf,
+    /// Whether the values are secrets, see `KubeDirSource::sensitive`.
+    sensitive: bool,
+    /// Whether surrounding whitespace is trimmed from the values.
+    trim:      bool,
+    /// The directory `..data` pointed to on the last parse.
+    seen:      Mutex<Option<PathBuf>>,
+}
+
+source_debug!(KubeDirSource);
+
+impl KubeDirSource {
+    /// Constructs a new `KubeDirSource`.
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - The directory the `ConfigMap` or `Secret` is mounted at.
+    pub fn new<P: Into<PathBuf>>(dir: P) -> Self {
+        Self {
+            dir:       dir.into(),
+            sensitive: false,
+            trim:      false,
+            seen:      Mutex::new(None),
+        }
+    }
+
+    /// Marks the values as secrets, as for a mounted `Secret`, so they are
+    /// left out of every export of the configuration.
+    #[must_use]
+    pub const fn sensitive(mut self) -> Self {
+        self.sensitive = true;
+        self
+    }
+
+    /// Trims surrounding whitespace, such as a trailing newline, from the
+    /// values.
+    #[must_use]
+    pub const fn trim_values(mut self) -> Self {
+        self.trim = true;
+        self
+    }
+
+    /// Returns the directory holding the current version of the files: the
+    /// target of `..data` if any, the mounted directory otherwise.
+    fn data_dir(&self) -> PathBuf {
+        std::fs::canonicalize(self.dir.join(DATA_LINK))
+            .unwrap_or_else(|_| self.dir.clone())
+    }
+
+    /// Reads every visible file of `dir` into a table.
+    fn read_dir(&self, dir: &Path) -> Result<Map<String, Value>, Error> {
+        let error = |path: &Path, e: &std::io::Error| {
+            Error::new_read_error(
+                format!("Failed to read file: {}, error: {e}", path.display()),
+                e,
+            )
+        };
+        let mut map = Map::new();
+        for entry in std::fs::read_dir(dir).map_err(|e| error(dir, &e))? {
+            let path = entry.map_err(|e| error(dir, &e))?.path();
+            let Some(key) = path.file_name().and_then(|name| name.to_str())
+            else {
+                continue;
+            };
+            if key.starts_with('.') || !path.is_file() {
+                continue;
+            }
+            let content =
+                std::fs::read_to_string(&path).map_err(|e| error(&path, &e))?;
+            let content = if self.trim {
+                content.trim().to_string()
+            } else {
+                content
+            };
+            map.insert(key.to_string(), Value::String(content));
+        }
+        Ok(map)
+    }
+}
+
+impl Source for KubeDirSource {
+    type Error = Error;
+    type Value = Value;
+
+    fn parse(&self) -> Result<Value, Error> {
+        let data_dir = self.data_dir();
+        let map = self.read_dir(&data_dir)?;
+        *self
+            .seen
+            .lock()
+            .map_err(|e| Error::LockError(e.to_string()))? = Some(data_dir);
+        Ok(Value::Table(map))
+    }
+
+    fn is_sensitive(&self) -> bool {
+        self.sensitive
+    }
+
+    /// Compares the target of `..data` with the one of the last parse, so
+    /// only an update of the volume counts as a change. Without `..data`
+    /// the source can not tell.
+    fn has_changed(&self) -> Option<bool> {
+        let current = std::fs::canonicalize(self.dir.join(DATA_LINK)).ok()?;
+        let seen = self.seen.lock().ok()?;
+        Some(seen.as_ref() != Some(&current))
+    }
+
+    #[cfg(feature = "watch")]
+    fn watcher(
+        &self,
+        s: crossbeam::channel::Sender<()>,
+    ) -> Result<(), Self::Error> {
+        super::watch_path(&self.dir, s);
+        Ok(())
+    }
+}
@@ -49,3 +49,10 @@ where
         Ok(())
     }
 }
+
+impl<T, V> ParsedBy<T> for SerSource<T, V>
+where
+    T: Parser<V> + Sync + Send,
+    V: Serialize + Sync + Send,
+{
+}
@@ -53,3 +53,8 @@ where
         Ok(())
     }
 }
+
+impl<T> ParsedBy<T> for StringSource<T> where
+    T: for<'a> Parser<&'a str> + Send + Sync
+{
+}
@@ -0,0 +1,532 @@
+use std::{
+    path::PathBuf,
+    sync::{
+        Arc,
+        Mutex,
+    },
+    time::{
+        Duration,
+        Instant,
+    },
+};
+
+use serde_json::{
+    Value as JsonValue,
+    json,
+};
+
+use crate::{
+    Error,
+    prelude::*,
+    source_debug,
+    value::insert_nested,
+};
+
+/// Path of the service account token mounted into Kubernetes pods.
+const SERVICE_ACCOUNT_TOKEN: &str =
+    "/var/run/secrets/kubernetes.io/serviceaccount/token";
+
+/// How a `VaultSource` gets its client token.
+enum Auth {
+    /// A client token used as is.
+    Token(String),
+    /// A login with the `AppRole` auth method.
+    AppRole {
+        role_id:   String,
+        secret_id: String,
+    },
+    /// A login with the Kubernetes auth method, using the JWT of the
+    /// service account of the pod.
+    Kubernetes { role: String, jwt_path: PathBuf },
+}
+
+/// What a `VaultLease` was granted for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VaultLeaseKind {
+    /// A client token obtained by logging in.
+    Login,
+    /// A client token renewed before it expired.
+    Renewal,
+    /// A secret read with a lease, which should be read again before the
+    /// lease runs out. `KV` v2 secrets are not leased.
+    Secret,
+}
+
+/// A lease granted by Vault to a `VaultSource`, passed to the hook set with
+/// `VaultSource::on_lease`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VaultLease {
+    /// What the lease was granted for.
+    pub kind:      VaultLeaseKind,
+    /// How long the lease lasts from now.
+    pub duration:  Duration,
+    /// Whether Vault allows renewing the lease.
+    pub renewable: bool,
+}
+
+/// The hook set with `VaultSource::on_lease`.
+type LeaseHook = Arc<dyn Fn(&VaultLease) + Send + Sync>;
+
+/// A client token with the time its lease runs out, if it has one.
+struct ClientToken {
+    token:      String,
+    expires_at: Option<Instant>,
+    renewable:  bool,
+}
+
+/// A failed request to Vault.
+enum Failure {
+    /// Vault refused the token, which may have expired or been revoked.
+    Forbidden(Error),
+    /// Any other failure.
+    Other(Error),
+}
+
+impl From<Failure> for Error {
+    fn from(failure: Failure) -> Self {
+        match failure {
+            Failure::Forbidden(e) | Failure::Other(e) => e,
+        }
+    }
+}
+
+/// A `Source` that reads a secret from the `KV` v2 secrets engine of
+/// `HashiCorp` Vault.
+///
+/// The fields of the secret become keys of the configuration, nested under
+/// `prefix` if set. Every `parse` reads the secret again, so `reload` picks
+/// up new versions, and the values are sensitive: they are left out of every
+/// export of the configuration.
+///
+/// The source authenticates with a client token, or logs in with `AppRole`
+/// or Kubernetes auth. A token obtained by a login is cached with its lease:
+/// it is renewed once less than `renew_before` of the lease is left, and the
+/// source logs in again if renewing fails or Vault refuses the token. The
+/// leases granted along the way are passed to the `on_lease` hook, which can
+/// schedule a `Realme::reload` before they run out.
+///
+/// # Examples
+///
+/// ```rust ignore
+/// let realme = Realme::builder()
+///     .load(Adaptor::new(
+///         VaultSource::new("https://vault:8200", "secret", "myapp/db")
+///             .app_role(role_id, secret_id)
+///             .prefix("database"),
+///     ))
+///     .build()?;
+/// let password = realme.get_as::<String, _>("database.password");
+/// ```
+pub struct VaultSource {
+    addr:         String,
+    mount:        String,
+    path:         String,
+    auth:         Auth,
+    auth_mount:   Option<String>,
+    namespace:    Option<String>,
+    prefix:       Option<String>,
+    timeout:      Duration,
+    renew_before: Duration,
+    on_lease:     Option<LeaseHook>,
+    token:        Mutex<Option<ClientToken>>,
+}
+
+source_debug!(VaultSource);
+
+impl VaultSource {
+    /// Constructs a new `VaultSource`.
+    ///
+    /// Without an auth method, the token of the `VAULT_TOKEN` environment
+    /// variable is used. By default requests time out after 30 seconds and
+    /// tokens are renewed a minute before they expire.
+    ///
+    /// # Arguments
+    ///
+    /// * `addr` - The address of the Vault server, e.g. `https://vault:8200`.
+    /// * `mount` - The path the `KV` v2 engine is mounted at, e.g. `secret`.
+    /// * `path` - The path of the secret in the engine, e.g. `myapp/db`.
+    pub fn new<A, M, P>(addr: A, mount: M, path: P) -> Self
+    where
+        A: Into<String>,
+        M: Into<String>,
+        P: Into<String>,
+    {
+        Self {
+            addr:         addr.into().trim_end_matches('/').to_string(),
+            mount:        mount.into().trim_matches('/').to_string(),
+            path:         path.into().trim_matches('/').to_string(),
+            auth:         Auth::Token(
+                std::env::var("VAULT_TOKEN").unwrap_or_default(),
+            ),
+            auth_mount:   None,
+            namespace:    None,
+            prefix:       None,
+            timeout:      Duration::from_secs(30),
+            renew_before: Duration::from_mins(1),
+            on_lease:     None,
+            token:        Mutex::new(None),
+        }
+    }
+
+    /// Authenticates with a client token, used as is.
+    #[must_use]
+    pub fn token<T: Into<String>>(mut self, token: T) -> Self {
+        self.auth = Auth::Token(token.into());
+        self
+    }
+
+    /// Authenticates by logging in with the `AppRole` auth method.
+    #[must_use]
+    pub fn app_role<R, S>(mut self, role_id: R, secret_id: S) -> Self
+    where
+        R: Into<String>,
+        S: Into<String>,
+    {
+        self.auth = Auth::AppRole {
+            role_id:   role_id.into(),
+            secret_id: secret_id.into(),
+        };
+        self
+    }
+
+    /// Authenticates by logging in with the Kubernetes auth method, using
+    /// the service account token mounted into the pod.
+    ///
+    /// # Arguments
+    ///
+    /// * `role` - The Vault role bound to the service account.
+    #[must_use]
+    pub fn kubernetes<R: Into<String>>(mut self, role: R) -> Self {
+        self.auth = Auth::Kubernetes {
+            role:     role.into(),
+            jwt_path: PathBuf::from(SERVICE_ACCOUNT_TOKEN),
+        };
+        self
+    }
+
+    /// Sets the file the Kubernetes auth method reads the service account
+    /// token from, instead of the one mounted into pods.
+    #[must_use]
+    pub fn kubernetes_jwt_path<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        if let Auth::Kubernetes { jwt_path, .. } = &mut self.auth {
+            *jwt_path = path.into();
+        }
+        self
+    }
+
+    /// Sets the path the auth method is mounted at, when it is not the
+    /// default `approle` or `kubernetes`.
+    #[must_use]
+    pub fn auth_mount<M: Into<String>>(mut self, mount: M) -> Self {
+        self.auth_mount = Some(mount.into().trim_matches('/').to_string());
+        self
+    }
+
+    /// Sets the Vault Enterprise namespace of the requests.
+    #[must_use]
+    pub fn namespace<N: Into<String>>(mut self, namespace: N) -> Self {
+        self.namespace = Some(namespace.into());
+        self
+    }
+
+    /// Nests the fields of the secret under a dotted key, so the prefix
+    /// `database` reads the field `password` into `database.password`.
+    #[must_use]
+    pub fn prefix<P: Into<String>>(mut self, prefix: P) -> Self {
+        self.prefix = Some(prefix.into());
+        self
+    }
+
+    /// Sets how long a request may take, from connecting to reading the
+    /// whole body.
+    #[must_use]
+    pub const fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sets how long before the lease of the client token runs out it is
+    /// renewed.
+    #[must_use]
+    pub const fn renew_before(mut self, renew_before: Duration) -> Self {
+        self.renew_before = renew_before;
+        self
+    }
+
+    /// Sets a hook called with every lease Vault grants: on login, on
+    /// renewal of the client token, and on reading a leased secret.
+    ///
+    /// Secrets are only read again on `parse`, so the hook is the place to
+    /// schedule a `Realme::reload` before a lease runs out.
+    #[must_use]
+    pub fn on_lease<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&VaultLease) + Send + Sync + 'static,
+    {
+        self.on_lease = Some(Arc::new(hook));
+        self
+    }
+
+    /// Sends a request to the Vault API and returns the JSON response.
+    fn call(
+        &self,
+        method: &str,
+        path: &str,
+        token: Option<&str>,
+        body: Option<&JsonValue>,
+    ) -> Result<JsonValue, Failure> {
+        let url = format!("{}/v1/{path}", self.addr);
+        let agent = ureq::AgentBuilder::new().timeout(self.timeout).build();
+        let mut request = agent.request(method, &url);
+        if let Some(token) = token {
+            request = request.set("X-Vault-Token", token);
+        }
+        if let Some(namespace) = &self.namespace {
+            request = request.set("X-Vault-Namespace", namespace);
+        }
+        let response = match body {
+            Some(body) => request
+                .set("Content-Type", "application/json")
+                .send_string(&body.to_string()),
+            None => request.call(),
+        };
+        let response = match response {
+            Ok(response) => response,
+            Err(ureq::Error::Status(status, response)) => {
+                let errors = response
+                    .into_string()
+                    .ok()
+                    .and_then(|body| {
+                        serde_json::from_str::<JsonValue>(&body).ok()
+                    })
+                    .and_then(|body| {
+                        Some(
+                            body.get("errors")?
+                                .as_array()?
+                                .iter()
+                                .filter_map(JsonValue::as_str)
+                                .collect::<Vec<_>>()
+                                .join(", "),
+                        )
+                    })
+                    .filter(|errors| !errors.is_empty())
+                    .map(|errors| format!(": {errors}"))
+                    .unwrap_or_default();
+                let err =
+                    format!("{url} responded with status {status}{errors}");
+                return Err(match status {
+                    403 => Failure::Forbidden(Error::VaultError(err)),
+                    404 => Failure::Other(Error::Unavailable(err)),
+                    _ => Failure::Other(Error::VaultError(err)),
+                });
+            }
+            Err(ureq::Error::Transport(e)) => {
+                let err = format!("failed to reach {url}: {e}");
+                return Err(Failure::Other(if super::is_unreachable(&e) {
+                    Error::Unavailable(err)
+                } else {
+                    Error::VaultError(err)
+                }));
+            }
+        };
+        let body = response.into_string().map_err(|e| {
+            Failure::Other(Error::VaultError(format!(
+                "failed to read the response from {url}: {e}"
+            )))
+        })?;
+        serde_json::from_str(&body).map_err(|e| {
+            Failure::Other(Error::VaultError(format!(
+                "invalid response from {url}: {e}"
+            )))
+        })
+    }
+
+    /// Calls the hook, if any, with a lease of `duration` seconds.
+    fn report(&self, kind: VaultLeaseKind, duration: u64, renewable: bool) {
+        if let Some(hook) = &self.on_lease {
+            hook(&VaultLease {
+                kind,
+                duration: Duration::from_secs(duration),
+                renewable,
+            });
+        }
+    }
+
+    /// Reads the `auth` block of a login or renewal response into a client
+    /// token, reporting its lease.
+    fn read_auth(
+        &self,
+        kind: VaultLeaseKind,
+        response: &JsonValue,
+    ) -> Result<ClientToken, Error> {
+        let auth = &response["auth"];
+        let token = auth["client_token"].as_str().ok_or_else(|| {
+            Error::VaultError("the response has no client token".to_string())
+        })?;
+        let duration = auth["lease_duration"].as_u64().unwrap_or_default();
+        let renewable = auth["renewable"].as_bool().unwrap_or_default();
+        self.report(kind, duration, renewable);
+        Ok(ClientToken {
+            token: token.to_string(),
+            expires_at: (duration > 0)
+                .then(|| Instant::now() + Duration::from_secs(duration)),
+            renewable,
+        })
+    }
+
+    /// Logs in with the auth method of the source.
+    fn login(&self) -> Result<ClientToken, Error> {
+        let (default_mount, body) = match &self.auth {
+            Auth::Token(token) => {
+                return Ok(ClientToken {
+                    token:      token.clone(),
+                    expires_at: None,
+                    renewable:  false,
+                });
+            }
+            Auth::AppRole { role_id, secret_id } => (
+                "approle",
+                json!({ "role_id": role_id, "secret_id": secret_id }),
+            ),
+            Auth::Kubernetes { role, jwt_path } => {
+                let jwt = std::fs::read_to_string(jwt_path).map_err(|e| {
+                    Error::VaultError(format!(
+                        "failed to read the service account token {}: {e}",
+                        jwt_path.display()
+                    ))
+                })?;
+                ("kubernetes", json!({ "role": role, "jwt": jwt.trim() }))
+            }
+        };
+        let mount = self.auth_mount.as_deref().unwrap_or(default_mount);
+        let response = self.call(
+            "POST",
+            &format!("auth/{mount}/login"),
+            None,
+            Some(&body),
+        )?;
+        self.read_auth(VaultLeaseKind::Login, &response)
+    }
+
+    /// Returns a valid client token, renewing the cached one or logging in
+    /// as needed.
+    fn valid_token(&self) -> Result<String, Error> {
+        let mut cached = self
+            .token
+            .lock()
+            .map_err(|e| Error::LockError(e.to_string()))?;
+        if let Some(current) = cached.as_ref() {
+            let expiring = current.expires_at.is_some_and(|expires_at| {
+                Instant::now() + self.renew_before >= expires_at
+            });
+            if !expiring {
+                return Ok(current.token.clone());
+            }
+            if current.renewable {
+                let renewed = self
+                    .call(
+                        "POST",
+                        "auth/token/renew-self",
+                        Some(&current.token),
+                        Some(&json!({})),
+                    )
+                    .map_err(Error::from)
+                    .and_then(|response| {
+                        self.read_auth(VaultLeaseKind::Renewal, &response)
+                    });
+                match renewed {
+                    Ok(renewed) => {
+                        let token = renewed.token.clone();
+                        *cached = Some(renewed);
+                        return Ok(token);
+                    }
+                    #[allow(unused_variables)]
+                    Err(e) => {
+                        #[cfg(feature = "tracing")]
+                        tracing::warn!("failed to renew the Vault token: {e}");
+                    }
+                }
+            }
+        }
+        let login = self.login()?;
+        let token = login.token.clone();
+        *cached = Some(login);
+        Ok(token)
+    }
+
+    /// Forgets the cached client token, so the next request logs in again.
+    fn forget_token(&self) -> Result<(), Error> {
+        *self
+            .token
+            .lock()
+            .map_err(|e| Error::LockError(e.to_string()))? = None;
+        Ok(())
+    }
+
+    /// Reads the data of the secret.
+    fn read(&self, token: &str) -> Result<JsonValue, Failure> {
+        let mut response = self.call(
+            "GET",
+            &format!("{}/data/{}", self.mount, self.path),
+            Some(token),
+            None,
+        )?;
+        let duration = response["lease_duration"].as_u64().unwrap_or_default();
+        if duration > 0 {
+            let renewable = response["renewable"].as_bool().unwrap_or_default();
+            self.report(VaultLeaseKind::Secret, duration, renewable);
+        }
+        match response["data"]["data"].take() {
+            data @ JsonValue::Object(_) => Ok(data),
+            _ => Err(Failure::Other(Error::VaultError(format!(
+                "the secret {}/{} has no data",
+                self.mount, self.path
+            )))),
+        }
+    }
+}
+
+impl Source for VaultSource {
+    type Error = Error;
+    type Value = Value;
+
+    fn parse(&self) -> Result<Value, Error> {
+        let token = self.valid_token()?;
+        let data = match self.read(&token) {
+            Err(Failure::Forbidden(_))
+                if !matches!(self.auth, Auth::Token(_)) =>
+            {
+                self.forget_token()?;
+                let token = self.valid_token()?;
+                self.read(&token)?
+            }
+            data => data?,
+        };
+        let value = Value::try_serialize(&data)?;
+        let parts: Vec<&str> = self
+            .prefix
+            .as_deref()
+            .unwrap_or_default()
+            .split('.')
+            .filter(|part| !part.is_empty())
+            .collect();
+        if parts.is_empty() {
+            return Ok(value);
+        }
+        let mut map = Map::new();
+        insert_nested(&mut map, &parts, value);
+        Ok(Value::Table(map))
+    }
+
+    fn is_sensitive(&self) -> bool {
+        true
+    }
+
+    #[cfg(feature = "watch")]
+    fn watcher(
+        &self,
+        _s: crossbeam::channel::Sender<()>,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
@@ -0,0 +1,119 @@
+//! A builder mirroring the `ConfigBuilder` of the `config` crate, to ease
+//! migrating to `Realme`.
+
+use serde::Serialize;
+
+use crate::{
+    Map,
+    Result,
+    prelude::*,
+};
+
+/// A builder with the surface of `config::ConfigBuilder`, producing a
+/// `Realme`.
+///
+/// Sources are added with `add_source` in order of increasing priority, as
+/// with the `config` crate. Values set with `set_default` lie below every
+/// source, and values set with `set_override` above them. Where the
+/// `config` crate deals in `config::Value`, this builder takes anything
+/// serializable into a realme `Value`.
+///
+/// # Examples
+///
+/// ```rust
+/// use realme::{
+///     compat::ConfigBuilder,
+///     prelude::*,
+/// };
+/// use serde_json::json;
+///
+/// let realme = ConfigBuilder::new()
+///     .set_default("server.port", 8080)
+///     .expect("set default")
+///     .add_source(Adaptor::new(SerSource::<SerParser, _>::new(json!({
+///         "server": { "host": "example.com", "port": 9090 }
+///     }))))
+///     .set_override("server.host", "localhost")
+///     .expect("set override")
+///     .build()
+///     .expect("build config");
+///
+/// assert_eq!(realme.get_as::<u16, _>("server.port"), Some(9090));
+/// assert_eq!(
+///     realme.get_as::<String, _>("server.host").as_deref(),
+///     Some("localhost")
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ConfigBuilder {
+    builder:   RealmeBuilder,
+    defaults:  Value,
+    /// Override values by dotted key.
+    overrides: Map<String, Value>,
+}
+
+impl ConfigBuilder {
+    /// Creates a new `ConfigBuilder`.
+    pub fn new() -> Self {
+        Self {
+            builder:   RealmeBuilder::new(),
+            defaults:  Value::Table(Map::new()),
+            overrides: Map::new(),
+        }
+    }
+
+    /// Adds a source, taking precedence over the sources added before it.
+    ///
+    /// Maps to `RealmeBuilder::load`: adaptors of the same priority are
+    /// merged in the order they are added.
+    #[must_use]
+    pub fn add_source<A: Into<Adaptor>>(mut self, source: A) -> Self {
+        self.builder = self.builder.load(source);
+        self
+    }
+
+    /// Sets the default value of `key`, used when no source sets it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `value` can not be serialized or `key` is not a
+    /// valid key.
+    pub fn set_default<K, V>(mut self, key: K, value: V) -> Result<Self>
+    where
+        K: AsRef<str>,
+        V: Serialize,
+    {
+        self.defaults
+            .set(key.as_ref(), Value::try_serialize(&value)?)?;
+        Ok(self)
+    }
+
+    /// Sets the value of `key`, taking precedence over every source.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `value` can not be serialized.
+    pub fn set_override<K, V>(mut self, key: K, value: V) -> Result<Self>
+    where
+        K: Into<String>,
+        V: Serialize,
+    {
+        self.overrides
+            .insert(key.into(), Value::try_serialize(&value)?);
+        Ok(self)
+    }
+
+    /// Builds the `Realme`, merging the defaults, the sources and the
+    /// overrides.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a source fails to load, see
+    /// `RealmeBuilder::build`, or if two overrides conflict, see
+    /// `Realme::overlay`.
+    pub fn build(self) -> Result<Realme> {
+        let mut realme = self.builder.defaults(&self.defaults).build()?;
+        realme.overlay(self.overrides)?;
+        Ok(realme)
+    }
+}
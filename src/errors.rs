@@ -21,12 +21,35 @@ pub enum Error {
     BuildError(String),
     #[error("Read file error: {0}")]
     ReadFileError(String),
+    /// The source does not exist or can not be reached, such as a missing
+    /// file, a refused connection or an HTTP 404. `Adaptor::optional`
+    /// skips sources failing with this error.
+    #[error("Unavailable source: {0}")]
+    Unavailable(String),
+    #[error("Write file error: {0}")]
+    WriteFileError(String),
     #[error("Tera error: {0}")]
     TeraError(String),
     #[error("Watcher error: {0}")]
     WatcherError(String),
     #[error("Lock error: {0}")]
     LockError(String),
+    #[error("Cache error: {0}")]
+    CacheError(String),
+    #[error("Git error: {0}")]
+    GitError(String),
+    #[error("HTTP error: {0}")]
+    HttpError(String),
+    #[error("Archive error: {0}")]
+    ArchiveError(String),
+    #[error("Keyring error: {0}")]
+    KeyringError(String),
+    #[error("Vault error: {0}")]
+    VaultError(String),
+    #[error("Interpolation error at `{key}`: {cause}")]
+    InterpolationError { key: String, cause: String },
+    #[error("Validation error: {}", join_validation_errors(.0))]
+    ValidationError(Vec<crate::ValidationError>),
 
     #[error(transparent)]
     DeserializeError(DeserializeError),
@@ -40,6 +63,15 @@ pub enum Error {
 /// Convenience type alias for this crate's error type
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// Formats the errors of a failed validation on a single line.
+fn join_validation_errors(errors: &[crate::ValidationError]) -> String {
+    errors
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
 impl Error {
     /// Creates a new `InvalidCast` error.
     pub fn new_cast_error(origin: String, cause: String) -> Self {
@@ -51,6 +83,20 @@ impl Error {
         Self::ParseError(ParseError::new(origin, cause))
     }
 
+    /// Creates a `ReadFileError` with `message` for a file that could not be
+    /// read because of `cause`, or an `Unavailable` error when the file does
+    /// not exist.
+    pub(crate) fn new_read_error(
+        message: String,
+        cause: &std::io::Error,
+    ) -> Self {
+        if cause.kind() == std::io::ErrorKind::NotFound {
+            Self::Unavailable(message)
+        } else {
+            Self::ReadFileError(message)
+        }
+    }
+
     /// Creates a new `BuildError`.
     #[allow(clippy::missing_const_for_fn)]
     pub fn new_build_error(cause: String) -> Self {
@@ -1,5 +1,7 @@
 #![allow(dead_code)]
 mod adaptor;
+#[cfg(feature = "compat-config")]
+pub mod compat;
 pub mod errors;
 pub mod prelude;
 pub mod utils;
@@ -8,33 +10,86 @@ pub mod realme;
 pub mod value;
 
 #[cfg(feature = "cmd")]
-pub use adaptor::parser::cmd::CmdParser;
+pub use adaptor::parser::cmd::{
+    CmdParser,
+    CommaArrayCmdParser,
+    SpaceSeparatedCmdParser,
+};
+#[cfg(feature = "dotenv")]
+pub use adaptor::parser::dotenv::{
+    DotenvParser,
+    NestedDotenvParser,
+};
 #[cfg(feature = "env")]
 pub use adaptor::parser::env::EnvParser;
+#[cfg(feature = "hcl")]
+pub use adaptor::parser::hcl::HclParser;
+#[cfg(feature = "hocon")]
+pub use adaptor::parser::hocon::HoconParser;
 #[cfg(feature = "ini")]
 pub use adaptor::parser::ini::IniParser;
 #[cfg(feature = "json")]
-pub use adaptor::parser::json::JsonParser;
+pub use adaptor::parser::json::{
+    DuplicateKeys,
+    JsonParser,
+    LosslessJsonParser,
+    StrictJsonParser,
+};
 #[cfg(feature = "json5")]
 pub use adaptor::parser::json5::Json5Parser;
+#[cfg(feature = "kdl")]
+pub use adaptor::parser::kdl::KdlParser;
+#[cfg(feature = "msgpack")]
+pub use adaptor::parser::msgpack::MsgPackParser;
+#[cfg(feature = "nginx")]
+pub use adaptor::parser::nginx::NginxParser;
+#[cfg(feature = "properties")]
+pub use adaptor::parser::properties::PropertiesParser;
 #[cfg(feature = "ron")]
 pub use adaptor::parser::ron::RonParser;
 #[cfg(feature = "toml")]
-pub use adaptor::parser::toml::TomlParser;
+pub use adaptor::parser::toml::{
+    LosslessTomlParser,
+    TomlParser,
+};
 #[cfg(feature = "yaml")]
 pub use adaptor::parser::yaml::YamlParser;
+#[cfg(feature = "archive")]
+pub use adaptor::source::archive::ArchiveSource;
 #[cfg(feature = "cmd")]
 pub use adaptor::source::cmd::CmdSource;
 #[cfg(feature = "env")]
 pub use adaptor::source::env::EnvSource;
+#[cfg(feature = "git")]
+pub use adaptor::source::git::GitSource;
+#[cfg(feature = "http")]
+pub use adaptor::source::http::HttpSource;
+#[cfg(feature = "keyring")]
+pub use adaptor::source::keyring::KeyringSource;
+#[cfg(feature = "kube")]
+pub use adaptor::source::kube::KubeDirSource;
+#[cfg(feature = "vault")]
+pub use adaptor::source::vault::{
+    VaultLease,
+    VaultLeaseKind,
+    VaultSource,
+};
 pub use adaptor::{
     Adaptor,
+    MergeStrategy,
     parser::{
+        Emitter,
+        FloatFormat,
+        LargeNumbers,
         Parser,
         ser::SerParser,
     },
     source::{
+        ParsedBy,
         Source,
+        bytes::BytesSource,
+        cached::CachedSource,
+        dir::DirSource,
         file::FileSource,
         ser::SerSource,
         string::StringSource,
@@ -42,13 +97,30 @@ pub use adaptor::{
 };
 pub use errors::Error;
 pub(crate) use errors::Result;
-#[cfg(feature = "watch")]
-pub use realme::SharedRealme;
 pub use realme::{
+    EmptyValues,
+    MergeAudit,
+    MissingPolicy,
     Realme,
     RealmeBuilder,
+    SourceInfo,
+};
+#[cfg(feature = "watch")]
+pub use realme::{
+    SharedRealme,
+    SharedRealmeExt,
+    Subscription,
 };
 #[cfg(feature = "macros")]
 pub use realme_macros::*;
 pub use utils::Map;
-pub use value::Value;
+#[cfg(feature = "json")]
+pub use value::JsonFormat;
+pub use value::{
+    Datetime,
+    Expression,
+    ValidationError,
+    Validator,
+    Value,
+    ValueKind,
+};
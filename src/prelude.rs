@@ -1,41 +1,101 @@
 //! Prelude for the crate
 
-#[cfg(feature = "watch")]
-pub use crate::SharedRealme;
+#[cfg(feature = "json")]
+pub use crate::JsonFormat;
 #[cfg(feature = "cmd")]
-pub use crate::adaptor::parser::cmd::CmdParser;
+pub use crate::adaptor::parser::cmd::{
+    CmdParser,
+    CommaArrayCmdParser,
+    SpaceSeparatedCmdParser,
+};
+#[cfg(feature = "dotenv")]
+pub use crate::adaptor::parser::dotenv::{
+    DotenvParser,
+    NestedDotenvParser,
+};
 #[cfg(feature = "env")]
 pub use crate::adaptor::parser::env::EnvParser;
+#[cfg(feature = "hcl")]
+pub use crate::adaptor::parser::hcl::HclParser;
+#[cfg(feature = "hocon")]
+pub use crate::adaptor::parser::hocon::HoconParser;
 #[cfg(feature = "ini")]
 pub use crate::adaptor::parser::ini::IniParser;
 #[cfg(feature = "json")]
-pub use crate::adaptor::parser::json::JsonParser;
+pub use crate::adaptor::parser::json::{
+    DuplicateKeys,
+    JsonParser,
+    LosslessJsonParser,
+    StrictJsonParser,
+};
 #[cfg(feature = "json5")]
 pub use crate::adaptor::parser::json5::Json5Parser;
+#[cfg(feature = "kdl")]
+pub use crate::adaptor::parser::kdl::KdlParser;
+#[cfg(feature = "msgpack")]
+pub use crate::adaptor::parser::msgpack::MsgPackParser;
+#[cfg(feature = "nginx")]
+pub use crate::adaptor::parser::nginx::NginxParser;
+#[cfg(feature = "properties")]
+pub use crate::adaptor::parser::properties::PropertiesParser;
 #[cfg(feature = "ron")]
 pub use crate::adaptor::parser::ron::RonParser;
 #[cfg(feature = "toml")]
-pub use crate::adaptor::parser::toml::TomlParser;
+pub use crate::adaptor::parser::toml::{
+    LosslessTomlParser,
+    TomlParser,
+};
 #[cfg(feature = "yaml")]
 pub use crate::adaptor::parser::yaml::YamlParser;
+#[cfg(feature = "archive")]
+pub use crate::adaptor::source::archive::ArchiveSource;
 #[cfg(feature = "cmd")]
 pub use crate::adaptor::source::cmd::CmdSource;
 #[cfg(feature = "env")]
 pub use crate::adaptor::source::env::EnvSource;
-#[cfg(feature = "macros")]
-pub use crate::builder;
+#[cfg(feature = "git")]
+pub use crate::adaptor::source::git::GitSource;
+#[cfg(feature = "http")]
+pub use crate::adaptor::source::http::HttpSource;
+#[cfg(feature = "keyring")]
+pub use crate::adaptor::source::keyring::KeyringSource;
+#[cfg(feature = "kube")]
+pub use crate::adaptor::source::kube::KubeDirSource;
+#[cfg(feature = "vault")]
+pub use crate::adaptor::source::vault::{
+    VaultLease,
+    VaultLeaseKind,
+    VaultSource,
+};
 pub use crate::{
+    Datetime,
+    EmptyValues,
+    Expression,
+    MergeAudit,
+    MissingPolicy,
     Realme,
     RealmeBuilder,
+    SourceInfo,
+    ValidationError,
+    Validator,
     Value,
+    ValueKind,
     adaptor::{
         Adaptor,
+        MergeStrategy,
         parser::{
+            Emitter,
+            FloatFormat,
+            LargeNumbers,
             Parser,
             ser::SerParser,
         },
         source::{
+            ParsedBy,
             Source,
+            bytes::BytesSource,
+            cached::CachedSource,
+            dir::DirSource,
             file::FileSource,
             ser::SerSource,
             string::StringSource,
@@ -46,3 +106,14 @@ pub use crate::{
         W,
     },
 };
+#[cfg(feature = "watch")]
+pub use crate::{
+    SharedRealme,
+    SharedRealmeExt,
+    Subscription,
+};
+#[cfg(feature = "macros")]
+pub use crate::{
+    builder,
+    key,
+};
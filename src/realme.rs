@@ -1,9 +1,20 @@
 //! This module contains the core `Realme` struct and its builder.
 
 pub mod api;
+mod audit;
 pub mod builder;
+#[cfg(feature = "cache")]
+mod cache;
+mod deferred;
+mod empty;
+mod interpolate;
+mod lowercase;
+mod origin;
 #[cfg(feature = "watch")]
 mod shared;
+mod subscribe;
+mod write;
+use std::sync::OnceLock;
 #[cfg(feature = "watch")]
 use std::sync::{
     Arc,
@@ -16,47 +27,133 @@ use serde::{
     de::DeserializeOwned,
 };
 
+pub use self::{
+    audit::MergeAudit,
+    empty::EmptyValues,
+    interpolate::MissingPolicy,
+    origin::SourceInfo,
+};
+use self::{
+    deferred::Deferred,
+    interpolate::Interpolation,
+    lowercase::Lowercase,
+    origin::Origins,
+    subscribe::Subscribers,
+};
+#[cfg(feature = "watch")]
+pub use self::{
+    shared::SharedRealmeExt,
+    subscribe::Subscription,
+};
 use crate::{
     Result,
     prelude::*,
+    value::ValueDeserializer,
 };
 /// Represents a configuration realme with a cache for storing configuration
 /// values.
 #[derive(Deserialize, Clone)]
 pub struct Realme {
     /// The cache storing configuration values.
-    cache:   Value,
+    cache:          Value,
     /// The default configuration values.
     #[serde(skip)]
-    default: Option<Value>,
+    default:        Option<Value>,
     /// The builder used to construct this Realme instance.
     #[serde(skip)]
-    builder: RealmeBuilder,
+    builder:        RealmeBuilder,
+    /// The values of the default layer, if the builder has one.
+    #[serde(skip)]
+    defaults_layer: Option<Value>,
+    /// Callbacks notified when watched keys change.
+    #[serde(skip)]
+    subscribers:    Subscribers,
+    /// Dotted keys provided by sensitive sources, left out of exports.
+    #[serde(skip)]
+    sensitive:      Vec<String>,
+    /// The source that provided each key, see `Realme::origin`.
+    #[serde(skip)]
+    origins:        Origins,
+    /// The cache with the deferred values resolved, built when a table
+    /// holding a deferred key is read.
+    #[serde(skip)]
+    resolved:       OnceLock<Value>,
 }
 
 /// Builder for constructing a `Realme` instance.
 #[derive(Default, Clone, Debug)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct RealmeBuilder {
     /// List of adaptors used to load configuration.
-    adaptors: Vec<Adaptor>,
+    adaptors:       Vec<Adaptor>,
+    /// Adaptors forming the default layer, below every other adaptor.
+    defaults:       Vec<Adaptor>,
     /// Optional profile name for configuration.
-    profile:  Option<String>,
+    profile:        Option<String>,
+    /// Human readable descriptions attached to keys.
+    annotations:    Map<String, String>,
+    /// Settings of the `${...}` interpolation.
+    interpolation:  Interpolation,
+    /// Whether numbers only deserialize into targets of their own kind.
+    strict_numbers: bool,
+    /// How floats are written back out.
+    float_format:   FloatFormat,
+    /// How empty values are represented after merging.
+    empty_values:   EmptyValues,
+    /// String values lowercased after merging.
+    lowercase:      Lowercase,
+    /// Keys whose one-element arrays are replaced by their element.
+    unwrap_arrays:  Vec<String>,
+    /// Key of the table holding per-profile sections.
+    profile_key:    Option<String>,
+    /// Whether a layer may not change the kind of a value set below it.
+    strict_types:   bool,
+    /// Whether files listed under `include` are merged.
+    includes:       bool,
+    /// Maximum nesting depth accepted from a source.
+    max_depth:      Option<usize>,
+    /// Values resolved on first access.
+    deferred:       Vec<Deferred>,
+    /// Checks run against the merged configuration.
+    validators:     Vec<Validator>,
+    /// Whether `shared_build` watches the sources of every adaptor.
+    #[cfg(feature = "watch")]
+    watch:          bool,
 }
 
 #[cfg(feature = "watch")]
 /// A thread-safe shared reference to a `Realme` instance.
-pub type SharedRealme = Arc<RwLock<Realme>>;
+///
+/// Reloads rebuild the configuration without holding the lock and swap in
+/// the new instance, so `SharedRealmeExt::current` hands out snapshots
+/// without copying them. Use `SharedRealmeExt::update` to change values.
+pub type SharedRealme = Arc<RwLock<Arc<Realme>>>;
 
 impl std::fmt::Debug for Realme {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
             "Realme {{ cache: {:?}, default: {:?} }}",
-            self.cache, self.default
+            self.redacted(),
+            self.default
         )
     }
 }
 
+impl Serialize for Realme {
+    /// Serializes the configuration values, leaving out the keys provided
+    /// by sensitive sources, see `Realme::redacted`. Floats are written as
+    /// set with `RealmeBuilder::float_format`.
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        let mut value = self.redacted();
+        self.builder.float_format.apply(&mut value);
+        value.serialize(serializer)
+    }
+}
+
 impl Realme {
     /// Creates a new `RealmeBuilder` for constructing a `Realme`.
     ///
@@ -76,6 +173,22 @@ impl Realme {
         RealmeBuilder::new()
     }
 
+    /// Creates a new `RealmeBuilder` watching the sources of all its
+    /// adaptors, to be built with `RealmeBuilder::shared_build`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust ignore
+    /// let realme = Realme::shared_builder()
+    ///     .load(Adaptor::new(FileSource::<TomlParser>::new("config.toml")))
+    ///     .shared_build()?;
+    /// let snapshot = realme.current()?;
+    /// ```
+    #[cfg(feature = "watch")]
+    pub fn shared_builder() -> RealmeBuilder {
+        RealmeBuilder::new().watch()
+    }
+
     /// Attempts to deserialize the realme's cache into a specified type.
     ///
     /// # Type Parameters
@@ -89,7 +202,48 @@ impl Realme {
     /// type if successful, or an `Err` containing a `Error` if the
     /// operation fails.
     pub fn try_deserialize<T: DeserializeOwned>(&self) -> Result<T> {
-        self.cache.clone().try_deserialize()
+        let mut cache = self.cache.clone();
+        self.apply_deferred(&mut cache, true);
+        T::deserialize(self.deserializer(cache)).map_err(Into::into)
+    }
+
+    /// Consumes the realme and deserializes the whole configuration into
+    /// `T`.
+    ///
+    /// A blanket `TryFrom<Realme>` for every `DeserializeOwned` type is not
+    /// possible, so this method stands in for `realme.try_into()`:
+    ///
+    /// ```rust
+    /// use realme::prelude::*;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct AppConfig {
+    ///     port: u16,
+    /// }
+    ///
+    /// let mut realme = Realme::builder().build().expect("build config");
+    /// realme.set("port", 8080).expect("set config");
+    /// let config: AppConfig =
+    ///     realme.try_into_config().expect("deserialize config");
+    /// assert_eq!(config.port, 8080);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the configuration can not be deserialized into
+    /// `T`.
+    pub fn try_into_config<T: DeserializeOwned>(mut self) -> Result<T> {
+        let mut cache = std::mem::take(&mut self.cache);
+        self.apply_deferred(&mut cache, true);
+        T::deserialize(self.deserializer(cache)).map_err(Into::into)
+    }
+
+    /// Returns a deserializer for `value` following the rules configured on
+    /// the builder.
+    pub(crate) fn deserializer(&self, value: Value) -> ValueDeserializer {
+        ValueDeserializer::new(value)
+            .with_strict_numbers(self.builder.strict_numbers)
     }
 
     /// Attempts to serialize a given object into a new `Realme` instance.
@@ -112,16 +266,22 @@ impl Realme {
     pub(crate) fn try_serialize<T: Serialize>(from: &T) -> Result<Self> {
         let cache = Value::try_serialize(from)?;
         Ok(Self {
-            cache:   cache.clone(),
-            default: Some(cache),
-            builder: RealmeBuilder::new(),
+            cache:          cache.clone(),
+            default:        Some(cache),
+            builder:        RealmeBuilder::new(),
+            defaults_layer: None,
+            subscribers:    Subscribers::default(),
+            sensitive:      Vec::new(),
+            origins:        Origins::default(),
+            resolved:       OnceLock::new(),
         })
     }
 
     /// Reloads the Realme instance from its builder.
     ///
     /// This method rebuilds the Realme instance using the current builder
-    /// configuration, and merges any default values if they exist.
+    /// configuration, and merges any default values if they exist. Callbacks
+    /// registered with `on_change` are notified of changed keys.
     ///
     /// # Returns
     ///
@@ -129,15 +289,131 @@ impl Realme {
     /// successful, or an `Err` containing a `Error` if the operation fails.
     pub fn reload(&mut self) -> Result<()> {
         let mut new_realme = self.builder.clone().build()?;
-        if let Some(default) = self.default.take() {
-            new_realme.cache.merge(&default);
-            new_realme.default = Some(default);
-        }
+        new_realme.take_over(self);
         *self = new_realme;
         Ok(())
     }
 
+    /// Keeps the runtime values and the callbacks of `previous`, the
+    /// instance this one was rebuilt from, and notifies the callbacks of the
+    /// keys whose value changed.
+    pub(crate) fn take_over(&mut self, previous: &Self) {
+        if let Some(default) = &previous.default {
+            self.cache.merge(default);
+            self.default = Some(default.clone());
+        }
+        self.subscribers = previous.subscribers.clone();
+        let snapshot = self.subscribers.snapshot(&previous.cache);
+        self.subscribers.notify(&snapshot, &self.cache);
+    }
+
+    /// Reloads the Realme instance if any of its sources changed.
+    ///
+    /// Sources report changes through `Source::has_changed`, such as a
+    /// `FileSource` polling the modification time of its file with
+    /// `FileSource::poll_changes`. When every source reports that it is
+    /// unchanged, nothing is read and the instance is left untouched.
+    /// Sources that can not tell are assumed to have changed.
+    ///
+    /// # Returns
+    ///
+    /// Returns whether the instance was reloaded.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reloading fails, see `reload`.
+    pub fn reload_if_changed(&mut self) -> Result<bool> {
+        let changed = self
+            .builder
+            .adaptors
+            .iter()
+            .chain(&self.builder.defaults)
+            .any(|adaptor| adaptor.has_changed() != Some(false));
+        if changed {
+            self.reload()?;
+        }
+        Ok(changed)
+    }
+
     // fn validate(&self) -> Result<()> {
     //     self.cache.validate()
     // }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct AppConfig {
+        name:   String,
+        server: Server,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Server {
+        host: String,
+        port: u16,
+    }
+
+    #[test]
+    fn test_try_into_config() -> anyhow::Result<()> {
+        let mut realme = Realme::builder().build()?;
+        realme.set("name", "realme")?;
+        realme.set("server.host", "localhost")?;
+        realme.set("server.port", 8080)?;
+
+        let config: AppConfig = realme.try_into_config()?;
+        assert_eq!(config, AppConfig {
+            name:   "realme".to_string(),
+            server: Server {
+                host: "localhost".to_string(),
+                port: 8080,
+            },
+        });
+        Ok(())
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_reload_if_changed() -> anyhow::Result<()> {
+        use std::time::{
+            Duration,
+            SystemTime,
+        };
+
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("app.toml");
+        let touch = |content: &str, secs: u64| -> std::io::Result<()> {
+            std::fs::write(&path, content)?;
+            std::fs::File::options()
+                .write(true)
+                .open(&path)?
+                .set_modified(
+                    SystemTime::UNIX_EPOCH + Duration::from_secs(secs),
+                )
+        };
+        touch("port = 8080", 1_000)?;
+
+        let mut realme = Realme::builder()
+            .load(Adaptor::new(
+                FileSource::<TomlParser>::new(&path).poll_changes(),
+            ))
+            .build()?;
+        assert!(!realme.reload_if_changed()?);
+
+        // Same modification time: the file is not read again.
+        touch("port = 9090", 1_000)?;
+        assert!(!realme.reload_if_changed()?);
+        realme.reload()?;
+        assert_eq!(realme.get_as::<u16, _>("port"), Some(8080));
+
+        touch("port = 9090", 2_000)?;
+        assert!(realme.reload_if_changed()?);
+        assert_eq!(realme.get_as::<u16, _>("port"), Some(9090));
+        assert!(!realme.reload_if_changed()?);
+        Ok(())
+    }
+}
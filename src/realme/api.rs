@@ -1,12 +1,19 @@
+use std::collections::HashMap;
+
 use serde::{
     Serialize,
     de::DeserializeOwned,
 };
 
 use crate::{
+    Error,
     Result,
     Value,
     prelude::*,
+    value::{
+        Array,
+        Table,
+    },
 };
 
 impl Realme {
@@ -37,7 +44,12 @@ impl Realme {
     /// }
     /// ```
     pub fn get<K: AsRef<str>>(&self, key: K) -> Option<&Value> {
-        self.cache.get(key.as_ref())
+        let key = key.as_ref();
+        match self.get_deferred(key) {
+            Some(value) => Some(value),
+            None if self.holds_deferred(key) => self.resolved().get(key),
+            None => self.cache.get(key),
+        }
     }
 
     /// Retrieves a mutable reference to the `Value` associated with the given
@@ -65,11 +77,15 @@ impl Realme {
     /// }
     /// ```
     pub fn get_mut<K: AsRef<str>>(&mut self, key: K) -> Option<&mut Value> {
+        self.resolved.take();
         self.cache.get_mut(key.as_ref())
     }
 
     /// Retrieves and deserializes the `Value` associated with the given key.
     ///
+    /// A key with a `*` wildcard, such as `servers[*].host`, deserializes
+    /// the array of the values matching it, see `get_all`.
+    ///
     /// # Arguments
     ///
     /// * `key` - The key to look up.
@@ -114,9 +130,164 @@ impl Realme {
     where
         V: DeserializeOwned,
     {
-        self.cache
-            .get(key.as_ref())
-            .and_then(|v| v.clone().try_deserialize().ok())
+        let key = key.as_ref();
+        let value = match self.get(key) {
+            Some(value) => value.clone(),
+            None if key
+                .parse::<Expression>()
+                .is_ok_and(|expr| expr.has_wildcard()) =>
+            {
+                Value::Array(self.get_all(key).into_iter().cloned().collect())
+            }
+            None => return None,
+        };
+        V::deserialize(self.deserializer(value)).ok()
+    }
+
+    /// Retrieves every `Value` matching the given key, in order.
+    ///
+    /// A `*` wildcard matches every element of an array, or every value of
+    /// a table in the order of their keys. A key without a wildcard returns
+    /// the value of `get`, if any.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::collections::HashMap;
+    ///
+    /// use realme::prelude::*;
+    ///
+    /// let mut realme = Realme::builder().build().expect("build config");
+    /// let servers = vec![
+    ///     HashMap::from([("host", "a")]),
+    ///     HashMap::from([("host", "b")]),
+    /// ];
+    /// realme.set("servers", servers).expect("set config");
+    ///
+    /// assert_eq!(realme.get_all("servers[*].host"), vec![
+    ///     &Value::from("a"),
+    ///     &Value::from("b")
+    /// ]);
+    /// ```
+    pub fn get_all<K: AsRef<str>>(&self, key: K) -> Vec<&Value> {
+        let key = key.as_ref();
+        match self.get_deferred(key) {
+            Some(value) => vec![value],
+            None if self.holds_deferred(key) ||
+                key.contains('*') && !self.builder.deferred.is_empty() =>
+            {
+                self.resolved().get_all(key)
+            }
+            None => self.cache.get_all(key),
+        }
+    }
+
+    /// Returns the value of `key` as a string.
+    ///
+    /// Numbers and booleans are formatted and null becomes an empty string.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidCast` naming the key if it is missing or holds
+    /// an array or a table.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use realme::prelude::*;
+    ///
+    /// let mut realme = Realme::builder().build().expect("build config");
+    /// realme.set("server.port", 8080).expect("set config");
+    /// assert_eq!(realme.get_string("server.port").expect("get port"), "8080");
+    /// ```
+    pub fn get_string<K: AsRef<str>>(&self, key: K) -> Result<String> {
+        self.get_cast(key.as_ref())
+    }
+
+    /// Returns the value of `key` as an integer.
+    ///
+    /// Strings holding an integer, such as `"30"`, are parsed, floats are
+    /// truncated and booleans are `0` or `1`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidCast` naming the key if it is missing or its
+    /// value can not be converted.
+    pub fn get_int<K: AsRef<str>>(&self, key: K) -> Result<i64> {
+        self.get_cast(key.as_ref())
+    }
+
+    /// Returns the value of `key` as a float.
+    ///
+    /// Strings holding a number, such as `"0.5"`, are parsed.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidCast` naming the key if it is missing or its
+    /// value can not be converted.
+    pub fn get_float<K: AsRef<str>>(&self, key: K) -> Result<f64> {
+        self.get_cast(key.as_ref())
+    }
+
+    /// Returns the value of `key` as a boolean.
+    ///
+    /// The strings `true`, `yes`, `on` and `1` are true, `false`, `no`,
+    /// `off` and `0` are false, and numbers are true unless zero.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidCast` naming the key if it is missing or its
+    /// value can not be converted.
+    pub fn get_bool<K: AsRef<str>>(&self, key: K) -> Result<bool> {
+        self.get_cast(key.as_ref())
+    }
+
+    /// Returns the array at `key`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidCast` naming the key if it is missing or is not
+    /// an array.
+    pub fn get_array<K: AsRef<str>>(&self, key: K) -> Result<Array> {
+        let key = key.as_ref();
+        match self.get_value(key)? {
+            Value::Array(array) => Ok(array.clone()),
+            value => Err(key_error(key, value)),
+        }
+    }
+
+    /// Returns the table at `key`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidCast` naming the key if it is missing or is not
+    /// a table.
+    pub fn get_table<K: AsRef<str>>(&self, key: K) -> Result<Table> {
+        let key = key.as_ref();
+        match self.get_value(key)? {
+            Value::Table(table) => Ok(table.clone()),
+            value => Err(key_error(key, value)),
+        }
+    }
+
+    /// Returns the value of `key`, failing if it is missing.
+    fn get_value(&self, key: &str) -> Result<&Value> {
+        self.get(key).ok_or_else(|| {
+            Error::new_cast_error(
+                format!("`{key}`"),
+                "key not found".to_string(),
+            )
+        })
+    }
+
+    /// Converts the value of `key` with its `TryFrom<Value>` implementation.
+    fn get_cast<T>(&self, key: &str) -> Result<T>
+    where
+        T: TryFrom<Value, Error = Error>,
+    {
+        T::try_from(self.get_value(key)?.clone()).map_err(|e| {
+            Error::new_cast_error(format!("`{key}`"), e.to_string())
+        })
     }
 
     /// Sets a value for the given key in the configuration.
@@ -159,7 +330,10 @@ impl Realme {
         value: V,
     ) -> Result<()> {
         let value = Value::try_serialize(&value)?;
+        self.clear_deferred(key.as_ref())?;
+        let snapshot = self.subscribers.snapshot(&self.cache);
         self.cache.set(key.as_ref(), value.clone())?;
+        self.subscribers.notify(&snapshot, &self.cache);
         if let Some(default) = &mut self.default {
             default.set(key.as_ref(), value)?;
         } else {
@@ -170,6 +344,82 @@ impl Realme {
         Ok(())
     }
 
+    /// Removes the value for the given key from the configuration, returning
+    /// it if it was present.
+    ///
+    /// The key accepts the syntax of `get`, such as `server.hosts[0]`. The
+    /// value is removed from the runtime values set with `set` too, but a
+    /// value provided by a source comes back on `reload`.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - A string slice that holds the key to remove.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use realme::prelude::*;
+    ///
+    /// let mut realme = Realme::builder().build().expect("build config");
+    /// realme.set("server.port", 8080).expect("set config");
+    /// assert_eq!(realme.remove("server.port"), Some(Value::Integer(8080)));
+    /// assert_eq!(realme.get("server.port"), None);
+    /// ```
+    pub fn remove<K: AsRef<str>>(&mut self, key: K) -> Option<Value> {
+        self.clear_deferred(key.as_ref()).ok()?;
+        if let Some(default) = &mut self.default {
+            default.remove(key.as_ref());
+        }
+        let snapshot = self.subscribers.snapshot(&self.cache);
+        let removed = self.cache.remove(key.as_ref());
+        if removed.is_some() {
+            self.subscribers.notify(&snapshot, &self.cache);
+        }
+        removed
+    }
+
+    /// Deep-merges a map of overrides over the configuration.
+    ///
+    /// Keys may be dotted, such as `server.port`, or hold nested tables, and
+    /// the overrides take precedence over every source. Like values set with
+    /// `set`, they are kept across reloads.
+    ///
+    /// # Arguments
+    ///
+    /// * `overrides` - The values to apply, by key.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if two keys conflict, such as `server` and
+    /// `server.port`, see `Value::from_dotted`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use realme::prelude::*;
+    ///
+    /// let mut realme = Realme::builder().build().expect("build config");
+    /// realme
+    ///     .overlay(Map::from_iter([
+    ///         ("server.port".to_string(), Value::from(9090)),
+    ///         ("debug".to_string(), Value::from(true)),
+    ///     ]))
+    ///     .expect("overlay config");
+    /// assert_eq!(realme.get_as::<u16, _>("server.port"), Some(9090));
+    /// ```
+    pub fn overlay(&mut self, overrides: Map<String, Value>) -> Result<()> {
+        let overrides = Value::from_dotted(overrides)?;
+        self.clear_deferred_overlay(&overrides)?;
+        let snapshot = self.subscribers.snapshot(&self.cache);
+        self.cache.merge(&overrides);
+        self.subscribers.notify(&snapshot, &self.cache);
+        match &mut self.default {
+            Some(default) => default.merge(&overrides),
+            None => self.default = Some(overrides),
+        }
+        Ok(())
+    }
+
     /// Merges another `Realme` instance into this one.
     ///
     /// This method combines the cache and default configurations of both
@@ -201,6 +451,9 @@ impl Realme {
     /// ```
     pub fn merge(&mut self, other: &Self) -> Result<()> {
         self.cache.merge(&other.cache);
+        self.resolved.take();
+        self.sensitive.extend(other.sensitive.iter().cloned());
+        self.origins.extend(&other.origins);
 
         match (&mut self.default, &other.default) {
             (Some(self_default), Some(other_default)) => {
@@ -213,4 +466,601 @@ impl Realme {
         }
         Ok(())
     }
+
+    /// Retrieves the description attached to the given key with
+    /// `RealmeBuilder::annotate`.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key to look up.
+    ///
+    /// # Returns
+    ///
+    /// An `Option<&str>` which is `Some` if the key has been annotated, or
+    /// `None` if it hasn't.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use realme::prelude::*;
+    ///
+    /// let realme = Realme::builder()
+    ///     .annotate("database.url", "Connection string of the database")
+    ///     .build()
+    ///     .expect("build config");
+    ///
+    /// assert_eq!(
+    ///     realme.describe_key("database.url"),
+    ///     Some("Connection string of the database")
+    /// );
+    /// assert_eq!(realme.describe_key("database.pool"), None);
+    /// ```
+    pub fn describe_key<K: AsRef<str>>(&self, key: K) -> Option<&str> {
+        self.builder
+            .annotations
+            .get(key.as_ref())
+            .map(String::as_str)
+    }
+
+    /// Returns an iterator over all annotated keys and their descriptions.
+    pub fn annotations(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.builder
+            .annotations
+            .iter()
+            .map(|(key, description)| (key.as_str(), description.as_str()))
+    }
+
+    /// Returns the leaves of the configuration with their path and kind.
+    ///
+    /// This is meant for validation tooling, e.g. checking that no string is
+    /// empty or that every `*.port` is an integer. See `Value::leaves`. The
+    /// leaves are those of `Realme::redacted`, so the keys provided by
+    /// sensitive sources are left out.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use realme::prelude::*;
+    ///
+    /// let mut realme = Realme::builder().build().expect("build config");
+    /// realme.set("server.port", 8080).expect("set config");
+    /// assert!(
+    ///     realme
+    ///         .leaves()
+    ///         .filter(|(path, ..)| path.ends_with(".port"))
+    ///         .all(|(_, kind, _)| kind == ValueKind::Integer)
+    /// );
+    /// ```
+    pub fn leaves(&self) -> impl Iterator<Item = (String, ValueKind, Value)> {
+        let redacted = self.redacted();
+        redacted
+            .leaves()
+            .map(|(path, kind, leaf)| (path, kind, leaf.clone()))
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    /// Flattens the configuration into a map of dotted keys to strings.
+    ///
+    /// Keys are the paths of `Realme::leaves`, with array elements indexed
+    /// as in `servers[0].host`, and values are the scalar leaves formatted
+    /// with their `Display` implementation. `Value::Null` leaves are left
+    /// out, as are empty arrays and tables since they have no leaves.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use realme::prelude::*;
+    ///
+    /// let mut realme = Realme::builder().build().expect("build config");
+    /// realme.set("server.port", 8080).expect("set config");
+    /// let flat = realme.flatten_to_strings();
+    /// assert_eq!(flat.get("server.port").map(String::as_str), Some("8080"));
+    /// ```
+    pub fn flatten_to_strings(&self) -> HashMap<String, String> {
+        self.redacted()
+            .leaves()
+            .filter(|(_, kind, _)| *kind != ValueKind::Null)
+            .map(|(path, _, leaf)| (path, leaf.to_string()))
+            .collect()
+    }
+
+    /// Returns the configuration values without the keys provided by
+    /// sensitive sources, such as `EnvSource::sensitive`.
+    ///
+    /// This is what every export of the configuration goes through:
+    /// serializing the `Realme`, printing it with `Debug`,
+    /// `flatten_to_strings` and `diff_from_defaults`. Tables left empty by
+    /// the removal are removed too. `get` and `try_deserialize` still see
+    /// the sensitive values.
+    pub fn redacted(&self) -> Value {
+        let mut value = self.cache.clone();
+        self.apply_deferred(&mut value, false);
+        if !self.sensitive.is_empty() {
+            value.retain(|path, _| !self.is_sensitive(path));
+        }
+        value
+    }
+
+    /// Returns whether `key` was provided by a sensitive source, see
+    /// `Realme::redacted`.
+    pub fn is_sensitive<K: AsRef<str>>(&self, key: K) -> bool {
+        self.sensitive.iter().any(|k| k == key.as_ref())
+    }
+
+    /// Returns the source that provided the value of `key`.
+    ///
+    /// This is the last adaptor that set the key while merging, the one
+    /// whose value won. Keys inside an array, as in `servers[0].host`, have
+    /// the origin of the whole array, since arrays replace each other when
+    /// merged. Tables, missing keys and keys set at runtime with `set` or
+    /// `overlay` have no origin.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use realme::prelude::*;
+    /// use serde_json::json;
+    ///
+    /// let realme = Realme::builder()
+    ///     .load(
+    ///         Adaptor::new(SerSource::<SerParser, _>::new(
+    ///             json!({ "host": "localhost", "port": 8080 }),
+    ///         ))
+    ///         .name("base"),
+    ///     )
+    ///     .load(
+    ///         Adaptor::new(SerSource::<SerParser, _>::new(json!({ "port": 9090 })))
+    ///             .name("overrides")
+    ///             .priority(1),
+    ///     )
+    ///     .build()
+    ///     .expect("build config");
+    ///
+    /// assert_eq!(realme.origin("host").map(|o| o.name.as_str()), Some("base"));
+    /// assert_eq!(
+    ///     realme.origin("port").map(|o| o.name.as_str()),
+    ///     Some("overrides")
+    /// );
+    /// ```
+    pub fn origin<K: AsRef<str>>(&self, key: K) -> Option<&SourceInfo> {
+        let key = key.as_ref();
+        if matches!(self.cache.get(key), None | Some(Value::Table(_))) {
+            return None;
+        }
+        let runtime = self.default.as_ref().and_then(|d| d.get(key));
+        if runtime.is_some() {
+            return None;
+        }
+        self.origins.get(key)
+    }
+
+    /// Describes where every value of the configuration comes from.
+    ///
+    /// Each leaf, as listed by `Realme::leaves`, is written on its own line
+    /// as `key = value (origin)`, sorted by key. Values of sensitive keys
+    /// are replaced by `<redacted>`, and keys without an origin, see
+    /// `Realme::origin`, are marked as set at runtime.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use realme::prelude::*;
+    ///
+    /// use serde_json::json;
+    ///
+    /// let realme = Realme::builder()
+    ///     .load(
+    ///         Adaptor::new(SerSource::<SerParser, _>::new(json!({ "port": 8080 })))
+    ///             .name("base"),
+    ///     )
+    ///     .build()
+    ///     .expect("build config");
+    ///
+    /// assert_eq!(realme.explain(), "port = 8080 (base, priority 0)\n");
+    /// ```
+    pub fn explain(&self) -> String {
+        let redacted = self.redacted();
+        let mut leaves: Vec<(String, &Value)> = self
+            .cache
+            .leaves()
+            .map(|(path, _, value)| (path, value))
+            .collect();
+        leaves.sort_by(|(a, _), (b, _)| a.cmp(b));
+        leaves
+            .into_iter()
+            .map(|(path, value)| {
+                let value = if redacted.get(path.as_str()).is_some() {
+                    value.to_string()
+                } else {
+                    "<redacted>".to_string()
+                };
+                let origin = self.origin(&path).map_or_else(
+                    || "set at runtime".to_string(),
+                    ToString::to_string,
+                );
+                format!("{path} = {value} ({origin})\n")
+            })
+            .collect::<Vec<_>>()
+            .concat()
+    }
+
+    /// Returns the configuration values that differ from the default layer.
+    ///
+    /// The result is a table holding only the keys whose resolved value is
+    /// absent from the layer added with `RealmeBuilder::load_defaults`, or
+    /// differs from it. Nested tables are compared key by key, while arrays
+    /// are compared as a whole. Without a default layer every value is
+    /// returned.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use realme::prelude::*;
+    /// use serde_json::json;
+    ///
+    /// let realme = Realme::builder()
+    ///     .load_defaults(Adaptor::new(SerSource::<SerParser, _>::new(
+    ///         json!({ "host": "localhost", "port": 8080 }),
+    ///     )))
+    ///     .load(Adaptor::new(SerSource::<SerParser, _>::new(
+    ///         json!({ "port": 9090 }),
+    ///     )))
+    ///     .build()
+    ///     .expect("build config");
+    ///
+    /// assert_eq!(
+    ///     realme.diff_from_defaults(),
+    ///     Value::from_dotted([("port", 9090)]).expect("build value")
+    /// );
+    /// ```
+    pub fn diff_from_defaults(&self) -> Value {
+        let cache = self.redacted();
+        self.defaults_layer
+            .as_ref()
+            .map_or_else(
+                || Some(cache.clone()),
+                |defaults| diff(&cache, defaults),
+            )
+            .unwrap_or_else(|| Value::Table(Map::new()))
+    }
+}
+
+/// Returns the part of `value` that differs from `base`, or `None` when both
+/// are equal.
+fn diff(value: &Value, base: &Value) -> Option<Value> {
+    match (value, base) {
+        (Value::Table(table), Value::Table(base)) => {
+            let delta: Map<String, Value> = table
+                .iter()
+                .filter_map(|(key, value)| {
+                    let changed = match base.get(key) {
+                        Some(base) => diff(value, base)?,
+                        None => value.clone(),
+                    };
+                    Some((key.clone(), changed))
+                })
+                .collect();
+            (!delta.is_empty()).then_some(Value::Table(delta))
+        }
+        _ => (value != base).then(|| value.clone()),
+    }
+}
+
+/// Error for a value of `key` that has not the requested kind.
+fn key_error(key: &str, value: &Value) -> Error {
+    Error::new_cast_error(
+        format!("`{key}`"),
+        format!("unexpected {} value", value.value_type()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_and_remove() -> anyhow::Result<()> {
+        let mut realme = Realme::builder().build()?;
+        realme.set("server.hosts", vec!["a", "b", "c"])?;
+        realme.set("server.tls.cert", "cert.pem")?;
+        assert_eq!(
+            realme.get("server.tls.cert"),
+            Some(&Value::from("cert.pem"))
+        );
+
+        assert_eq!(realme.remove("server.hosts[-1]"), Some(Value::from("c")));
+        assert_eq!(
+            realme.get("server.hosts"),
+            Some(&Value::from(vec!["a", "b"]))
+        );
+        assert_eq!(
+            realme.remove("server.tls.cert"),
+            Some(Value::from("cert.pem"))
+        );
+        assert_eq!(realme.remove("server.tls.cert"), None);
+        assert_eq!(realme.remove("missing.key"), None);
+        assert_eq!(realme.get("missing"), None);
+
+        realme.reload()?;
+        assert_eq!(realme.get("server.tls.cert"), None);
+        assert_eq!(
+            realme.get("server.hosts"),
+            Some(&Value::from(vec!["a", "b"]))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_with_wildcard() -> anyhow::Result<()> {
+        let mut realme = Realme::builder().build()?;
+        realme.set("servers", vec![
+            HashMap::from([("host", Value::from("alpha"))]),
+            HashMap::from([("host", Value::from("beta"))]),
+            HashMap::from([("port", Value::from(8080))]),
+        ])?;
+        assert_eq!(realme.get("servers[*].host"), None);
+        assert_eq!(realme.get("servers[-1].port"), Some(&Value::from(8080)));
+        assert_eq!(realme.get_all("servers.*.host"), vec![
+            &Value::from("alpha"),
+            &Value::from("beta")
+        ]);
+        assert_eq!(
+            realme.get_as::<Vec<String>, _>("servers[*].host"),
+            Some(vec!["alpha".to_string(), "beta".to_string()])
+        );
+        assert_eq!(
+            realme.get_as::<Vec<u16>, _>("servers[*].missing"),
+            Some(Vec::new())
+        );
+        assert!(realme.set("servers[*].host", "gamma").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_annotations_for_nested_keys() -> anyhow::Result<()> {
+        let mut realme = Realme::builder()
+            .annotate("server.port", "The port the server listens on")
+            .annotate("server.tls.cert", "Path to the TLS certificate")
+            .build()?;
+        realme.set("server.port", 8080)?;
+
+        assert_eq!(realme.get_as::<u16, _>("server.port"), Some(8080));
+        assert_eq!(
+            realme.describe_key("server.port"),
+            Some("The port the server listens on")
+        );
+        assert_eq!(
+            realme.describe_key("server.tls.cert"),
+            Some("Path to the TLS certificate")
+        );
+        assert_eq!(realme.describe_key("server"), None);
+        assert_eq!(realme.get("server.tls.cert"), None);
+
+        let mut annotations = realme.annotations().collect::<Vec<_>>();
+        annotations.sort_unstable();
+        assert_eq!(annotations, vec![
+            ("server.port", "The port the server listens on"),
+            ("server.tls.cert", "Path to the TLS certificate"),
+        ]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_annotations_survive_reload() -> anyhow::Result<()> {
+        let mut realme = Realme::builder()
+            .annotate("app.name", "Name of the application")
+            .annotate("app.name", "Display name of the application")
+            .build()?;
+        realme.reload()?;
+        assert_eq!(
+            realme.describe_key("app.name"),
+            Some("Display name of the application")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_flatten_to_strings() -> anyhow::Result<()> {
+        let realme = Realme::builder()
+            .load(Adaptor::new(SerSource::<SerParser, _>::new(
+                serde_json::json!({
+                    "name": "realme",
+                    "debug": false,
+                    "ratio": 0.5,
+                    "cert": null,
+                    "empty": {},
+                    "server": {
+                        "port": 8080,
+                        "hosts": ["a", "b"],
+                        "routes": [{ "path": "/api", "methods": ["GET"] }],
+                    },
+                }),
+            )))
+            .build()?;
+
+        let expected: HashMap<String, String> = [
+            ("name", "realme"),
+            ("debug", "false"),
+            ("ratio", "0.5"),
+            ("server.port", "8080"),
+            ("server.hosts[0]", "a"),
+            ("server.hosts[1]", "b"),
+            ("server.routes[0].path", "/api"),
+            ("server.routes[0].methods[0]", "GET"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+        assert_eq!(realme.flatten_to_strings(), expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_overlay() -> anyhow::Result<()> {
+        let mut realme = Realme::builder()
+            .load(Adaptor::new(SerSource::<SerParser, _>::new(
+                serde_json::json!({
+                    "name": "realme",
+                    "server": { "host": "localhost", "port": 8080 },
+                    "log": { "level": "info" },
+                }),
+            )))
+            .build()?;
+
+        realme.overlay(Map::from_iter([
+            ("server.port".to_string(), Value::from(9090)),
+            ("debug".to_string(), Value::from(true)),
+            (
+                "log".to_string(),
+                Value::from_dotted([("format", "json"), ("level", "debug")])?,
+            ),
+        ]))?;
+
+        assert_eq!(
+            realme.get_as::<String, _>("name").as_deref(),
+            Some("realme")
+        );
+        assert_eq!(
+            realme.get_as::<String, _>("server.host").as_deref(),
+            Some("localhost")
+        );
+        assert_eq!(realme.get_as::<u16, _>("server.port"), Some(9090));
+        assert_eq!(realme.get_as::<bool, _>("debug"), Some(true));
+        assert_eq!(
+            realme.get_as::<String, _>("log.level").as_deref(),
+            Some("debug")
+        );
+        assert_eq!(
+            realme.get_as::<String, _>("log.format").as_deref(),
+            Some("json")
+        );
+
+        realme.reload()?;
+        assert_eq!(realme.get_as::<u16, _>("server.port"), Some(9090));
+        assert_eq!(
+            realme.get_as::<String, _>("server.host").as_deref(),
+            Some("localhost")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_diff_from_defaults() -> anyhow::Result<()> {
+        let realme = Realme::builder()
+            .load_defaults(Adaptor::new(SerSource::<SerParser, _>::new(
+                serde_json::json!({
+                    "server": { "host": "localhost", "port": 8080 },
+                    "log": { "level": "info", "targets": ["stdout"] },
+                }),
+            )))
+            .load(Adaptor::new(SerSource::<SerParser, _>::new(
+                serde_json::json!({
+                    "server": { "host": "localhost", "port": 9090 },
+                    "log": { "targets": ["stdout", "file"] },
+                    "name": "realme",
+                }),
+            )))
+            .build()?;
+
+        assert_eq!(
+            realme.get_as::<String, _>("log.level").as_deref(),
+            Some("info")
+        );
+        assert_eq!(
+            realme.diff_from_defaults(),
+            Value::try_serialize(&serde_json::json!({
+                "server": { "port": 9090 },
+                "log": { "targets": ["stdout", "file"] },
+                "name": "realme",
+            }))?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_origin() -> anyhow::Result<()> {
+        let mut realme = Realme::builder()
+            .load_defaults(
+                Adaptor::new(SerSource::<SerParser, _>::new(
+                    serde_json::json!({
+                        "server": { "host": "localhost", "port": 8080 },
+                        "hosts": [{ "name": "a" }],
+                    }),
+                ))
+                .name("defaults"),
+            )
+            .load(
+                Adaptor::new(SerSource::<SerParser, _>::new(
+                    serde_json::json!({ "server": { "port": 9090 } }),
+                ))
+                .name("overrides")
+                .priority(2),
+            )
+            .build()?;
+
+        let origin = realme.origin("server.host").expect("host has an origin");
+        assert_eq!(origin.name, "defaults");
+        assert!(origin.default);
+        let origin = realme.origin("server.port").expect("port has an origin");
+        assert_eq!((origin.name.as_str(), origin.priority), ("overrides", 2));
+        assert_eq!(
+            realme.origin("hosts[0].name").map(|o| o.name.as_str()),
+            Some("defaults")
+        );
+        assert_eq!(realme.origin("server"), None);
+        assert_eq!(realme.origin("missing"), None);
+
+        realme.set("server.host", "0.0.0.0")?;
+        assert_eq!(realme.origin("server.host"), None);
+        assert_eq!(
+            realme.explain(),
+            "hosts[0].name = a (defaults, default layer)\nserver.host = \
+             0.0.0.0 (set at runtime)\nserver.port = 9090 (overrides, \
+             priority 2)\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_diff_without_overrides() -> anyhow::Result<()> {
+        let defaults = serde_json::json!({ "server": { "port": 8080 } });
+        let realme = Realme::builder()
+            .load_defaults(Adaptor::new(SerSource::<SerParser, _>::new(
+                defaults.clone(),
+            )))
+            .load(Adaptor::new(SerSource::<SerParser, _>::new(defaults)))
+            .build()?;
+        assert_eq!(realme.diff_from_defaults(), Value::Table(Map::new()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_typed_getters() -> anyhow::Result<()> {
+        let mut realme = Realme::builder().build()?;
+        realme.set("server.port", "30")?;
+        realme.set("server.ratio", "0.5")?;
+        realme.set("server.debug", "true")?;
+        realme.set("server.hosts", vec!["a", "b"])?;
+        realme.set("name", 42)?;
+
+        assert_eq!(realme.get_int("server.port")?, 30);
+        assert!((realme.get_float("server.ratio")? - 0.5).abs() < f64::EPSILON);
+        assert!(realme.get_bool("server.debug")?);
+        assert_eq!(realme.get_string("name")?, "42");
+        assert_eq!(realme.get_array("server.hosts")?, vec![
+            Value::from("a"),
+            Value::from("b")
+        ]);
+        assert_eq!(realme.get_table("server")?.len(), 4);
+
+        let err = realme.get_int("server.debug").expect_err("not an integer");
+        assert!(err.to_string().contains("`server.debug`"));
+        let err = realme.get_string("missing.key").expect_err("missing key");
+        assert!(err.to_string().contains("`missing.key`"));
+        let err = realme.get_table("name").expect_err("not a table");
+        assert!(err.to_string().contains("`name`"));
+        assert!(realme.get_array("server").is_err());
+        Ok(())
+    }
 }
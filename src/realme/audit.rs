@@ -0,0 +1,128 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    prelude::*,
+    value::join_key,
+};
+
+/// Record of how each key of a `Realme` was produced.
+///
+/// For every dotted key, the audit lists the sources that provided a value
+/// for it, in the order they were merged: the last entry is the value that
+/// won. Tables are recorded key by key while arrays are recorded whole, as
+/// they replace each other when merged. Default layer adaptors come first.
+/// Changes made after merging, such as interpolation, are not recorded.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MergeAudit {
+    entries: BTreeMap<String, Vec<(String, Value)>>,
+}
+
+impl MergeAudit {
+    /// Returns the sources that provided a value for `key`, in merge order.
+    pub fn get(&self, key: &str) -> Option<&[(String, Value)]> {
+        self.entries.get(key).map(Vec::as_slice)
+    }
+
+    /// Returns the name of the source whose value for `key` won.
+    pub fn winner(&self, key: &str) -> Option<&str> {
+        self.get(key)
+            .and_then(<[_]>::last)
+            .map(|(source, _)| source.as_str())
+    }
+
+    /// Returns an iterator over the audited keys, sorted by key, with the
+    /// sources that provided them.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &[(String, Value)])> {
+        self.entries
+            .iter()
+            .map(|(key, sources)| (key.as_str(), sources.as_slice()))
+    }
+
+    /// Records the values provided by `source`.
+    pub(crate) fn record(&mut self, source: &str, value: &Value) {
+        self.record_at(source, String::new(), value);
+    }
+
+    fn record_at(&mut self, source: &str, path: String, value: &Value) {
+        match value {
+            Value::Table(table) => {
+                for (key, value) in table {
+                    let path = join_key(&path, key);
+                    self.record_at(source, path, value);
+                }
+            }
+            _ if path.is_empty() => {}
+            _ => self
+                .entries
+                .entry(path)
+                .or_default()
+                .push((source.to_string(), value.clone())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source(value: serde_json::Value) -> Adaptor {
+        Adaptor::new(SerSource::<SerParser, _>::new(value))
+    }
+
+    #[test]
+    fn test_audit_records_contested_key() -> anyhow::Result<()> {
+        let (realme, audit) = Realme::builder()
+            .load(
+                source(serde_json::json!({ "port": 9090, "tags": ["b"] }))
+                    .name("override")
+                    .priority(2),
+            )
+            .load(
+                source(serde_json::json!({
+                    "port": 8080,
+                    "host": "localhost",
+                    "tags": ["a", "c"],
+                }))
+                .name("base")
+                .priority(1),
+            )
+            .build_with_audit()?;
+
+        assert_eq!(realme.get_as::<u16, _>("port"), Some(9090));
+        assert_eq!(
+            audit.get("port"),
+            Some(
+                &[
+                    ("base".to_string(), Value::Integer(8080)),
+                    ("override".to_string(), Value::Integer(9090)),
+                ][..]
+            )
+        );
+        assert_eq!(audit.winner("port"), Some("override"));
+        assert_eq!(audit.winner("host"), Some("base"));
+        assert_eq!(audit.get("tags").map(<[_]>::len), Some(2));
+        assert_eq!(audit.iter().map(|(key, _)| key).collect::<Vec<_>>(), vec![
+            "host", "port", "tags"
+        ]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_audit_names_unnamed_sources() -> anyhow::Result<()> {
+        let (_, audit) = Realme::builder()
+            .defaults(&serde_json::json!({ "server": { "port": 8080 } }))
+            .load(source(serde_json::json!({ "server": { "port": 9090 } })))
+            .build_with_audit()?;
+
+        assert_eq!(
+            audit.get("server.port"),
+            Some(
+                &[
+                    ("SerSource".to_string(), Value::Integer(8080)),
+                    ("SerSource".to_string(), Value::Integer(9090)),
+                ][..]
+            )
+        );
+        Ok(())
+    }
+}
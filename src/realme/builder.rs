@@ -1,7 +1,25 @@
-use super::Realme;
+#[cfg(feature = "app-dirs")]
+use std::path::PathBuf;
+use std::{
+    path::Path,
+    sync::OnceLock,
+};
+
+use serde::Serialize;
+
+use super::{
+    MergeAudit,
+    MissingPolicy,
+    Realme,
+    deferred::Deferred,
+    lowercase::Lowercase,
+    origin::Origins,
+    subscribe::Subscribers,
+};
 use crate::{
     Error,
     prelude::*,
+    value::join_key,
 };
 
 /// A builder for creating a `Realme` instance.
@@ -35,6 +53,195 @@ impl RealmeBuilder {
         self
     }
 
+    /// Adds a configuration layer parsed by the parser `P`.
+    ///
+    /// This is a shorthand for `load(Adaptor::new(source))` which names the
+    /// parser of the layer explicitly. Layers take precedence in declaration
+    /// order: a later layer overrides the keys of the earlier ones.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The source of the layer, parsed by `P`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::collections::HashMap;
+    ///
+    /// use realme::prelude::*;
+    ///
+    /// let realme = Realme::builder()
+    ///     .layer::<SerParser>(SerSource::new(HashMap::from([("port", 8080)])))
+    ///     .layer::<SerParser>(SerSource::new(HashMap::from([("port", 9090)])))
+    ///     .build()
+    ///     .expect("build config");
+    /// assert_eq!(realme.get_as::<u16, _>("port"), Some(9090));
+    /// ```
+    #[must_use]
+    pub fn layer<P>(self, source: impl ParsedBy<P> + 'static) -> Self {
+        self.load(Adaptor::new(source))
+    }
+
+    /// Adds the overlay of `base` for the current operating system, parsed
+    /// by the parser `P`.
+    ///
+    /// The overlay sits next to the base file, with the name of the
+    /// operating system inserted before the extension: the overlays of
+    /// `config/app.toml` are `config/app.linux.toml`, `config/app.macos.toml`,
+    /// `config/app.windows.toml` and so on, named after
+    /// `std::env::consts::OS`. The overlay is added as a layer at this point
+    /// of the builder, so it should follow the base file. It is added as an
+    /// optional source, see `Adaptor::optional`: it is skipped while the
+    /// file does not exist, and picked up by `Realme::reload` once it does.
+    ///
+    /// # Arguments
+    ///
+    /// * `base` - The path of the base configuration file.
+    ///
+    /// # Examples
+    ///
+    /// ```rust ignore
+    /// let builder = RealmeBuilder::new()
+    ///     .layer::<TomlParser>(FileSource::new("config/app.toml"))
+    ///     .with_os_overlay::<TomlParser>("config/app.toml");
+    /// ```
+    #[must_use]
+    pub fn with_os_overlay<P>(self, base: impl AsRef<Path>) -> Self
+    where
+        P: for<'a> Parser<&'a str> + Send + Sync + 'static,
+    {
+        self.os_overlay::<P>(base.as_ref(), std::env::consts::OS)
+    }
+
+    /// Adds the overlay of `base` for the operating system `os`, see
+    /// `with_os_overlay`.
+    fn os_overlay<P>(self, base: &Path, os: &str) -> Self
+    where
+        P: for<'a> Parser<&'a str> + Send + Sync + 'static,
+    {
+        let stem = base.file_stem().unwrap_or_default().to_string_lossy();
+        let name = match base.extension() {
+            Some(ext) => format!("{stem}.{os}.{}", ext.to_string_lossy()),
+            None => format!("{stem}.{os}"),
+        };
+        let overlay = base.with_file_name(name);
+        self.load(Adaptor::new(FileSource::<P>::new(overlay)).optional())
+    }
+
+    /// Creates a builder loading the `config.toml` files of `app` from the
+    /// standard configuration directories of the platform.
+    ///
+    /// The system file is loaded first, from `/etc/<app>` or
+    /// `%PROGRAMDATA%\<app>` on Windows, and the user file overrides it,
+    /// from the directory given by the `directories` crate:
+    /// `$XDG_CONFIG_HOME/<app>` on Linux, `~/Library/Application
+    /// Support/<app>` on macOS and `%APPDATA%\<app>\config` on Windows.
+    /// Files that do not exist when the builder is created are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `app` - The name of the application.
+    ///
+    /// # Examples
+    ///
+    /// ```rust ignore
+    /// let realme = RealmeBuilder::from_app_dirs("myapp")
+    ///     .load(Adaptor::new(EnvSource::<EnvParser>::new("MYAPP_")))
+    ///     .build()?;
+    /// ```
+    #[cfg(feature = "app-dirs")]
+    #[must_use]
+    pub fn from_app_dirs(app: &str) -> Self {
+        let system = if cfg!(windows) {
+            std::env::var_os("PROGRAMDATA")
+                .map_or_else(|| r"C:\ProgramData".into(), PathBuf::from)
+        } else {
+            PathBuf::from("/etc")
+        };
+        let user = directories::ProjectDirs::from("", "", app)
+            .map(|dirs| dirs.config_dir().to_path_buf());
+        Self::new().app_dirs(&system.join(app), user.as_deref())
+    }
+
+    /// Adds the `config.toml` files of the `system` and `user` directories,
+    /// see `from_app_dirs`.
+    #[cfg(feature = "app-dirs")]
+    fn app_dirs(self, system: &Path, user: Option<&Path>) -> Self {
+        std::iter::once(system)
+            .chain(user)
+            .map(|dir| dir.join("config.toml"))
+            .filter(|file| file.is_file())
+            .fold(self, |builder, file| {
+                builder.layer::<TomlParser>(FileSource::new(file))
+            })
+    }
+
+    /// Adds an `Adaptor` to the default layer.
+    ///
+    /// The default layer sits below every adaptor added with `load`,
+    /// whatever their priority, and is tracked separately so that
+    /// `Realme::diff_from_defaults` can tell which values were overridden.
+    /// Adaptors of the default layer are merged in the order they are added.
+    ///
+    /// # Arguments
+    ///
+    /// * `adaptor` - The `Adaptor` to be added to the default layer.
+    ///
+    /// # Examples
+    ///
+    /// ```rust ignore
+    /// let builder = RealmeBuilder::new()
+    ///     .load_defaults(Adaptor::new(defaults))
+    ///     .load(Adaptor::new(user));
+    /// ```
+    #[must_use]
+    pub fn load_defaults<A: Into<Adaptor>>(mut self, adaptor: A) -> Self {
+        self.defaults.push(adaptor.into());
+        self
+    }
+
+    /// Uses a serializable struct as the default layer.
+    ///
+    /// The struct is converted into a `Value` and added with
+    /// `load_defaults`, below every other adaptor, so loaded sources only
+    /// override the fields they specify. Serialization errors are reported
+    /// by `build`.
+    ///
+    /// # Arguments
+    ///
+    /// * `defaults` - The default values.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use realme::prelude::*;
+    /// use serde::Serialize;
+    ///
+    /// #[derive(Clone, Serialize)]
+    /// struct Defaults {
+    ///     host: String,
+    ///     port: u16,
+    /// }
+    ///
+    /// let realme = Realme::builder()
+    ///     .defaults(&Defaults {
+    ///         host: "localhost".to_string(),
+    ///         port: 8080,
+    ///     })
+    ///     .build()
+    ///     .expect("build config");
+    /// assert_eq!(realme.get_as::<u16, _>("port"), Some(8080));
+    /// ```
+    #[must_use]
+    pub fn defaults<T>(self, defaults: &T) -> Self
+    where
+        T: Serialize + Clone + Send + Sync + 'static,
+    {
+        self.load_defaults(Adaptor::new(SerSource::<SerParser, T>::new(
+            defaults.clone(),
+        )))
+    }
+
     /// Sets the profile for the `Realme` instance.
     ///
     /// This method takes ownership of the builder and returns it after
@@ -45,6 +252,9 @@ impl RealmeBuilder {
     /// * `profile` - The profile to be set for the `Realme` instance. e.g.
     ///   `dev`, `prod`. which you have seted in the adaptor.
     ///
+    /// The profile selects the adaptors loaded with `load_for_profile`, and
+    /// the profile sections promoted with `promote_profile_section`.
+    ///
     /// # Examples
     ///
     /// ```rust ignore
@@ -56,6 +266,437 @@ impl RealmeBuilder {
         self
     }
 
+    /// Adds an `Adaptor` only loaded when `profile` is the active profile.
+    ///
+    /// This is a shorthand for `load(adaptor.profile(profile))`. Adaptors
+    /// without a profile are loaded whatever the active profile, so an
+    /// adaptor loaded for a profile after them overrides their keys.
+    ///
+    /// # Arguments
+    ///
+    /// * `profile` - The profile the adaptor belongs to.
+    /// * `adaptor` - The `Adaptor` to be added.
+    ///
+    /// # Examples
+    ///
+    /// ```rust ignore
+    /// let realme = RealmeBuilder::new()
+    ///     .load(Adaptor::new(FileSource::<TomlParser>::new("base.toml")))
+    ///     .load_for_profile(
+    ///         "prod",
+    ///         Adaptor::new(FileSource::<TomlParser>::new("prod.toml")),
+    ///     )
+    ///     .profile("prod")
+    ///     .build()?;
+    /// ```
+    #[must_use]
+    pub fn load_for_profile<A: Into<Adaptor>>(
+        self,
+        profile: impl Into<String>,
+        adaptor: A,
+    ) -> Self {
+        self.load(adaptor.into().profile(profile))
+    }
+
+    /// Attaches a human readable description to a key.
+    ///
+    /// Annotations are kept in a side table next to the configuration
+    /// values, they never become part of the `Value` tree and survive
+    /// reloads. Use `Realme::describe_key` to read them back.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key to describe, e.g. `server.port`.
+    /// * `description` - The description of the key.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use realme::prelude::*;
+    ///
+    /// let realme = Realme::builder()
+    ///     .annotate("server.port", "The port the server listens on")
+    ///     .build()
+    ///     .expect("build config");
+    /// assert_eq!(
+    ///     realme.describe_key("server.port"),
+    ///     Some("The port the server listens on")
+    /// );
+    /// ```
+    #[must_use]
+    pub fn annotate(
+        mut self,
+        key: impl Into<String>,
+        description: impl Into<String>,
+    ) -> Self {
+        self.annotations.insert(key.into(), description.into());
+        self
+    }
+
+    /// Enables expansion of `${NAME}` references to environment variables.
+    ///
+    /// References are expanded in every string value once all adaptors are
+    /// merged. `${NAME:-default}` expands to `default` when `NAME` is unset
+    /// or empty. Other unresolved references are handled according to
+    /// `on_missing_reference`, failing the build with an
+    /// `Error::InterpolationError` naming the key by default.
+    ///
+    /// # Examples
+    ///
+    /// ```rust ignore
+    /// // with `dir = "${HOME}/app"` in the source
+    /// let realme = Realme::builder()
+    ///     .load(adaptor)
+    ///     .with_env_expansion()
+    ///     .build()?;
+    /// ```
+    #[must_use]
+    pub const fn with_env_expansion(mut self) -> Self {
+        self.interpolation.env = true;
+        self
+    }
+
+    /// Enables expansion of `${a.b}` references to other configuration keys.
+    ///
+    /// A string consisting of a single reference takes the value of the
+    /// referenced key as is, keeping its type. Key references take precedence
+    /// over environment variables when both are enabled. Cyclic references
+    /// fail the build.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::collections::HashMap;
+    ///
+    /// use realme::prelude::*;
+    ///
+    /// let realme = Realme::builder()
+    ///     .load(Adaptor::new(SerSource::<SerParser, _>::new(HashMap::from(
+    ///         [("host", "localhost"), ("url", "http://${host}/")],
+    ///     ))))
+    ///     .with_key_references()
+    ///     .build()
+    ///     .expect("build config");
+    /// assert_eq!(
+    ///     realme.get_as::<String, _>("url"),
+    ///     Some("http://localhost/".to_string())
+    /// );
+    /// ```
+    #[must_use]
+    pub const fn with_key_references(mut self) -> Self {
+        self.interpolation.keys = true;
+        self
+    }
+
+    /// Sets how `${...}` references to undefined names are handled.
+    ///
+    /// The policy applies to both environment and key references. By default
+    /// the build fails with `Error::InterpolationError`.
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - The `MissingPolicy` to apply.
+    #[must_use]
+    pub const fn on_missing_reference(mut self, policy: MissingPolicy) -> Self {
+        self.interpolation.missing = policy;
+        self
+    }
+
+    /// Fails the build listing every `${...}` reference left unresolved.
+    ///
+    /// Instead of stopping at the first undefined name as
+    /// `MissingPolicy::Error` does, or hiding it under another policy,
+    /// interpolation goes on and `build` fails with an error naming all the
+    /// unresolved references and the keys holding them. This takes
+    /// precedence over `on_missing_reference`, and is meant for checks such
+    /// as catching every missing environment variable at once in CI.
+    #[must_use]
+    pub const fn require_all_interpolations_resolved(mut self) -> Self {
+        self.interpolation.require_all = true;
+        self
+    }
+
+    /// Makes numbers deserialize only into targets of their own kind.
+    ///
+    /// By default a `Value::Float` without fractional part deserializes into
+    /// integer fields and a `Value::Integer` into float fields. With strict
+    /// numbers both are rejected by `Realme::try_deserialize` and
+    /// `Realme::get_as`.
+    #[must_use]
+    pub const fn strict_numbers(mut self) -> Self {
+        self.strict_numbers = true;
+        self
+    }
+
+    /// Sets how floats are written when the built `Realme` is serialized.
+    ///
+    /// By default floats are written in the shortest form reading back as
+    /// the same value, see `FloatFormat::Shortest`.
+    ///
+    /// # Example
+    ///
+    /// ```rust ignore
+    /// let mut realme = Realme::builder()
+    ///     .float_format(FloatFormat::Precision(2))
+    ///     .build()?;
+    /// realme.set("scale", 1.23456)?;
+    /// assert_eq!(serde_json::to_string(&realme)?, r#"{"scale":1.23}"#);
+    /// ```
+    #[must_use]
+    pub const fn float_format(mut self, format: FloatFormat) -> Self {
+        self.float_format = format;
+        self
+    }
+
+    /// Rejects layers that change the kind of a value set by a lower layer.
+    ///
+    /// By default a higher priority adaptor may replace a value with one of
+    /// any kind, so an environment variable holding `abc` silently replaces
+    /// an integer `port` read from a file. With strict types `build` fails
+    /// instead. `Value::Null` on either side is always accepted, as are an
+    /// integer replacing a float and a string that parses as the kind it
+    /// replaces, such as `"8080"` replacing an integer, since sources like
+    /// `EnvParser` only produce strings. The same holds the other way round,
+    /// so an integer may in turn replace `"8080"`.
+    #[must_use]
+    pub const fn strict_types_on_merge(mut self) -> Self {
+        self.strict_types = true;
+        self
+    }
+
+    /// Sets `key` to a value produced by `resolver` on first access.
+    ///
+    /// This suits values that are expensive or sensitive to materialize,
+    /// such as secrets fetched from a remote store. The resolver runs at
+    /// most once, the first time the key or one of its children is read
+    /// with `get` or `get_as`, or the configuration is deserialized with
+    /// `try_deserialize`, and its value is kept for later accesses,
+    /// including across `reload`. The deferred value takes precedence over
+    /// any value loaded at `key`, while `Realme::set`, `Realme::overlay` and
+    /// `Realme::remove` replace it: changing one of its children keeps the
+    /// others. Reading a table holding `key`, such as `db` for
+    /// `db.password`, resolves it too.
+    ///
+    /// Deferred values do not appear in exports such as `Realme::redacted`
+    /// or the serialized `Realme` until they are resolved.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use realme::prelude::*;
+    ///
+    /// let realme = Realme::builder()
+    ///     .deferred("db.password", || Value::from("hunter2"))
+    ///     .build()
+    ///     .expect("build config");
+    /// assert_eq!(realme.get("db.password"), Some(&Value::from("hunter2")));
+    /// ```
+    #[must_use]
+    pub fn deferred<K, F>(mut self, key: K, resolver: F) -> Self
+    where
+        K: Into<String>,
+        F: Fn() -> Value + Send + Sync + 'static,
+    {
+        self.deferred.push(Deferred::new(key.into(), resolver));
+        self
+    }
+
+    /// Adds a check of the merged configuration.
+    ///
+    /// The checks run once the sources are merged and post-processed, before
+    /// `build` returns, and again on every `reload`, which keeps the previous
+    /// configuration when they fail. Every check runs, and the build fails
+    /// with `Error::ValidationError` listing the errors of all of them, each
+    /// naming the offending key. Values set at runtime and deferred values
+    /// are not checked. A `Validator` is added with `validator`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use realme::{
+    ///     Error,
+    ///     prelude::*,
+    /// };
+    /// use serde_json::json;
+    ///
+    /// let port = Validator::range("server.port", 1..=65535);
+    /// let err = Realme::builder()
+    ///     .load(Adaptor::new(SerSource::<SerParser, _>::new(
+    ///         json!({ "server": { "port": 0, "host": "" } }),
+    ///     )))
+    ///     .validator(port)
+    ///     .validate(|value| match value.get("server.host") {
+    ///         Some(Value::String(host)) if host.is_empty() => Err(vec![
+    ///             ValidationError::new("server.host", "is empty"),
+    ///         ]),
+    ///         _ => Ok(()),
+    ///     })
+    ///     .build()
+    ///     .expect_err("invalid config");
+    /// assert!(matches!(err, Error::ValidationError(errors) if errors.len() == 2));
+    /// ```
+    #[must_use]
+    pub fn validate<F>(mut self, check: F) -> Self
+    where
+        F: Fn(&Value) -> Result<(), Vec<ValidationError>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.validators.push(Validator::new(check));
+        self
+    }
+
+    /// Adds a `Validator` checking the merged configuration, like
+    /// `validate`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use realme::prelude::*;
+    /// use serde_json::json;
+    ///
+    /// let result = Realme::builder()
+    ///     .load(Adaptor::new(SerSource::<SerParser, _>::new(
+    ///         json!({ "server": { "port": 8080 } }),
+    ///     )))
+    ///     .validator(
+    ///         Validator::required("server.port")
+    ///             .and(Validator::range("server.port", 1..=65535)),
+    ///     )
+    ///     .build();
+    /// assert!(result.is_ok());
+    /// ```
+    #[must_use]
+    pub fn validator(mut self, validator: Validator) -> Self {
+        self.validators.push(validator);
+        self
+    }
+
+    /// Rejects sources nesting values deeper than `max_depth`.
+    ///
+    /// The depth of each parsed source is measured with `Value::depth`
+    /// before it is merged, and `build` fails when it exceeds `max_depth`.
+    /// This guards against pathologically nested input, such as a JSON
+    /// document supplied by an untrusted user.
+    #[must_use]
+    pub const fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Merges the files listed under the `include` key of file sources.
+    ///
+    /// A file may list other files to include, as a string or an array of
+    /// strings, e.g. `include = ["db.toml", "cache.toml"]`. Relative paths
+    /// are resolved against the directory of the including file, and the
+    /// included files are parsed with the parser of the including file.
+    /// They are merged in the order they are listed, then the including
+    /// file is merged over them, so its own values take precedence.
+    /// Included files may include other files, and `build` fails when a
+    /// file ends up including itself.
+    #[must_use]
+    pub const fn enable_includes(mut self) -> Self {
+        self.includes = true;
+        self
+    }
+
+    /// Sets how empty values are represented once the sources are merged.
+    ///
+    /// By default values are kept as produced by each parser, so an empty
+    /// value may be an empty string or `Value::Null` depending on the
+    /// source format.
+    ///
+    /// # Arguments
+    ///
+    /// * `empty_values` - The `EmptyValues` representation to apply.
+    #[must_use]
+    pub const fn empty_values(mut self, empty_values: EmptyValues) -> Self {
+        self.empty_values = empty_values;
+        self
+    }
+
+    /// Lowercases the string values found at the given keys once the
+    /// sources are merged.
+    ///
+    /// This suits case-insensitive settings such as log levels. Strings
+    /// nested in a table or array found at one of the keys are lowercased as
+    /// well. Keys themselves are never changed.
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - The keys whose values are lowercased, e.g. `log.level`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use realme::prelude::*;
+    ///
+    /// let builder = Realme::builder().lowercase_values(&["log.level"]);
+    /// ```
+    #[must_use]
+    pub fn lowercase_values<K: AsRef<str>>(mut self, keys: &[K]) -> Self {
+        self.lowercase.extend(keys);
+        self
+    }
+
+    /// Lowercases every string value once the sources are merged.
+    #[must_use]
+    pub fn lowercase_all_values(mut self) -> Self {
+        self.lowercase = Lowercase::All;
+        self
+    }
+
+    /// Replaces one-element arrays found at the given keys by their element
+    /// once the sources are merged.
+    ///
+    /// This suits sources wrapping scalars in arrays, such as `hosts = ["a"]`
+    /// where a single host is expected. Arrays of any other length, and
+    /// values that are not arrays, are left unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - The keys whose arrays are unwrapped, e.g. `server.hosts`.
+    #[must_use]
+    pub fn unwrap_single_arrays<K: AsRef<str>>(mut self, keys: &[K]) -> Self {
+        self.unwrap_arrays
+            .extend(keys.iter().map(|key| key.as_ref().to_string()));
+        self
+    }
+
+    /// Promotes the section of the active profile found under `key`.
+    ///
+    /// Each source is promoted on its own, before it is merged with the
+    /// others: the table at `key` is removed and its subtable named after
+    /// the active profile, if any, is merged into the root of that source.
+    /// With `promote_profile_section("profiles")` and the `prod` profile,
+    /// `profiles.prod.server.port` overrides `server.port` while
+    /// `profiles.dev` is dropped. A promoted value keeps the priority of its
+    /// source, so it never overrides a source of higher priority. The key is
+    /// a top level key.
+    ///
+    /// The profile then no longer has to match an adaptor: a profile without
+    /// a section nor adaptor uses the common values only.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The top level key holding the profile sections.
+    ///
+    /// # Examples
+    ///
+    /// ```rust ignore
+    /// let realme = Realme::builder()
+    ///     .load(Adaptor::new(FileSource::<TomlParser>::new("config.toml")))
+    ///     .promote_profile_section("profiles")
+    ///     .profile("prod")
+    ///     .build()?;
+    /// ```
+    #[must_use]
+    pub fn promote_profile_section(mut self, key: impl Into<String>) -> Self {
+        self.profile_key = Some(key.into());
+        self
+    }
+
     /// Constructs a `Realme` instance using the accumulated adaptors and
     /// profile.
     ///
@@ -68,30 +709,156 @@ impl RealmeBuilder {
     ///
     /// * `Result<Realme, Error>` - A `Result` containing the constructed
     ///   `Realme` instance or an `Error` if the build process fails.
-    pub fn build(mut self) -> Result<Realme, Error> {
+    pub fn build(self) -> Result<Realme, Error> {
+        self.build_audited(None)
+    }
+
+    /// Constructs a `Realme` instance like `build`, along with a record of
+    /// the sources that provided each key.
+    ///
+    /// Adaptors can be named with `Adaptor::name` to tell them apart in the
+    /// audit.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::collections::HashMap;
+    ///
+    /// use realme::prelude::*;
+    ///
+    /// let (realme, audit) = Realme::builder()
+    ///     .load(
+    ///         Adaptor::new(SerSource::<SerParser, _>::new(HashMap::from([(
+    ///             "port", 8080,
+    ///         )])))
+    ///         .name("base"),
+    ///     )
+    ///     .build_with_audit()
+    ///     .expect("build config");
+    /// assert_eq!(audit.winner("port"), Some("base"));
+    /// ```
+    pub fn build_with_audit(self) -> Result<(Realme, MergeAudit), Error> {
+        let mut audit = MergeAudit::default();
+        let realme = self.build_audited(Some(&mut audit))?;
+        Ok((realme, audit))
+    }
+
+    fn build_audited(
+        mut self,
+        mut audit: Option<&mut MergeAudit>,
+    ) -> Result<Realme, Error> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("build").entered();
         self.check_profile()?;
-        self.adaptors.sort_by(|a, b| a.priority.cmp(&b.priority));
-        let mut cache = Value::Table(Map::new());
+        self.adaptors.sort_by_key(|a| a.priority);
+        let mut origins = Origins::default();
+        let defaults_layer =
+            self.parse_defaults(audit.as_deref_mut(), &mut origins)?;
+        let mut cache = defaults_layer
+            .clone()
+            .unwrap_or_else(|| Value::Table(Map::new()));
+        let mut sensitive = Vec::new();
         self.adaptors.iter().try_for_each(|adaptor| {
-            adaptor.parse().and_then(|value| match value {
-                Value::Table(table) => {
-                    cache.merge(&Value::Table(table));
-                    Ok(())
-                }
-                Value::Null => Ok(()),
-                _ => Err(Error::new_build_error(
-                    "Adaptor parse result is not a table".to_string(),
-                )),
-            })
+            let value = self.parse_adaptor(adaptor)?;
+            if let Some(audit) = audit.as_deref_mut() {
+                audit.record(&adaptor.source_name(), &value);
+            }
+            origins.record(adaptor, false, &value);
+            record_sensitive(adaptor, &value, &mut sensitive);
+            self.merge_parsed(&mut cache, adaptor, &value)
         })?;
+        self.post_process(&mut cache)?;
+        self.run_validators(&cache)?;
 
         Ok(Realme {
             cache,
             default: None,
             builder: self,
+            defaults_layer,
+            subscribers: Subscribers::default(),
+            sensitive,
+            origins,
+            resolved: OnceLock::new(),
         })
     }
 
+    /// Parses and merges the adaptors of the default layer.
+    pub(crate) fn parse_defaults(
+        &self,
+        mut audit: Option<&mut MergeAudit>,
+        origins: &mut Origins,
+    ) -> Result<Option<Value>, Error> {
+        if self.defaults.is_empty() {
+            return Ok(None);
+        }
+        let mut defaults = Value::Table(Map::new());
+        self.defaults.iter().try_for_each(|adaptor| {
+            let value = self.parse_adaptor(adaptor)?;
+            if let Some(audit) = audit.as_deref_mut() {
+                audit.record(&adaptor.source_name(), &value);
+            }
+            origins.record(adaptor, true, &value);
+            self.merge_parsed(&mut defaults, adaptor, &value)
+        })?;
+        Ok(Some(defaults))
+    }
+
+    /// Applies the transformations configured on the builder to the merged
+    /// configuration.
+    pub(crate) fn post_process(&self, cache: &mut Value) -> Result<(), Error> {
+        self.interpolation.apply(cache)?;
+        self.empty_values.apply(cache);
+        self.lowercase.apply(cache);
+        for key in &self.unwrap_arrays {
+            if let Some(value) = cache.find_mut(key.as_str()) {
+                if let Value::Array(array) = value {
+                    if array.len() == 1 {
+                        *value = array.remove(0);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Runs the checks added with `validate` against the merged
+    /// configuration, reporting the errors of all of them.
+    pub(crate) fn run_validators(&self, cache: &Value) -> Result<(), Error> {
+        let errors: Vec<ValidationError> = self
+            .validators
+            .iter()
+            .filter_map(|validator| validator.validate(cache).err())
+            .flatten()
+            .collect();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::ValidationError(errors))
+        }
+    }
+
+    /// Merges the section of the active profile into the root of a parsed
+    /// source and drops the profile sections, so the section only overrides
+    /// the keys of its own layer.
+    fn promote_profile(&self, cache: &mut Value) {
+        let Some(key) = &self.profile_key else {
+            return;
+        };
+        let Some(Value::Table(root)) = cache
+            .as_table_mut()
+            .map(|root| root.remove(key.as_str()).unwrap_or_default())
+        else {
+            return;
+        };
+        let section = self
+            .profile
+            .as_ref()
+            .and_then(|profile| root.get(profile.as_str()));
+        if let Some(section) = section {
+            cache.merge(section);
+        }
+    }
+
     pub(crate) fn check_profile(&mut self) -> Result<(), Error> {
         let mut profile_not_found = self.profile.is_some();
         self.adaptors.retain(|adaptor| {
@@ -103,16 +870,159 @@ impl RealmeBuilder {
                     profile_not_found = false;
                     true
                 }
-                _ => false,
+                _ => false,
+            }
+        });
+        if profile_not_found && self.profile_key.is_none() {
+            return Err(Error::new_build_error(format!(
+                "Can not find profile {}",
+                self.profile.as_ref().expect("Profile is not set")
+            )));
+        }
+        Ok(())
+    }
+
+    /// Parses `adaptor`, resolving its includes when they are enabled and
+    /// enforcing the maximum depth.
+    pub(crate) fn parse_adaptor(
+        &self,
+        adaptor: &Adaptor,
+    ) -> Result<Value, Error> {
+        let mut value = if self.includes {
+            adaptor.parse_with_includes()?
+        } else {
+            adaptor.parse()?
+        };
+        self.promote_profile(&mut value);
+        match self.max_depth {
+            Some(max_depth) if value.depth() > max_depth => {
+                Err(Error::new_build_error(format!(
+                    "{} nests values deeper than the maximum depth of \
+                     {max_depth}",
+                    adaptor.source_name()
+                )))
+            }
+            _ => Ok(value),
+        }
+    }
+
+    /// Merges the value parsed by `adaptor` into `cache`.
+    pub(crate) fn merge_parsed(
+        &self,
+        cache: &mut Value,
+        adaptor: &Adaptor,
+        value: &Value,
+    ) -> Result<(), Error> {
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            source = %adaptor.source_name(),
+            priority = adaptor.priority,
+            "merging source"
+        );
+        match value {
+            Value::Table(_) => {
+                if self.strict_types {
+                    check_kinds(cache, value, "").map_err(|e| {
+                        Error::new_build_error(format!(
+                            "{e} in {}",
+                            adaptor.source_name()
+                        ))
+                    })?;
+                }
+                #[cfg(feature = "tracing")]
+                trace_overrides(cache, value, "", &adaptor.source_name());
+                match (cache, value) {
+                    (Value::Table(cache), Value::Table(value)) => {
+                        adaptor.merge.merge(cache, value);
+                    }
+                    (cache, value) => cache.merge(value),
+                }
+                Ok(())
+            }
+            Value::Null => Ok(()),
+            _ => Err(Error::new_build_error(
+                "Adaptor parse result is not a table".to_string(),
+            )),
+        }
+    }
+}
+
+/// Appends the dotted keys of the values parsed by `adaptor` to
+/// `sensitive` when its source is sensitive, see `Source::is_sensitive`.
+pub(crate) fn record_sensitive(
+    adaptor: &Adaptor,
+    value: &Value,
+    sensitive: &mut Vec<String>,
+) {
+    fn collect(value: &Value, path: &str, keys: &mut Vec<String>) {
+        match value {
+            Value::Table(table) => {
+                for (key, value) in table {
+                    collect(value, &join_key(path, key), keys);
+                }
+            }
+            Value::Null => {}
+            _ => keys.push(path.to_string()),
+        }
+    }
+    if adaptor.is_sensitive() {
+        collect(value, "", sensitive);
+    }
+}
+
+/// Emits an event for every value of `base` that merging `value` replaces.
+#[cfg(feature = "tracing")]
+fn trace_overrides(base: &Value, value: &Value, path: &str, source: &str) {
+    match (base, value) {
+        (Value::Table(base), Value::Table(table)) => {
+            for (key, value) in table {
+                if let Some(base) = base.get(key) {
+                    trace_overrides(base, value, &join_key(path, key), source);
+                }
+            }
+        }
+        _ if base != value => {
+            tracing::debug!(key = path, source, "overriding key");
+        }
+        _ => {}
+    }
+}
+
+/// Checks that merging `value` into `base` keeps the kind of every value
+/// present in both, as described on `strict_types_on_merge`.
+fn check_kinds(base: &Value, value: &Value, path: &str) -> Result<(), String> {
+    match (base, value) {
+        (Value::Table(base), Value::Table(table)) => {
+            for (key, value) in table {
+                if let Some(base) = base.get(key) {
+                    check_kinds(base, value, &join_key(path, key))?;
+                }
             }
-        });
-        if profile_not_found {
-            return Err(Error::new_build_error(format!(
-                "Can not find profile {}",
-                self.profile.as_ref().expect("Profile is not set")
-            )));
+            Ok(())
         }
-        Ok(())
+        (Value::Null, _) |
+        (_, Value::Null) |
+        (Value::Float(_), Value::Integer(_)) => Ok(()),
+        (_, Value::String(s)) if parses_as(base, s) => Ok(()),
+        (Value::String(s), _) if parses_as(value, s) => Ok(()),
+        _ if base.kind() == value.kind() => Ok(()),
+        _ => Err(format!(
+            "type of `{path}` changes from {} to {}",
+            base.kind(),
+            value.kind()
+        )),
+    }
+}
+
+/// Whether the string `s` parses as a value of the kind of `value`, so
+/// the two are compatible in either order of the layers.
+fn parses_as(value: &Value, s: &str) -> bool {
+    match value {
+        Value::Integer(_) => s.parse::<i64>().is_ok(),
+        Value::Float(_) => s.parse::<f64>().is_ok(),
+        Value::Boolean(_) => s.parse::<bool>().is_ok(),
+        Value::Datetime(_) => s.parse::<Datetime>().is_ok(),
+        _ => false,
     }
 }
 
@@ -304,6 +1214,64 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_build_with_negative_priority() -> Result<(), Error> {
+        let builder = RealmeBuilder::new()
+            .load(Adaptor::new(StringSource::<TomlParser>::new("port = 9000")))
+            .load(
+                Adaptor::new(StringSource::<TomlParser>::new(
+                    "host = \"localhost\"\nport = 8080",
+                ))
+                .priority(-1),
+            )
+            .load(
+                Adaptor::new(StringSource::<TomlParser>::new("port = 7000"))
+                    .priority(i32::MIN),
+            );
+        let realme = builder.build()?;
+
+        assert_eq!(realme.get("host"), Some(&Value::from("localhost")));
+        assert_eq!(realme.get("port"), Some(&Value::Integer(9000)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_with_validators() -> Result<(), Error> {
+        let source = || {
+            Adaptor::new(StringSource::<TomlParser>::new(
+                "[server]\nport = 70000\nhost = \"localhost\"",
+            ))
+        };
+        let err = RealmeBuilder::new()
+            .load(source())
+            .validator(Validator::range("server.port", 1..=65535))
+            .validate(|value| match value.get("server.tls") {
+                Some(_) => Ok(()),
+                None => {
+                    Err(vec![ValidationError::new("server.tls", "is required")])
+                }
+            })
+            .build()
+            .expect_err("the port is out of range");
+        let crate::Error::ValidationError(errors) = err else {
+            panic!("unexpected error: {err}");
+        };
+        assert_eq!(errors, vec![
+            ValidationError::new(
+                "server.port",
+                "is 70000, expected a value in 1..=65535"
+            ),
+            ValidationError::new("server.tls", "is required"),
+        ]);
+
+        let realme = RealmeBuilder::new()
+            .load(source())
+            .validator(Validator::one_of("server.host", ["localhost"]))
+            .build()?;
+        assert_eq!(realme.get("server.port"), Some(&Value::Integer(70000)));
+        Ok(())
+    }
+
     #[test]
     fn test_build_with_invalid_adaptor() {
         let builder = RealmeBuilder::new().load(
@@ -313,6 +1281,46 @@ mod tests {
         assert!(builder.build().is_err());
     }
 
+    #[test]
+    fn test_load_for_profile() -> Result<(), Error> {
+        let base = create_temp_toml(
+            r#"
+            [server]
+            host = "localhost"
+            port = 3000
+
+            [profile.prod.server]
+            host = "example.com"
+        "#,
+        );
+        let prod = create_temp_toml("[server]\nport = 80\n");
+
+        let build = |profile: &str| {
+            RealmeBuilder::new()
+                .load(Adaptor::new(FileSource::<TomlParser>::new(base.path())))
+                .load_for_profile(
+                    "prod",
+                    Adaptor::new(FileSource::<TomlParser>::new(prod.path())),
+                )
+                .promote_profile_section("profile")
+                .profile(profile)
+                .build()
+        };
+
+        let realme = build("prod")?;
+        assert_eq!(
+            realme.get("server.host"),
+            Some(&Value::from("example.com"))
+        );
+        assert_eq!(realme.get("server.port"), Some(&Value::Integer(80)));
+        assert_eq!(realme.get("profile"), None);
+
+        let realme = build("dev")?;
+        assert_eq!(realme.get("server.host"), Some(&Value::from("localhost")));
+        assert_eq!(realme.get("server.port"), Some(&Value::Integer(3000)));
+        Ok(())
+    }
+
     #[test]
     // TODO: it can be build with select function in the future
     fn test_build_with_profile_filtering() -> Result<(), Error> {
@@ -484,4 +1492,589 @@ mod tests {
         assert_eq!(realme.get("server.debug"), Some(&Value::Boolean(true)));
         Ok(())
     }
+
+    #[test]
+    fn test_layer_precedence_follows_declaration_order() -> Result<(), Error> {
+        let base = create_temp_toml(
+            r#"
+            [server]
+            host = "localhost"
+            port = 8080
+            debug = false
+        "#,
+        );
+        let realme = RealmeBuilder::new()
+            .layer::<TomlParser>(FileSource::new(base.path()))
+            .layer::<TomlParser>(StringSource::new(
+                "
+                [server]
+                port = 9000
+                debug = true
+            ",
+            ))
+            .layer::<SerParser>(SerSource::new(toml! {
+                [server]
+                port = 9001
+            }))
+            .build()?;
+
+        assert_eq!(
+            realme.get("server.host"),
+            Some(&Value::String("localhost".to_string()))
+        );
+        assert_eq!(realme.get("server.port"), Some(&Value::Integer(9001)));
+        assert_eq!(realme.get("server.debug"), Some(&Value::Boolean(true)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_with_missing_reference_policy() -> Result<(), Error> {
+        let source = || {
+            StringSource::<TomlParser>::new(
+                r#"
+                host = "localhost"
+                url = "http://${host}:${port}/"
+            "#,
+            )
+        };
+        let result = RealmeBuilder::new()
+            .layer::<TomlParser>(source())
+            .with_key_references()
+            .build();
+        assert!(matches!(
+            result,
+            Err(crate::Error::InterpolationError { ref key, .. }) if key == "url"
+        ));
+
+        let realme = RealmeBuilder::new()
+            .layer::<TomlParser>(source())
+            .with_key_references()
+            .on_missing_reference(MissingPolicy::Leave)
+            .build()?;
+        assert_eq!(
+            realme.get("url"),
+            Some(&Value::String("http://localhost:${port}/".to_string()))
+        );
+
+        let realme = RealmeBuilder::new()
+            .layer::<TomlParser>(source())
+            .with_key_references()
+            .on_missing_reference(MissingPolicy::Empty)
+            .build()?;
+        assert_eq!(
+            realme.get("url"),
+            Some(&Value::String("http://localhost:/".to_string()))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_require_all_interpolations_resolved() {
+        let source = StringSource::<TomlParser>::new(
+            r#"
+            host = "localhost"
+            url = "http://${host}:${port}/"
+            [db]
+            url = "postgres://${REALME_UNSET_DB_USER}@${host}"
+        "#,
+        );
+        let err = RealmeBuilder::new()
+            .layer::<TomlParser>(source)
+            .with_key_references()
+            .with_env_expansion()
+            .on_missing_reference(MissingPolicy::Leave)
+            .require_all_interpolations_resolved()
+            .build()
+            .expect_err("references are unresolved")
+            .to_string();
+        assert!(
+            err.ends_with(
+                "unresolved references: `REALME_UNSET_DB_USER` at `db.url`, \
+                 `port` at `url`"
+            ),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_build_with_adaptor_under_root_key() -> Result<(), Error> {
+        let secrets = create_temp_toml(
+            r#"
+            token = "s3cr3t"
+            [db]
+            password = "hunter2"
+        "#,
+        );
+        let realme = RealmeBuilder::new()
+            .load(Adaptor::new(StringSource::<TomlParser>::new(
+                "token = \"public\"",
+            )))
+            .load(
+                Adaptor::new(FileSource::<TomlParser>::new(secrets.path()))
+                    .under("secrets"),
+            )
+            .load(
+                Adaptor::new(FileSource::<TomlParser>::new(secrets.path()))
+                    .under("app.vault"),
+            )
+            .build()?;
+
+        assert_eq!(
+            realme.get("token"),
+            Some(&Value::String("public".to_string()))
+        );
+        assert_eq!(
+            realme.get("secrets.token"),
+            Some(&Value::String("s3cr3t".to_string()))
+        );
+        assert_eq!(
+            realme.get("secrets.db.password"),
+            Some(&Value::String("hunter2".to_string()))
+        );
+        assert_eq!(
+            realme.get("app.vault.db.password"),
+            Some(&Value::String("hunter2".to_string()))
+        );
+        assert_eq!(realme.get("db"), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_with_strict_numbers() -> Result<(), Error> {
+        let source = || {
+            StringSource::<TomlParser>::new(
+                "
+                ratio = 3.0
+                count = 3
+            ",
+            )
+        };
+        let realme =
+            RealmeBuilder::new().layer::<TomlParser>(source()).build()?;
+        assert_eq!(realme.get_as::<i64, _>("ratio"), Some(3));
+        assert_eq!(realme.get_as::<f64, _>("count"), Some(3.0));
+
+        let realme = RealmeBuilder::new()
+            .layer::<TomlParser>(source())
+            .strict_numbers()
+            .build()?;
+        assert_eq!(realme.get_as::<i64, _>("ratio"), None);
+        assert_eq!(realme.get_as::<f64, _>("count"), None);
+        assert_eq!(realme.get_as::<f64, _>("ratio"), Some(3.0));
+        assert_eq!(realme.get_as::<i64, _>("count"), Some(3));
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_with_struct_defaults() -> Result<(), Error> {
+        #[derive(Clone, serde::Serialize)]
+        struct Server {
+            host:  String,
+            port:  u16,
+            debug: bool,
+        }
+
+        #[derive(Clone, serde::Serialize)]
+        struct Defaults {
+            name:   String,
+            server: Server,
+        }
+
+        let realme = RealmeBuilder::new()
+            .load(
+                Adaptor::new(StringSource::<TomlParser>::new(
+                    "
+                    [server]
+                    port = 9000
+                ",
+                ))
+                .priority(0),
+            )
+            .defaults(&Defaults {
+                name:   "realme".to_string(),
+                server: Server {
+                    host:  "localhost".to_string(),
+                    port:  8080,
+                    debug: false,
+                },
+            })
+            .build()?;
+
+        assert_eq!(
+            realme.get("name"),
+            Some(&Value::String("realme".to_string()))
+        );
+        assert_eq!(
+            realme.get("server.host"),
+            Some(&Value::String("localhost".to_string()))
+        );
+        assert_eq!(realme.get("server.port"), Some(&Value::Integer(9000)));
+        assert_eq!(realme.get("server.debug"), Some(&Value::Boolean(false)));
+        assert_eq!(
+            realme.diff_from_defaults(),
+            Value::from_dotted([("server.port", 9000)])?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_with_unwrapped_single_arrays() -> Result<(), Error> {
+        let realme = RealmeBuilder::new()
+            .load(Adaptor::new(StringSource::<TomlParser>::new(
+                r#"
+                hosts = ["a"]
+                ports = [80, 443]
+                tags = ["x"]
+                name = "realme"
+            "#,
+            )))
+            .unwrap_single_arrays(&["hosts", "ports", "name", "other"])
+            .build()?;
+
+        assert_eq!(realme.get("hosts"), Some(&Value::String("a".to_string())));
+        assert_eq!(
+            realme.get("ports"),
+            Some(&Value::Array(vec![Value::Integer(80), Value::Integer(443)]))
+        );
+        assert_eq!(
+            realme.get("tags"),
+            Some(&Value::Array(vec![Value::String("x".to_string())]))
+        );
+        assert_eq!(
+            realme.get("name"),
+            Some(&Value::String("realme".to_string()))
+        );
+        assert_eq!(realme.get("other"), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_with_promoted_profile_section() -> Result<(), Error> {
+        let build = |profile: &str| {
+            RealmeBuilder::new()
+                .load(Adaptor::new(StringSource::<TomlParser>::new(
+                    r#"
+                    [server]
+                    host = "localhost"
+                    port = 8080
+
+                    [profiles.prod.server]
+                    host = "example.com"
+
+                    [profiles.dev]
+                    debug = true
+                "#,
+                )))
+                .promote_profile_section("profiles")
+                .profile(profile)
+                .build()
+        };
+
+        let prod = build("prod")?;
+        assert_eq!(
+            prod.get("server.host"),
+            Some(&Value::String("example.com".to_string()))
+        );
+        assert_eq!(prod.get("server.port"), Some(&Value::Integer(8080)));
+        assert_eq!(prod.get("debug"), None);
+        assert_eq!(prod.get("profiles"), None);
+
+        let dev = build("dev")?;
+        assert_eq!(
+            dev.get("server.host"),
+            Some(&Value::String("localhost".to_string()))
+        );
+        assert_eq!(dev.get("debug"), Some(&Value::Boolean(true)));
+        assert_eq!(dev.get("profiles"), None);
+
+        let test = build("test")?;
+        assert_eq!(
+            test.get("server.host"),
+            Some(&Value::String("localhost".to_string()))
+        );
+        assert_eq!(test.get("profiles"), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_promoted_profile_section_keeps_layer_priority() -> Result<(), Error>
+    {
+        let realme = RealmeBuilder::new()
+            .load(Adaptor::new(StringSource::<TomlParser>::new(
+                "port = 3000\n[profiles.prod]\nport = 80\n",
+            )))
+            .load(
+                Adaptor::new(StringSource::<TomlParser>::new("port = 9999"))
+                    .priority(10),
+            )
+            .promote_profile_section("profiles")
+            .profile("prod")
+            .build()?;
+        assert_eq!(realme.get("port"), Some(&Value::Integer(9999)));
+        assert_eq!(realme.get("profiles"), None);
+        Ok(())
+    }
+
+    fn build_strict(overrides: &str) -> crate::Result<Realme> {
+        RealmeBuilder::new()
+            .load(
+                Adaptor::new(StringSource::<TomlParser>::new(
+                    "
+                    [server]
+                    port = 8080
+                    host = \"localhost\"
+                ",
+                ))
+                .priority(0),
+            )
+            .load(
+                Adaptor::new(StringSource::<TomlParser>::new(overrides))
+                    .priority(1),
+            )
+            .strict_types_on_merge()
+            .build()
+    }
+
+    #[test]
+    fn test_strict_types_rejects_kind_change() {
+        let err = build_strict("server.port = \"abc\"")
+            .expect_err("string replacing an integer");
+        assert!(
+            err.to_string().contains(
+                "type of `server.port` changes from integer to string"
+            )
+        );
+
+        build_strict("server = 1").expect_err("integer replacing a table");
+    }
+
+    #[test]
+    fn test_strict_types_accepts_same_kind() -> Result<(), Error> {
+        let realme = build_strict("server.port = 9090")?;
+        assert_eq!(realme.get("server.port"), Some(&Value::Integer(9090)));
+
+        let realme = build_strict("server.port = \"9090\"")?;
+        assert_eq!(realme.get_as::<u16, _>("server.port"), Some(9090));
+
+        let realme = RealmeBuilder::new()
+            .load(
+                Adaptor::new(StringSource::<TomlParser>::new("port = 8080"))
+                    .priority(0),
+            )
+            .load(
+                Adaptor::new(StringSource::<TomlParser>::new("port = \"abc\""))
+                    .priority(1),
+            )
+            .build()?;
+        assert_eq!(realme.get("port"), Some(&Value::String("abc".to_string())));
+        Ok(())
+    }
+
+    #[test]
+    fn test_strict_types_across_three_layers() -> Result<(), Error> {
+        let build = |last: &str| {
+            RealmeBuilder::new()
+                .load(Adaptor::new(StringSource::<TomlParser>::new(
+                    "port = 8080",
+                )))
+                .load(
+                    Adaptor::new(StringSource::<TomlParser>::new(
+                        "port = \"9090\"",
+                    ))
+                    .priority(1),
+                )
+                .load(
+                    Adaptor::new(StringSource::<TomlParser>::new(last))
+                        .priority(2),
+                )
+                .strict_types_on_merge()
+                .build()
+        };
+
+        let realme = build("port = 1")?;
+        assert_eq!(realme.get("port"), Some(&Value::Integer(1)));
+        build("port = true").expect_err("boolean replacing an integer");
+        Ok(())
+    }
+
+    #[test]
+    fn test_os_overlay() -> Result<(), Error> {
+        let dir = tempfile::tempdir()?;
+        let base = dir.path().join("app.toml");
+        std::fs::write(&base, "name = \"realme\"\nport = 8080\n")?;
+        std::fs::write(dir.path().join("app.linux.toml"), "port = 9090\n")?;
+
+        let build = |os: &str| {
+            RealmeBuilder::new()
+                .layer::<TomlParser>(FileSource::new(base.as_path()))
+                .os_overlay::<TomlParser>(&base, os)
+                .build()
+        };
+
+        let linux = build("linux")?;
+        assert_eq!(linux.get("port"), Some(&Value::Integer(9090)));
+        assert_eq!(linux.get("name"), Some(&Value::from("realme")));
+
+        let windows = build("windows")?;
+        assert_eq!(windows.get("port"), Some(&Value::Integer(8080)));
+
+        let mut macos = build("macos")?;
+        assert_eq!(macos.get("port"), Some(&Value::Integer(8080)));
+        std::fs::write(dir.path().join("app.macos.toml"), "port = 7070\n")?;
+        macos.reload()?;
+        assert_eq!(macos.get("port"), Some(&Value::Integer(7070)));
+        Ok(())
+    }
+
+    #[cfg(feature = "app-dirs")]
+    #[test]
+    fn test_app_dirs() -> Result<(), Error> {
+        let dir = tempfile::tempdir()?;
+        let system = dir.path().join("system");
+        let user = dir.path().join("user");
+        std::fs::create_dir_all(&system)?;
+        std::fs::create_dir_all(&user)?;
+        std::fs::write(
+            system.join("config.toml"),
+            "name = \"realme\"\nport = 8080\n",
+        )?;
+        std::fs::write(user.join("config.toml"), "port = 9090\n")?;
+
+        let realme = RealmeBuilder::new()
+            .app_dirs(&system, Some(&user))
+            .build()?;
+        assert_eq!(realme.get("port"), Some(&Value::Integer(9090)));
+        assert_eq!(realme.get("name"), Some(&Value::from("realme")));
+
+        let missing = dir.path().join("missing");
+        let realme = RealmeBuilder::new()
+            .app_dirs(&system, Some(&missing))
+            .build()?;
+        assert_eq!(realme.get("port"), Some(&Value::Integer(8080)));
+
+        let realme = RealmeBuilder::new().app_dirs(&missing, None).build()?;
+        assert_eq!(realme.get("port"), None);
+        Ok(())
+    }
+
+    fn build_included(dir: &std::path::Path) -> crate::Result<Realme> {
+        RealmeBuilder::new()
+            .load(Adaptor::new(FileSource::<TomlParser>::new(
+                dir.join("app.toml"),
+            )))
+            .enable_includes()
+            .build()
+    }
+
+    #[test]
+    fn test_include() -> Result<(), Error> {
+        let dir = tempfile::tempdir()?;
+        std::fs::write(
+            dir.path().join("app.toml"),
+            "include = [\"db.toml\", \"cache.toml\"]\nname = \
+             \"realme\"\n[db]\nport = 6432\n",
+        )?;
+        std::fs::write(
+            dir.path().join("db.toml"),
+            "[db]\nhost = \"localhost\"\nport = 5432\n",
+        )?;
+        std::fs::write(
+            dir.path().join("cache.toml"),
+            "[cache]\nttl = 60\n[db]\nhost = \"db.local\"\n",
+        )?;
+
+        let realme = build_included(dir.path())?;
+        assert_eq!(realme.get("name"), Some(&Value::from("realme")));
+        assert_eq!(realme.get("db.host"), Some(&Value::from("db.local")));
+        assert_eq!(realme.get("db.port"), Some(&Value::Integer(6432)));
+        assert_eq!(realme.get("cache.ttl"), Some(&Value::Integer(60)));
+        assert_eq!(realme.get("include"), None);
+
+        let disabled = RealmeBuilder::new()
+            .load(Adaptor::new(FileSource::<TomlParser>::new(
+                dir.path().join("app.toml"),
+            )))
+            .build()?;
+        assert_eq!(disabled.get("cache.ttl"), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_nested_include() -> Result<(), Error> {
+        let dir = tempfile::tempdir()?;
+        std::fs::create_dir(dir.path().join("conf"))?;
+        std::fs::write(
+            dir.path().join("app.toml"),
+            "include = \"conf/db.toml\"\n",
+        )?;
+        std::fs::write(
+            dir.path().join("conf/db.toml"),
+            "include = \"pool.toml\"\n[db]\nhost = \"localhost\"\n",
+        )?;
+        std::fs::write(
+            dir.path().join("conf/pool.toml"),
+            "[db]\nhost = \"pool\"\npool = 8\n",
+        )?;
+
+        let realme = build_included(dir.path())?;
+        assert_eq!(realme.get("db.host"), Some(&Value::from("localhost")));
+        assert_eq!(realme.get("db.pool"), Some(&Value::Integer(8)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_include_cycle() -> Result<(), Error> {
+        let dir = tempfile::tempdir()?;
+        std::fs::write(dir.path().join("app.toml"), "include = \"b.toml\"\n")?;
+        std::fs::write(dir.path().join("b.toml"), "include = \"app.toml\"\n")?;
+
+        let err = build_included(dir.path())
+            .expect_err("the includes form a cycle")
+            .to_string();
+        assert!(err.contains("include cycle"), "{err}");
+        assert!(err.contains("b.toml -> "), "{err}");
+        Ok(())
+    }
+
+    #[test]
+    fn test_max_depth() -> Result<(), Error> {
+        let build = |max_depth: usize| {
+            RealmeBuilder::new()
+                .load(Adaptor::new(StringSource::<TomlParser>::new(
+                    "[a.b]\nc = [1, 2]\n",
+                )))
+                .max_depth(max_depth)
+                .build()
+        };
+        assert_eq!(build(4)?.get("a.b.c"), Some(&Value::from(vec![1, 2])));
+
+        let err = build(3)
+            .expect_err("the value is nested too deeply")
+            .to_string();
+        assert!(err.contains("maximum depth of 3"), "{err}");
+        Ok(())
+    }
+
+    #[test]
+    fn test_serialize_float_format() -> anyhow::Result<()> {
+        let build = |format: FloatFormat| {
+            RealmeBuilder::new()
+                .load(Adaptor::new(StringSource::<TomlParser>::new(
+                    "ratio = 1.1\nscale = 1.23456\n",
+                )))
+                .float_format(format)
+                .build()
+        };
+
+        let json = serde_json::to_value(build(FloatFormat::default())?)?;
+        assert_eq!(json["ratio"].to_string(), "1.1");
+        assert_eq!(json["scale"].to_string(), "1.23456");
+
+        let realme = build(FloatFormat::Precision(2))?;
+        let json = serde_json::to_value(&realme)?;
+        assert_eq!(json["ratio"].to_string(), "1.1");
+        assert_eq!(json["scale"].to_string(), "1.23");
+        assert_eq!(realme.get("scale"), Some(&Value::Float(1.23456)));
+        Ok(())
+    }
 }
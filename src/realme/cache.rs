@@ -0,0 +1,371 @@
+use std::{
+    hash::Hasher,
+    path::Path,
+    sync::OnceLock,
+    time::UNIX_EPOCH,
+};
+
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use super::{
+    origin::Origins,
+    subscribe::Subscribers,
+};
+use crate::{
+    Error,
+    Result,
+    prelude::*,
+    value::Fnv,
+};
+
+/// Magic bytes identifying a realme cache file.
+const MAGIC: &[u8; 4] = b"RLMC";
+/// Version of the cache layout, bumped on incompatible changes.
+const VERSION: u8 = 2;
+/// Length of the header: magic, version, fingerprint of the sources and
+/// checksum.
+const HEADER_LEN: usize = MAGIC.len() + 1 + 8 + 8;
+
+/// Mirror of `Value` with a layout suited to non self-describing formats.
+#[derive(Serialize, Deserialize)]
+enum CachedValue {
+    Null,
+    Boolean(bool),
+    Integer(i64),
+    Float(f64),
+    String(String),
+    Array(Vec<Self>),
+    Table(Vec<(String, Self)>),
+    Datetime(String),
+}
+
+impl From<&Value> for CachedValue {
+    fn from(value: &Value) -> Self {
+        match value {
+            Value::Null => Self::Null,
+            Value::Boolean(b) => Self::Boolean(*b),
+            Value::Integer(i) => Self::Integer(*i),
+            Value::Float(f) => Self::Float(*f),
+            Value::String(s) => Self::String(s.clone()),
+            Value::Datetime(d) => Self::Datetime(d.to_string()),
+            Value::Array(a) => Self::Array(a.iter().map(Self::from).collect()),
+            Value::Table(t) => Self::Table(
+                t.iter().map(|(k, v)| (k.clone(), Self::from(v))).collect(),
+            ),
+        }
+    }
+}
+
+impl From<CachedValue> for Value {
+    fn from(value: CachedValue) -> Self {
+        match value {
+            CachedValue::Null => Self::Null,
+            CachedValue::Boolean(b) => Self::Boolean(b),
+            CachedValue::Integer(i) => Self::Integer(i),
+            CachedValue::Float(f) => Self::Float(f),
+            CachedValue::String(s) => Self::String(s),
+            CachedValue::Datetime(d) => {
+                d.parse().map_or(Self::String(d), Self::Datetime)
+            }
+            CachedValue::Array(a) => {
+                Self::Array(a.into_iter().map(Self::from).collect())
+            }
+            CachedValue::Table(t) => Self::Table(
+                t.into_iter().map(|(k, v)| (k, Self::from(v))).collect(),
+            ),
+        }
+    }
+}
+
+/// 64-bit FNV-1a hash of `bytes`.
+fn checksum(bytes: &[u8]) -> u64 {
+    let mut hasher = Fnv::default();
+    hasher.write(bytes);
+    hasher.finish()
+}
+
+/// Fingerprint of the state of the sources of `builder`.
+///
+/// Sources reading a file contribute its path, size and modification time,
+/// so they are not read. Other sources, such as an `EnvSource`, are parsed
+/// and contribute the content hash of their values.
+fn fingerprint(builder: &RealmeBuilder) -> Result<u64> {
+    let mut hasher = Fnv::default();
+    for adaptor in builder.defaults.iter().chain(&builder.adaptors) {
+        hasher.write(adaptor.source_name().as_bytes());
+        let Some(path) = adaptor.path() else {
+            hasher.write_u64(adaptor.parse()?.content_hash());
+            continue;
+        };
+        hasher.write(path.to_string_lossy().as_bytes());
+        if let Ok(metadata) = std::fs::metadata(path) {
+            hasher.write_u64(metadata.len());
+            let modified = metadata
+                .modified()
+                .ok()
+                .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                .map_or(0, |elapsed| elapsed.as_nanos());
+            hasher.write_u128(modified);
+        }
+    }
+    Ok(hasher.finish())
+}
+
+impl Realme {
+    /// Writes the configuration values to a compact binary cache file.
+    ///
+    /// The file starts with a header holding a format version, a
+    /// fingerprint of the sources and a checksum of the payload, which
+    /// `RealmeBuilder::load_cache` verifies before use. Runtime values set
+    /// with `set` are part of the cache, while the keys provided by
+    /// sensitive sources are left out of it, see `Realme::redacted`, so no
+    /// secret is written to disk.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the cache file to write.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::CacheError` if the values can not be encoded or the
+    /// file can not be written, or the error of a source that can not be
+    /// parsed while fingerprinting the sources.
+    pub fn save_cache<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let payload = postcard::to_stdvec(&CachedValue::from(&self.redacted()))
+            .map_err(|e| Error::CacheError(e.to_string()))?;
+        let mut bytes = Vec::with_capacity(HEADER_LEN + payload.len());
+        bytes.extend_from_slice(MAGIC);
+        bytes.push(VERSION);
+        bytes.extend_from_slice(&fingerprint(&self.builder)?.to_le_bytes());
+        bytes.extend_from_slice(&checksum(&payload).to_le_bytes());
+        bytes.extend_from_slice(&payload);
+        std::fs::write(path.as_ref(), bytes).map_err(|e| {
+            Error::CacheError(format!(
+                "Failed to write cache: {}, error: {}",
+                path.as_ref().display(),
+                e
+            ))
+        })
+    }
+}
+
+impl RealmeBuilder {
+    /// Loads the `Realme` this builder builds from a cache file written by
+    /// `Realme::save_cache`, without merging the sources.
+    ///
+    /// The cache is only used if the sources have not changed since it was
+    /// written: files must have kept their size and modification time, and
+    /// other sources must parse to the same values. The loaded instance
+    /// keeps the builder, so `reload` rebuilds it from the sources. The
+    /// keys of sensitive sources are not cached, so a configuration needing
+    /// them should be built instead. Callers are expected to build from the
+    /// sources when loading fails.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the cache file to read.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::ReadFileError` if the file can not be read, or
+    /// `Error::CacheError` if it is not a valid cache, was written by an
+    /// incompatible version, fails the checksum or is stale.
+    ///
+    /// # Examples
+    ///
+    /// ```rust ignore
+    /// let builder = Realme::builder()
+    ///     .load(Adaptor::new(FileSource::<TomlParser>::new("config.toml")));
+    /// let realme = match builder.clone().load_cache("config.cache") {
+    ///     Ok(realme) => realme,
+    ///     Err(_) => {
+    ///         let realme = builder.build()?;
+    ///         realme.save_cache("config.cache")?;
+    ///         realme
+    ///     }
+    /// };
+    /// ```
+    pub fn load_cache<P: AsRef<Path>>(mut self, path: P) -> Result<Realme> {
+        let bytes = std::fs::read(path.as_ref()).map_err(|e| {
+            Error::ReadFileError(format!(
+                "Failed to read cache: {}, error: {}",
+                path.as_ref().display(),
+                e
+            ))
+        })?;
+        if bytes.len() < HEADER_LEN || !bytes.starts_with(MAGIC) {
+            return Err(Error::CacheError("not a realme cache".to_string()));
+        }
+        let (header, payload) = bytes.split_at(HEADER_LEN);
+        if header[MAGIC.len()] != VERSION {
+            return Err(Error::CacheError(format!(
+                "unsupported cache version {}",
+                header[MAGIC.len()]
+            )));
+        }
+        let mut expected = [0; 8];
+        expected.copy_from_slice(&header[HEADER_LEN - 8..]);
+        if u64::from_le_bytes(expected) != checksum(payload) {
+            return Err(Error::CacheError("checksum mismatch".to_string()));
+        }
+        self.check_profile()?;
+        self.adaptors.sort_by_key(|a| a.priority);
+        expected.copy_from_slice(&header[MAGIC.len() + 1..HEADER_LEN - 8]);
+        if u64::from_le_bytes(expected) != fingerprint(&self)? {
+            return Err(Error::CacheError(
+                "the sources changed since the cache was written".to_string(),
+            ));
+        }
+        let cached: CachedValue = postcard::from_bytes(payload)
+            .map_err(|e| Error::CacheError(e.to_string()))?;
+        Ok(Realme {
+            cache:          cached.into(),
+            default:        None,
+            builder:        self,
+            defaults_layer: None,
+            subscribers:    Subscribers::default(),
+            sensitive:      Vec::new(),
+            origins:        Origins::default(),
+            resolved:       OnceLock::new(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{
+        Deserialize,
+        Serialize,
+    };
+
+    use super::*;
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Config {
+        name:    String,
+        port:    u16,
+        ratio:   f64,
+        debug:   bool,
+        tags:    Vec<String>,
+        nothing: Option<String>,
+        nested:  Nested,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Nested {
+        values: Vec<Vec<i64>>,
+    }
+
+    fn source<T: Serialize>(value: &T) -> anyhow::Result<Adaptor> {
+        Ok(Adaptor::new(SerSource::<SerParser, _>::new(
+            Value::try_serialize(value)?,
+        )))
+    }
+
+    #[test]
+    fn test_cache_round_trip() -> anyhow::Result<()> {
+        let config = Config {
+            name:    "realme".to_string(),
+            port:    8080,
+            ratio:   0.25,
+            debug:   true,
+            tags:    vec!["a".to_string(), "b".to_string()],
+            nothing: None,
+            nested:  Nested {
+                values: vec![vec![1, 2], vec![], vec![-3]],
+            },
+        };
+        let builder = Realme::builder().load(source(&config)?);
+        let realme = builder.clone().build()?;
+        let file = tempfile::NamedTempFile::new()?;
+
+        realme.save_cache(file.path())?;
+        let loaded = builder.load_cache(file.path())?;
+
+        assert_eq!(loaded.cache, realme.cache);
+        assert_eq!(loaded.try_deserialize::<Config>()?, config);
+        Ok(())
+    }
+
+    #[test]
+    fn test_cache_rejects_corrupted_file() -> anyhow::Result<()> {
+        let mut realme = Realme::builder().build()?;
+        realme.set("server.port", 8080)?;
+        let file = tempfile::NamedTempFile::new()?;
+        realme.save_cache(file.path())?;
+
+        let mut bytes = std::fs::read(file.path())?;
+        if let Some(last) = bytes.last_mut() {
+            *last ^= 0xff;
+        }
+        std::fs::write(file.path(), &bytes)?;
+        assert!(matches!(
+            Realme::builder().load_cache(file.path()),
+            Err(Error::CacheError(_))
+        ));
+
+        std::fs::write(file.path(), b"port = 8080")?;
+        assert!(matches!(
+            Realme::builder().load_cache(file.path()),
+            Err(Error::CacheError(_))
+        ));
+        Ok(())
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_cache_rejects_changed_sources() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let config = dir.path().join("config.toml");
+        let cache = dir.path().join("config.cache");
+        std::fs::write(&config, "port = 8080")?;
+        let builder =
+            Realme::builder()
+                .load(Adaptor::new(FileSource::<TomlParser>::new(&config)));
+        builder.clone().build()?.save_cache(&cache)?;
+        assert_eq!(
+            builder.clone().load_cache(&cache)?.get("port"),
+            Some(&Value::Integer(8080))
+        );
+
+        std::fs::write(&config, "port = 10080")?;
+        assert!(matches!(
+            builder.clone().load_cache(&cache),
+            Err(Error::CacheError(_))
+        ));
+
+        let other = Realme::builder()
+            .load(source(&std::collections::HashMap::from([("port", 8080)]))?);
+        assert!(matches!(
+            other.load_cache(&cache),
+            Err(Error::CacheError(_))
+        ));
+        Ok(())
+    }
+
+    #[cfg(feature = "env")]
+    #[test]
+    fn test_cache_leaves_out_sensitive_keys() -> anyhow::Result<()> {
+        std::env::set_var("REALME_CACHE_TEST_TOKEN", "hunter2");
+        let builder = Realme::builder()
+            .load(source(&std::collections::HashMap::from([(
+                "name", "realme",
+            )]))?)
+            .load(Adaptor::new(
+                EnvSource::<EnvParser>::new("REALME_CACHE_TEST_")
+                    .sensitive(true),
+            ));
+        let file = tempfile::NamedTempFile::new()?;
+        builder.clone().build()?.save_cache(file.path())?;
+
+        let bytes = std::fs::read(file.path())?;
+        assert!(!bytes.windows(7).any(|window| window == b"hunter2"));
+        let loaded = builder.load_cache(file.path())?;
+        assert_eq!(loaded.get("token"), None);
+        assert_eq!(loaded.get("name"), Some(&Value::from("realme")));
+        Ok(())
+    }
+}
@@ -0,0 +1,312 @@
+use std::sync::{
+    Arc,
+    OnceLock,
+};
+
+use crate::{
+    Result,
+    prelude::*,
+};
+
+/// Function producing the value of a deferred key.
+type Resolver = dyn Fn() -> Value + Send + Sync;
+
+/// A value resolved on first access, see `RealmeBuilder::deferred`.
+///
+/// Clones share the resolver and the resolved value, so the resolver runs
+/// at most once across clones of the builder and of the `Realme`, including
+/// after a `reload`.
+#[derive(Clone)]
+pub(crate) struct Deferred {
+    /// The dotted key holding the value.
+    key:      String,
+    resolver: Arc<Resolver>,
+    value:    Arc<OnceLock<Value>>,
+}
+
+impl std::fmt::Debug for Deferred {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Deferred")
+            .field("key", &self.key)
+            .field("value", &self.value.get())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Deferred {
+    pub(crate) fn new<F>(key: String, resolver: F) -> Self
+    where
+        F: Fn() -> Value + Send + Sync + 'static,
+    {
+        Self {
+            key,
+            resolver: Arc::new(resolver),
+            value: Arc::new(OnceLock::new()),
+        }
+    }
+
+    /// Returns the value, running the resolver if it has not run yet.
+    fn resolve(&self) -> &Value {
+        self.value.get_or_init(|| (self.resolver)())
+    }
+
+    /// Returns the value at `key`, resolving it if `key` is the deferred key
+    /// or one of its children.
+    fn get(&self, key: &str) -> Option<&Value> {
+        if key == self.key {
+            return Some(self.resolve());
+        }
+        let child = key.strip_prefix(&self.key)?.strip_prefix('.')?;
+        self.resolve().get(child)
+    }
+}
+
+impl Realme {
+    /// Looks `key` up in the deferred values, see `RealmeBuilder::deferred`.
+    pub(crate) fn get_deferred(&self, key: &str) -> Option<&Value> {
+        self.builder
+            .deferred
+            .iter()
+            .rev()
+            .find_map(|deferred| deferred.get(key))
+    }
+
+    /// Returns whether `key` is a table holding a deferred key, such as `db`
+    /// for `db.password`.
+    pub(crate) fn holds_deferred(&self, key: &str) -> bool {
+        self.builder
+            .deferred
+            .iter()
+            .any(|deferred| is_child(&deferred.key, key))
+    }
+
+    /// Returns the configuration with every deferred value resolved into it,
+    /// so that reading a table holding a deferred key agrees with reading
+    /// the key itself.
+    pub(crate) fn resolved(&self) -> &Value {
+        self.resolved.get_or_init(|| {
+            let mut cache = self.cache.clone();
+            self.apply_deferred(&mut cache, true);
+            cache
+        })
+    }
+
+    /// Drops the deferred values replaced by setting or removing `key` at
+    /// runtime, so the runtime value takes precedence.
+    pub(crate) fn clear_deferred(&mut self, key: &str) -> Result<()> {
+        self.clear_deferred_by(|deferred| {
+            if deferred == key || is_child(deferred, key) {
+                Some(Overlap::Replaced)
+            } else {
+                is_child(key, deferred).then_some(Overlap::Child)
+            }
+        })
+    }
+
+    /// Drops the deferred values replaced by merging `overrides` at runtime,
+    /// see `clear_deferred`.
+    pub(crate) fn clear_deferred_overlay(
+        &mut self,
+        overrides: &Value,
+    ) -> Result<()> {
+        self.clear_deferred_by(|deferred| {
+            let mut parts = deferred.split('.').peekable();
+            let mut path = String::new();
+            while let Some(part) = parts.next() {
+                if !path.is_empty() {
+                    path.push('.');
+                }
+                path.push_str(part);
+                match overrides.get(path.as_str())? {
+                    Value::Table(_) if parts.peek().is_some() => {}
+                    Value::Table(_) => return Some(Overlap::Child),
+                    _ => return Some(Overlap::Replaced),
+                }
+            }
+            None
+        })
+    }
+
+    /// Drops the deferred values for which `overlap` returns an overlap. A
+    /// deferred value whose children change is resolved into the runtime
+    /// values first, so its other children are kept.
+    fn clear_deferred_by<F>(&mut self, overlap: F) -> Result<()>
+    where
+        F: Fn(&str) -> Option<Overlap>,
+    {
+        let (cleared, kept): (Vec<_>, Vec<_>) =
+            std::mem::take(&mut self.builder.deferred)
+                .into_iter()
+                .map(|deferred| (overlap(&deferred.key), deferred))
+                .partition(|(overlap, _)| overlap.is_some());
+        self.builder.deferred =
+            kept.into_iter().map(|(_, deferred)| deferred).collect();
+        self.resolved.take();
+        for (overlap, deferred) in cleared {
+            if overlap == Some(Overlap::Child) {
+                self.set(deferred.key.as_str(), deferred.resolve())?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Sets every deferred value into `cache`, resolving them when `resolve`
+    /// is set, or only those already resolved otherwise.
+    pub(crate) fn apply_deferred(&self, cache: &mut Value, resolve: bool) {
+        for deferred in &self.builder.deferred {
+            let value = if resolve {
+                Some(deferred.resolve())
+            } else {
+                deferred.value.get()
+            };
+            if let Some(value) = value {
+                // A key that can not be set, e.g. below a scalar, is left
+                // out, as `get` can not reach it either.
+                let _ = cache.set(deferred.key.as_str(), value.clone());
+            }
+        }
+    }
+}
+
+/// How a runtime change overlaps a deferred key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Overlap {
+    /// The change replaces the deferred value.
+    Replaced,
+    /// The change sets or removes children of the deferred value.
+    Child,
+}
+
+/// Returns whether the dotted `key` is below `parent`.
+fn is_child(key: &str, parent: &str) -> bool {
+    key.strip_prefix(parent)
+        .is_some_and(|rest| rest.starts_with('.'))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{
+        AtomicUsize,
+        Ordering,
+    };
+
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Config {
+        name: String,
+        db:   Db,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Db {
+        user:     String,
+        password: String,
+    }
+
+    #[test]
+    fn test_deferred_resolves_once() -> anyhow::Result<()> {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let mut realme = Realme::builder()
+            .defaults(&Value::from_dotted([
+                ("name", "realme"),
+                ("db.user", "admin"),
+            ])?)
+            .deferred("db.password", move || {
+                counter.fetch_add(1, Ordering::SeqCst);
+                Value::from("hunter2")
+            })
+            .build()?;
+
+        let dump = serde_json::to_value(&realme)?;
+        assert_eq!(dump["db"].get("password"), None);
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+
+        assert_eq!(realme.get("db.password"), Some(&Value::from("hunter2")));
+        assert_eq!(
+            realme.get_as::<String, _>("db.password").as_deref(),
+            Some("hunter2")
+        );
+        assert_eq!(realme.try_deserialize::<Config>()?, Config {
+            name: "realme".to_string(),
+            db:   Db {
+                user:     "admin".to_string(),
+                password: "hunter2".to_string(),
+            },
+        });
+        realme.reload()?;
+        assert_eq!(realme.get("db.password"), Some(&Value::from("hunter2")));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        let dump = serde_json::to_value(&realme)?;
+        assert_eq!(dump["db"]["password"], "hunter2");
+        Ok(())
+    }
+
+    #[test]
+    fn test_runtime_values_replace_deferred() -> anyhow::Result<()> {
+        let builder = Realme::builder()
+            .defaults(&Value::from_dotted([
+                ("name", "realme"),
+                ("db.user", "admin"),
+            ])?)
+            .deferred("db.password", || Value::from("hunter2"));
+
+        let mut realme = builder.clone().build()?;
+        realme.set("db.password", "swordfish")?;
+        assert_eq!(realme.get("db.password"), Some(&Value::from("swordfish")));
+        realme.reload()?;
+        assert_eq!(realme.get("db.password"), Some(&Value::from("swordfish")));
+        assert_eq!(
+            realme.try_deserialize::<Config>()?.db.password,
+            "swordfish"
+        );
+
+        let mut realme = builder.clone().build()?;
+        realme.overlay(Map::from_iter([(
+            "db".to_string(),
+            Value::from_dotted([("password", "swordfish")])?,
+        )]))?;
+        assert_eq!(realme.get("db.password"), Some(&Value::from("swordfish")));
+
+        let mut realme = builder.clone().build()?;
+        realme.remove("db.password");
+        assert_eq!(realme.get("db.password"), None);
+        assert_eq!(realme.get("db.user"), Some(&Value::from("admin")));
+        Ok(())
+    }
+
+    #[test]
+    fn test_deferred_seen_from_parent() -> anyhow::Result<()> {
+        let mut realme = Realme::builder()
+            .defaults(&Value::from_dotted([("db.user", "admin")])?)
+            .deferred("db.password", || Value::from("hunter2"))
+            .deferred("cache", || {
+                Value::from_dotted([("host", "localhost"), ("port", "6379")])
+                    .unwrap_or_default()
+            })
+            .build()?;
+
+        assert_eq!(
+            realme.get("db"),
+            Some(&Value::from_dotted([
+                ("user", "admin"),
+                ("password", "hunter2"),
+            ])?)
+        );
+        assert_eq!(realme.get_all("*.password"), vec![&Value::from("hunter2")]);
+
+        realme.set("cache.port", 6380)?;
+        assert_eq!(realme.get("cache.host"), Some(&Value::from("localhost")));
+        assert_eq!(realme.get("cache.port"), Some(&Value::Integer(6380)));
+        realme.set("db.user", "root")?;
+        assert_eq!(
+            realme.get("db").and_then(|db| db.get("user")),
+            Some(&Value::from("root"))
+        );
+        Ok(())
+    }
+}
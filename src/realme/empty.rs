@@ -0,0 +1,95 @@
+use crate::prelude::*;
+
+/// How empty values are represented once the sources are merged.
+///
+/// Parsers disagree on what an empty value is: `CmdParser` yields an empty
+/// string for `key= `, while `YamlParser` yields `Value::Null` for `key:`.
+/// Choosing a representation on the builder makes both look the same.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EmptyValues {
+    /// Keep the values as produced by each parser.
+    #[default]
+    Keep,
+    /// Represent empty values as `Value::Null`.
+    Null,
+    /// Represent empty values as an empty `Value::String`.
+    EmptyString,
+}
+
+impl EmptyValues {
+    /// Rewrites the empty values found in `value` to the chosen
+    /// representation.
+    ///
+    /// Only scalars are considered empty: empty arrays and tables are left
+    /// untouched.
+    pub(crate) fn apply(self, value: &mut Value) {
+        match (self, &mut *value) {
+            (Self::Keep, _) => {}
+            (Self::Null, Value::String(s)) if s.is_empty() => {
+                *value = Value::Null;
+            }
+            (Self::EmptyString, Value::Null) => {
+                *value = Value::String(String::new());
+            }
+            (_, Value::Array(array)) => {
+                for v in array {
+                    self.apply(v);
+                }
+            }
+            (_, Value::Table(table)) => {
+                for (_, v) in table {
+                    self.apply(v);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(all(test, feature = "cmd", feature = "yaml"))]
+mod tests {
+    use super::*;
+
+    fn build(empty_values: EmptyValues) -> anyhow::Result<(Value, Value)> {
+        let from_cmd = Realme::builder()
+            .load(Adaptor::new(CmdSource::<CmdParser>::new(
+                "name= , tags=[a;b]",
+            )))
+            .empty_values(empty_values)
+            .build()?;
+        let from_yaml = Realme::builder()
+            .load(Adaptor::new(StringSource::<YamlParser>::new(
+                "name:\ntags: [a, b]\n",
+            )))
+            .empty_values(empty_values)
+            .build()?;
+        Ok((
+            from_cmd.get("name").cloned().expect("name from cmd"),
+            from_yaml.get("name").cloned().expect("name from yaml"),
+        ))
+    }
+
+    #[test]
+    fn test_parsers_disagree_by_default() -> anyhow::Result<()> {
+        assert_eq!(
+            build(EmptyValues::Keep)?,
+            (Value::String(String::new()), Value::Null)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_empty_as_null() -> anyhow::Result<()> {
+        assert_eq!(build(EmptyValues::Null)?, (Value::Null, Value::Null));
+        Ok(())
+    }
+
+    #[test]
+    fn test_empty_as_empty_string() -> anyhow::Result<()> {
+        assert_eq!(
+            build(EmptyValues::EmptyString)?,
+            (Value::String(String::new()), Value::String(String::new()))
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,120 @@
+use crate::prelude::*;
+
+/// String values lowercased once the sources are merged.
+#[derive(Debug, Clone, Default)]
+pub(crate) enum Lowercase {
+    /// Values are kept as loaded.
+    #[default]
+    None,
+    /// Values found at these keys, or nested under them, are lowercased.
+    Keys(Vec<String>),
+    /// Every string value is lowercased.
+    All,
+}
+
+impl Lowercase {
+    /// Adds keys whose values are lowercased.
+    pub(crate) fn extend<K: AsRef<str>>(&mut self, keys: &[K]) {
+        let keys = keys.iter().map(|key| key.as_ref().to_string());
+        match self {
+            Self::None => *self = Self::Keys(keys.collect()),
+            Self::Keys(existing) => existing.extend(keys),
+            Self::All => {}
+        }
+    }
+
+    /// Lowercases the targeted string values of `cache`.
+    pub(crate) fn apply(&self, cache: &mut Value) {
+        match self {
+            Self::None => {}
+            Self::Keys(keys) => {
+                for key in keys {
+                    if let Some(value) = cache.find_mut(key.as_str()) {
+                        lowercase(value);
+                    }
+                }
+            }
+            Self::All => lowercase(cache),
+        }
+    }
+}
+
+/// Lowercases `value` and the string values nested in it.
+fn lowercase(value: &mut Value) {
+    match value {
+        Value::String(s) => *s = s.to_lowercase(),
+        Value::Array(array) => {
+            for v in array {
+                lowercase(v);
+            }
+        }
+        Value::Table(table) => {
+            for (_, v) in table {
+                lowercase(v);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn realme(builder: RealmeBuilder) -> anyhow::Result<Realme> {
+        Ok(builder
+            .load(Adaptor::new(SerSource::<SerParser, _>::new(
+                serde_json::json!({
+                    "log": { "level": "INFO", "Format": "JSON" },
+                    "server": { "modes": ["Fast", "SAFE"], "Name": "Realme" },
+                }),
+            )))
+            .build()?)
+    }
+
+    #[test]
+    fn test_lowercase_scoped_keys() -> anyhow::Result<()> {
+        let realme = realme(
+            Realme::builder().lowercase_values(&["log.level", "server.modes"]),
+        )?;
+        assert_eq!(
+            realme.get_as::<String, _>("log.level").as_deref(),
+            Some("info")
+        );
+        assert_eq!(
+            realme.get_as::<Vec<String>, _>("server.modes"),
+            Some(vec!["fast".to_string(), "safe".to_string()])
+        );
+        assert_eq!(
+            realme.get_as::<String, _>("log.Format").as_deref(),
+            Some("JSON")
+        );
+        assert_eq!(
+            realme.get_as::<String, _>("server.Name").as_deref(),
+            Some("Realme")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_lowercase_missing_key_stays_absent() -> anyhow::Result<()> {
+        let realme =
+            realme(Realme::builder().lowercase_values(&["missing.key"]))?;
+        assert_eq!(realme.get("missing"), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_lowercase_all_values() -> anyhow::Result<()> {
+        let realme = realme(Realme::builder().lowercase_all_values())?;
+        assert_eq!(
+            realme.get_as::<String, _>("log.Format").as_deref(),
+            Some("json")
+        );
+        assert_eq!(
+            realme.get_as::<String, _>("server.Name").as_deref(),
+            Some("realme")
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,127 @@
+use std::{
+    collections::BTreeMap,
+    fmt,
+    path::PathBuf,
+};
+
+use crate::{
+    prelude::*,
+    value::join_key,
+};
+
+/// The source that provided a key, see `Realme::origin`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceInfo {
+    /// The name of the adaptor, or the type of its source when it is
+    /// unnamed, see `Adaptor::name`.
+    pub name:     String,
+    /// The priority of the adaptor, see `Adaptor::priority`.
+    pub priority: i32,
+    /// The path of the file the source reads, if any.
+    pub path:     Option<PathBuf>,
+    /// Whether the adaptor belongs to the default layer, see
+    /// `RealmeBuilder::load_defaults`.
+    pub default:  bool,
+}
+
+impl SourceInfo {
+    fn new(adaptor: &Adaptor, default: bool) -> Self {
+        Self {
+            name: adaptor.source_name(),
+            priority: adaptor.priority,
+            path: adaptor.path().map(PathBuf::from),
+            default,
+        }
+    }
+}
+
+impl fmt::Display for SourceInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)?;
+        if let Some(path) = &self.path {
+            write!(f, " at {}", path.display())?;
+        }
+        if self.default {
+            write!(f, ", default layer")?;
+        } else {
+            write!(f, ", priority {}", self.priority)?;
+        }
+        Ok(())
+    }
+}
+
+/// The source that last provided each key while merging.
+///
+/// Tables are recorded key by key while arrays are recorded whole, like in
+/// a `MergeAudit`, but only the last source of each key is kept.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Origins {
+    sources: Vec<SourceInfo>,
+    keys:    BTreeMap<String, usize>,
+}
+
+impl Origins {
+    /// Records the values parsed by `adaptor` as provided by it.
+    pub(crate) fn record(
+        &mut self,
+        adaptor: &Adaptor,
+        default: bool,
+        value: &Value,
+    ) {
+        let source = SourceInfo::new(adaptor, default);
+        let index = self.sources.len();
+        self.sources.push(source.clone());
+        let mut keys = Vec::new();
+        collect_keys(String::new(), value, &mut keys);
+        // Sources reading several files name the file of each key
+        let mut files = BTreeMap::new();
+        for key in keys {
+            let index = match adaptor.key_path(&key) {
+                Some(path) => *files.entry(path.clone()).or_insert_with(|| {
+                    self.sources.push(SourceInfo {
+                        path: Some(path),
+                        ..source.clone()
+                    });
+                    self.sources.len() - 1
+                }),
+                None => index,
+            };
+            self.keys.insert(key, index);
+        }
+    }
+
+    /// Returns the source recorded for `key`, or for the array holding it
+    /// when `key` points into an array, as in `servers[0].host`.
+    pub(crate) fn get(&self, key: &str) -> Option<&SourceInfo> {
+        let mut key = key;
+        loop {
+            if let Some(&index) = self.keys.get(key) {
+                return self.sources.get(index);
+            }
+            key = &key[..key.rfind(['.', '['])?];
+        }
+    }
+
+    /// Records the keys of `other` over the keys of `self`.
+    pub(crate) fn extend(&mut self, other: &Self) {
+        let offset = self.sources.len();
+        self.sources.extend(other.sources.iter().cloned());
+        for (key, index) in &other.keys {
+            self.keys.insert(key.clone(), index + offset);
+        }
+    }
+}
+
+/// Appends the keys of `value` under `path` to `keys`, recording tables key
+/// by key and arrays whole.
+fn collect_keys(path: String, value: &Value, keys: &mut Vec<String>) {
+    match value {
+        Value::Table(table) => {
+            for (key, value) in table {
+                collect_keys(join_key(&path, key), value, keys);
+            }
+        }
+        _ if path.is_empty() => {}
+        _ => keys.push(path),
+    }
+}
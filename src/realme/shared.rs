@@ -1,6 +1,7 @@
 use std::{
     sync::{
         Arc,
+        OnceLock,
         RwLock,
     },
     time::{
@@ -9,7 +10,12 @@ use std::{
     },
 };
 
-use super::SharedRealme;
+use super::{
+    SharedRealme,
+    builder::record_sensitive,
+    origin::Origins,
+    subscribe::Subscribers,
+};
 use crate::{
     Error,
     Result,
@@ -17,6 +23,17 @@ use crate::{
 };
 
 impl RealmeBuilder {
+    /// Watches the sources of every adaptor once built with `shared_build`,
+    /// like calling `Adaptor::watch` on each of them.
+    ///
+    /// Sources that can not be watched, such as environment variables, are
+    /// left alone.
+    #[must_use]
+    pub const fn watch(mut self) -> Self {
+        self.watch = true;
+        self
+    }
+
     /// Builds the `SharedRealme` instance.
     ///
     /// This method checks the profile, sorts the adaptors by priority,
@@ -27,21 +44,46 @@ impl RealmeBuilder {
     /// * `Result<SharedRealme, Error>` - A `Result` containing the built
     ///   `SharedRealme` instance or an `Error` if the build process fails.
     pub fn shared_build(mut self) -> Result<SharedRealme> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("shared_build").entered();
         self.check_profile()?;
-        self.adaptors.sort_by(|a, b| a.priority.cmp(&b.priority));
+        self.adaptors.sort_by_key(|a| a.priority);
+        if self.watch {
+            for adaptor in &mut self.adaptors {
+                adaptor.watch = true;
+            }
+        }
         let (sender, receiver) = crossbeam::channel::unbounded::<()>();
-        let cache = update_cache(&self.adaptors, &sender)?;
-        let shared_realme = Arc::new(RwLock::new(Realme {
+        let mut origins = Origins::default();
+        let defaults_layer = self.parse_defaults(None, &mut origins)?;
+        let mut sensitive = Vec::new();
+        let mut cache = update_cache(
+            defaults_layer
+                .clone()
+                .unwrap_or_else(|| Value::Table(Map::new())),
+            &self,
+            &sender,
+            &mut sensitive,
+            &mut origins,
+        )?;
+        self.post_process(&mut cache)?;
+        self.run_validators(&cache)?;
+        let shared_realme = Arc::new(RwLock::new(Arc::new(Realme {
             cache,
             default: None,
             builder: self.clone(),
-        }));
+            defaults_layer,
+            subscribers: Subscribers::default(),
+            sensitive,
+            origins,
+            resolved: OnceLock::new(),
+        })));
 
         let shared_realme_clone = shared_realme.clone();
 
-        std::thread::spawn(move || -> Result<()> {
+        std::thread::spawn(move || {
             // To avoid too many updates, set a debounce time
-            let debounce_duration = Duration::from_millis(1000);
+            let debounce_duration = Duration::from_secs(1);
             // Timeout time
             let timeout_duration = Duration::from_millis(500);
             let mut last_update = Instant::now();
@@ -64,16 +106,34 @@ impl RealmeBuilder {
                 if should_update &&
                     now.duration_since(last_update) >= debounce_duration
                 {
-                    // Update shared_realme
-                    if let Ok(mut realme) = shared_realme_clone.write() {
-                        realme.reload()?;
+                    // Rebuild without holding the lock, so readers are only
+                    // blocked while the new instance is swapped in. A failed
+                    // rebuild, such as of a file saved halfway, keeps the
+                    // current instance until the next change.
+                    if let Ok(builder) = shared_realme_clone
+                        .read()
+                        .map(|realme| realme.builder.clone())
+                    {
+                        match builder.build() {
+                            Ok(mut realme) => {
+                                if let Ok(mut current) =
+                                    shared_realme_clone.write()
+                                {
+                                    realme.take_over(&current);
+                                    *current = Arc::new(realme);
+                                }
+                            }
+                            #[allow(unused_variables)]
+                            Err(e) => {
+                                #[cfg(feature = "tracing")]
+                                tracing::error!("Reload error: {:?}", e);
+                            }
+                        }
                     }
                     last_update = now;
                     should_update = false;
                 }
             }
-
-            Ok(())
         });
 
         Ok(shared_realme)
@@ -81,27 +141,69 @@ impl RealmeBuilder {
 }
 
 fn update_cache(
-    adaptor: &[Adaptor],
+    mut cache: Value,
+    builder: &RealmeBuilder,
     sender: &crossbeam::channel::Sender<()>,
+    sensitive: &mut Vec<String>,
+    origins: &mut Origins,
 ) -> Result<Value> {
-    let mut cache = Value::Table(Map::new());
-    adaptor.iter().try_for_each(|adaptor| {
+    builder.adaptors.iter().try_for_each(|adaptor| {
         adaptor.watcher(sender.clone()).and_then(|()| {
-            adaptor.parse().and_then(|value| match value {
-                Value::Table(table) => {
-                    cache.merge(&Value::Table(table));
-                    Ok(())
-                }
-                Value::Null => Ok(()),
-                _ => Err(Error::new_build_error(
-                    "Adaptor parse result is not a table".to_string(),
-                )),
+            builder.parse_adaptor(adaptor).and_then(|value| {
+                origins.record(adaptor, false, &value);
+                record_sensitive(adaptor, &value, sensitive);
+                builder.merge_parsed(&mut cache, adaptor, &value)
             })
         })
     })?;
     Ok(cache)
 }
 
+/// Access to the current state of a `SharedRealme`.
+pub trait SharedRealmeExt {
+    /// Returns a snapshot of the current configuration.
+    ///
+    /// Reloads replace the configuration as a whole, so the snapshot is
+    /// consistent: it never mixes values from before and after a reload,
+    /// and later reloads leave it untouched.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::LockError` if a thread panicked while updating the
+    /// configuration.
+    fn current(&self) -> Result<Arc<Realme>>;
+
+    /// Changes the configuration with `f`, such as with `Realme::set`.
+    ///
+    /// Snapshots returned by `current` are left untouched: the
+    /// configuration is copied first if one of them is still alive.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of `f`, or `Error::LockError` if a thread panicked
+    /// while updating the configuration.
+    fn update<T, F>(&self, f: F) -> Result<T>
+    where
+        F: FnOnce(&mut Realme) -> Result<T>;
+}
+
+impl SharedRealmeExt for SharedRealme {
+    fn current(&self) -> Result<Arc<Realme>> {
+        let realme =
+            self.read().map_err(|e| Error::LockError(e.to_string()))?;
+        Ok(Arc::clone(&realme))
+    }
+
+    fn update<T, F>(&self, f: F) -> Result<T>
+    where
+        F: FnOnce(&mut Realme) -> Result<T>,
+    {
+        let mut realme =
+            self.write().map_err(|e| Error::LockError(e.to_string()))?;
+        f(Arc::make_mut(&mut realme))
+    }
+}
+
 // impl SharedRealme {
 //     pub fn get_realme(&self) -> Result<&Realme> {
 //         let Ok(realme) = self.0.read() else {
@@ -0,0 +1,313 @@
+use std::sync::Arc;
+#[cfg(feature = "watch")]
+use std::sync::Weak;
+
+use crate::prelude::*;
+
+/// Callback invoked with the old and new value of a watched key.
+type Callback = Arc<dyn Fn(Option<&Value>, Option<&Value>) + Send + Sync>;
+
+/// Channel delivering snapshots of the configuration, see
+/// `Realme::subscribe`. The sender keeps a receiver to drop the snapshot a
+/// lagging consumer has not taken yet, and a weak handle telling whether the
+/// `Subscription` is still alive.
+#[cfg(feature = "watch")]
+type Channel = (
+    crossbeam::channel::Sender<Arc<Value>>,
+    crossbeam::channel::Receiver<Arc<Value>>,
+    Weak<()>,
+);
+
+/// Receiver of the configuration snapshots, returned by
+/// `Realme::subscribe`.
+///
+/// It dereferences to a `crossbeam::channel::Receiver`. Once it is dropped
+/// the channel is removed from the `Realme` on the next update.
+#[cfg(feature = "watch")]
+#[derive(Debug)]
+pub struct Subscription {
+    receiver: crossbeam::channel::Receiver<Arc<Value>>,
+    _alive:   Arc<()>,
+}
+
+#[cfg(feature = "watch")]
+impl std::ops::Deref for Subscription {
+    type Target = crossbeam::channel::Receiver<Arc<Value>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.receiver
+    }
+}
+
+/// Registry of callbacks watching individual keys of a `Realme`.
+#[derive(Default, Clone)]
+pub(crate) struct Subscribers {
+    entries:  Vec<(String, Callback)>,
+    /// Channels receiving every new configuration.
+    #[cfg(feature = "watch")]
+    channels: Vec<Channel>,
+}
+
+impl std::fmt::Debug for Subscribers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(self.entries.iter().map(|(key, _)| key))
+            .finish()
+    }
+}
+
+impl Subscribers {
+    /// Captures the current value of every watched key.
+    pub(crate) fn snapshot(&self, cache: &Value) -> Vec<Option<Value>> {
+        self.entries
+            .iter()
+            .map(|(key, _)| cache.get(key.as_str()).cloned())
+            .collect()
+    }
+
+    /// Invokes the callbacks whose key resolves to a different value than
+    /// in the given snapshot, and sends `cache` to the subscribed channels.
+    pub(crate) fn notify(&mut self, snapshot: &[Option<Value>], cache: &Value) {
+        for ((key, callback), old) in self.entries.iter().zip(snapshot) {
+            let new = cache.get(key.as_str());
+            if old.as_ref() != new {
+                callback(old.as_ref(), new);
+            }
+        }
+        #[cfg(feature = "watch")]
+        self.publish(cache);
+    }
+
+    /// Sends a snapshot of `cache` to every subscribed channel, replacing
+    /// the snapshot not received yet, if any. The channels of dropped
+    /// subscriptions are removed.
+    #[cfg(feature = "watch")]
+    fn publish(&mut self, cache: &Value) {
+        self.channels.retain(|(.., alive)| alive.strong_count() > 0);
+        if self.channels.is_empty() {
+            return;
+        }
+        let snapshot = Arc::new(cache.clone());
+        for (sender, stale, _) in &self.channels {
+            while stale.try_recv().is_ok() {}
+            // The channel holds one snapshot and was just drained, and
+            // `stale` keeps it connected, so sending can not fail.
+            let _ = sender.try_send(snapshot.clone());
+        }
+    }
+}
+
+impl Realme {
+    /// Registers a callback invoked when the value of `key` changes.
+    ///
+    /// The callback is run by `reload` and `set` whenever the resolved value
+    /// of the key differs from the value it had before. It receives the old
+    /// and the new value: the old value is `None` when the key newly
+    /// appeared, and the new value is `None` when the key was removed.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key to watch, e.g. `feature.flags`.
+    /// * `callback` - The function called with the old and new value.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use realme::prelude::*;
+    ///
+    /// let mut realme = Realme::builder().build().expect("build config");
+    /// realme.on_change("feature.flags", |old, new| {
+    ///     println!("feature.flags changed from {old:?} to {new:?}");
+    /// });
+    /// realme
+    ///     .set("feature.flags", vec!["beta"])
+    ///     .expect("set config");
+    /// ```
+    pub fn on_change<K, F>(&mut self, key: K, callback: F)
+    where
+        K: Into<String>,
+        F: Fn(Option<&Value>, Option<&Value>) + Send + Sync + 'static,
+    {
+        self.subscribers
+            .entries
+            .push((key.into(), Arc::new(callback)));
+    }
+
+    /// Returns a channel receiving a snapshot of the configuration every
+    /// time it is updated by `reload`, `set` or `overlay`.
+    ///
+    /// The channel holds a single snapshot: when the consumer lags behind,
+    /// a new snapshot replaces the one it has not received yet, so it
+    /// always gets the latest configuration and updates are never queued
+    /// up. The channel is disconnected once the `Realme` and its clones are
+    /// dropped, and the receiver can be used with `crossbeam::select!`
+    /// through `Deref`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use realme::prelude::*;
+    ///
+    /// let mut realme = Realme::builder().build().expect("build config");
+    /// let snapshots = realme.subscribe();
+    /// realme.set("server.port", 8080).expect("set config");
+    /// let snapshot = snapshots.try_recv().expect("receive snapshot");
+    /// assert_eq!(snapshot.get("server.port"), Some(&Value::Integer(8080)));
+    /// ```
+    #[cfg(feature = "watch")]
+    pub fn subscribe(&mut self) -> Subscription {
+        let (sender, receiver) = crossbeam::channel::bounded(1);
+        let alive = Arc::new(());
+        self.subscribers.channels.push((
+            sender,
+            receiver.clone(),
+            Arc::downgrade(&alive),
+        ));
+        Subscription {
+            receiver,
+            _alive: alive,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+
+    type Changes = Arc<Mutex<Vec<(Option<Value>, Option<Value>)>>>;
+
+    fn record(realme: &mut Realme, key: &str) -> Changes {
+        let changes = Changes::default();
+        let recorded = changes.clone();
+        realme.on_change(key, move |old, new| {
+            if let Ok(mut recorded) = recorded.lock() {
+                recorded.push((old.cloned(), new.cloned()));
+            }
+        });
+        changes
+    }
+
+    #[test]
+    fn test_on_change_set() -> anyhow::Result<()> {
+        let mut realme = Realme::builder().build()?;
+        let changes = record(&mut realme, "server.port");
+
+        realme.set("server.port", 8080)?;
+        realme.set("server.port", 8080)?;
+        realme.set("server.host", "localhost")?;
+        realme.set("server.port", 9090)?;
+
+        let changes = changes.lock().expect("lock changes");
+        assert_eq!(*changes, vec![
+            (None, Some(Value::Integer(8080))),
+            (Some(Value::Integer(8080)), Some(Value::Integer(9090))),
+        ]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_on_change_parent_key() -> anyhow::Result<()> {
+        let mut realme = Realme::builder().build()?;
+        realme.set("server.port", 8080)?;
+        let changes = record(&mut realme, "server.port");
+
+        realme
+            .set("server", std::collections::HashMap::<String, i64>::new())?;
+
+        let changes = changes.lock().expect("lock changes");
+        assert_eq!(*changes, vec![(Some(Value::Integer(8080)), None)]);
+        Ok(())
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_on_change_file_reload() -> anyhow::Result<()> {
+        let file = tempfile::NamedTempFile::new()?;
+        std::fs::write(&file, "[feature]\nflags = [\"a\"]\nold = true\n")?;
+        let mut realme = Realme::builder()
+            .load(Adaptor::new(FileSource::<TomlParser>::new(file.path())))
+            .build()?;
+        let flags = record(&mut realme, "feature.flags");
+        let old = record(&mut realme, "feature.old");
+        let new = record(&mut realme, "feature.new");
+
+        std::fs::write(&file, "[feature]\nflags = [\"a\", \"b\"]\nnew = 1\n")?;
+        realme.reload()?;
+
+        assert_eq!(*flags.lock().expect("lock changes"), vec![(
+            Some(Value::Array(vec![Value::String("a".to_string())])),
+            Some(Value::Array(vec![
+                Value::String("a".to_string()),
+                Value::String("b".to_string()),
+            ])),
+        )]);
+        assert_eq!(*old.lock().expect("lock changes"), vec![(
+            Some(Value::Boolean(true)),
+            None
+        )]);
+        assert_eq!(*new.lock().expect("lock changes"), vec![(
+            None,
+            Some(Value::Integer(1))
+        )]);
+
+        realme.reload()?;
+        assert_eq!(flags.lock().expect("lock changes").len(), 1);
+        Ok(())
+    }
+
+    #[cfg(all(feature = "watch", feature = "toml"))]
+    #[test]
+    fn test_subscribe_reload() -> anyhow::Result<()> {
+        let file = tempfile::NamedTempFile::new()?;
+        std::fs::write(&file, "port = 8080\n")?;
+        let mut realme = Realme::builder()
+            .load(Adaptor::new(FileSource::<TomlParser>::new(file.path())))
+            .build()?;
+        let snapshots = realme.subscribe();
+        assert!(snapshots.try_recv().is_err());
+
+        std::fs::write(&file, "port = 9090\n")?;
+        realme.reload()?;
+        let snapshot = snapshots.try_recv()?;
+        assert_eq!(snapshot.get("port"), Some(&Value::Integer(9090)));
+
+        std::fs::write(&file, "port = 9191\n")?;
+        realme.reload()?;
+        let snapshot = snapshots.try_recv()?;
+        assert_eq!(snapshot.get("port"), Some(&Value::Integer(9191)));
+        assert!(snapshots.try_recv().is_err());
+        Ok(())
+    }
+
+    #[cfg(feature = "watch")]
+    #[test]
+    fn test_subscribe_latest_wins() -> anyhow::Result<()> {
+        let mut realme = Realme::builder().build()?;
+        let snapshots = realme.subscribe();
+        realme.set("port", 1)?;
+        realme.set("port", 2)?;
+        realme.set("port", 3)?;
+
+        assert_eq!(snapshots.try_recv()?.get("port"), Some(&Value::Integer(3)));
+        assert!(snapshots.try_recv().is_err());
+
+        drop(realme);
+        assert!(snapshots.recv().is_err());
+        Ok(())
+    }
+
+    #[cfg(feature = "watch")]
+    #[test]
+    fn test_subscribe_dropped() -> anyhow::Result<()> {
+        let mut realme = Realme::builder().build()?;
+        let kept = realme.subscribe();
+        drop(realme.subscribe());
+        realme.set("port", 1)?;
+
+        assert_eq!(realme.subscribers.channels.len(), 1);
+        assert_eq!(kept.try_recv()?.get("port"), Some(&Value::Integer(1)));
+        Ok(())
+    }
+}
@@ -0,0 +1,258 @@
+#[cfg(any(feature = "toml", feature = "json", feature = "yaml"))]
+use std::path::Path;
+
+#[cfg(any(feature = "toml", feature = "json", feature = "yaml"))]
+use serde::ser::Error as _;
+
+#[cfg(any(feature = "toml", feature = "json", feature = "yaml"))]
+use crate::{
+    Error,
+    errors::SerializeError,
+};
+use crate::{
+    Result,
+    prelude::*,
+};
+
+impl Realme {
+    /// Serializes the configuration values into a string in the format of
+    /// the emitter `E`, such as `TomlParser`, `JsonParser` or `YamlParser`.
+    ///
+    /// The values are those of `Realme::redacted`: keys provided by
+    /// sensitive sources are left out. Table keys are sorted, so the output
+    /// is stable across runs, and floats are written as set with
+    /// `RealmeBuilder::float_format`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::SerializeError` if the values can not be represented
+    /// in the format, see `Emitter::emit`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust ignore
+    /// let mut realme = Realme::builder().build()?;
+    /// realme.set("server.port", 8080)?;
+    /// let toml = realme.try_serialize_to::<TomlParser>()?;
+    /// assert_eq!(toml, "[server]\nport = 8080\n");
+    /// ```
+    pub fn try_serialize_to<E: Emitter>(&self) -> Result<String> {
+        let mut value = self.redacted();
+        self.builder.float_format.apply(&mut value);
+        E::emit(&value)
+    }
+
+    /// Writes the configuration values to a file, in the format given by its
+    /// extension: `toml`, `json`, or `yaml` and `yml`, each with its
+    /// feature enabled.
+    ///
+    /// The file is written as by `try_serialize_to`, replacing any content.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file to write.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::SerializeError` if the extension is not a supported
+    /// format or the values can not be represented in it, or
+    /// `Error::WriteFileError` if the file can not be written.
+    #[cfg(any(feature = "toml", feature = "json", feature = "yaml"))]
+    pub fn write_to_file<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(str::to_ascii_lowercase);
+        let content = match extension.as_deref() {
+            #[cfg(feature = "toml")]
+            Some("toml") => self.try_serialize_to::<TomlParser>()?,
+            #[cfg(feature = "json")]
+            Some("json") => self.try_serialize_to::<JsonParser>()?,
+            #[cfg(feature = "yaml")]
+            Some("yaml" | "yml") => self.try_serialize_to::<YamlParser>()?,
+            _ => {
+                return Err(SerializeError::custom(format!(
+                    "can not detect the format of {} from its extension",
+                    path.display()
+                ))
+                .into());
+            }
+        };
+        std::fs::write(path, content).map_err(|e| {
+            Error::WriteFileError(format!(
+                "Failed to write file: {}, error: {}",
+                path.display(),
+                e
+            ))
+        })
+    }
+}
+
+#[cfg(all(test, feature = "toml", feature = "json", feature = "yaml"))]
+mod tests {
+    use super::*;
+
+    fn realme() -> anyhow::Result<Realme> {
+        let mut realme = Realme::builder()
+            .load(Adaptor::new(StringSource::<TomlParser>::new(
+                r#"
+                name = "realme"
+                [server]
+                port = 8080
+                hosts = ["a", "b"]
+                "#,
+            )))
+            .build()?;
+        realme.set("server.debug", true)?;
+        Ok(realme)
+    }
+
+    #[test]
+    fn test_try_serialize_to() -> anyhow::Result<()> {
+        let realme = realme()?;
+        assert_eq!(
+            realme.try_serialize_to::<TomlParser>()?,
+            "name = \"realme\"\n\n[server]\ndebug = true\nhosts = [\n    \
+             \"a\",\n    \"b\",\n]\nport = 8080\n"
+        );
+        assert_eq!(
+            realme.try_serialize_to::<YamlParser>()?,
+            "---\nname: realme\nserver:\n  debug: true\n  hosts:\n    - a\n    \
+             - b\n  port: 8080\n"
+        );
+        let json = realme.try_serialize_to::<JsonParser>()?;
+        assert_eq!(
+            JsonParser::parse(json.as_str())?,
+            serde_json::json!({
+                "name": "realme",
+                "server": { "debug": true, "hosts": ["a", "b"], "port": 8080 }
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_try_serialize_to_toml_rejects_null() -> anyhow::Result<()> {
+        let mut realme = realme()?;
+        realme.set("server.proxy", Value::Null)?;
+        let err = realme
+            .try_serialize_to::<TomlParser>()
+            .expect_err("TOML has no null")
+            .to_string();
+        assert!(err.contains("`server.proxy` is null"), "{err}");
+        Ok(())
+    }
+
+    #[test]
+    fn test_float_format() -> anyhow::Result<()> {
+        let build = |format: FloatFormat| {
+            Realme::builder()
+                .load(Adaptor::new(StringSource::<TomlParser>::new(
+                    "ratio = 1.1\nscale = 1.23456\n",
+                )))
+                .float_format(format)
+                .build()
+        };
+
+        let realme = build(FloatFormat::default())?;
+        assert_eq!(
+            realme.try_serialize_to::<TomlParser>()?,
+            "ratio = 1.1\nscale = 1.23456\n"
+        );
+        assert_eq!(
+            realme.try_serialize_to::<YamlParser>()?,
+            "---\nratio: 1.1\nscale: 1.23456\n"
+        );
+        assert_eq!(
+            realme
+                .try_serialize_to::<JsonParser>()?
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .concat(),
+            "{\"ratio\":1.1,\"scale\":1.23456}"
+        );
+
+        let realme = build(FloatFormat::Precision(2))?;
+        assert_eq!(
+            realme.try_serialize_to::<TomlParser>()?,
+            "ratio = 1.1\nscale = 1.23\n"
+        );
+        assert_eq!(realme.get("scale"), Some(&Value::Float(1.23456)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_datetime_round_trip() -> anyhow::Result<()> {
+        let realme = Realme::builder()
+            .load(Adaptor::new(StringSource::<TomlParser>::new(
+                "released = 1979-05-27T07:32:00Z",
+            )))
+            .build()?;
+        let released: Datetime = "1979-05-27T07:32:00Z".parse()?;
+
+        let toml = realme.try_serialize_to::<TomlParser>()?;
+        assert_eq!(toml, "released = 1979-05-27T07:32:00Z\n");
+        let reloaded = Realme::builder()
+            .load(Adaptor::new(StringSource::<TomlParser>::new(&toml)))
+            .build()?;
+        assert_eq!(
+            reloaded.get("released"),
+            Some(&Value::Datetime(released.clone()))
+        );
+
+        let yaml = realme.try_serialize_to::<YamlParser>()?;
+        assert_eq!(yaml, "---\nreleased: 1979-05-27T07:32:00Z\n");
+        let reloaded = Realme::builder()
+            .load(Adaptor::new(StringSource::<YamlParser>::new(&yaml)))
+            .build()?;
+        let value = reloaded.get("released").cloned().unwrap_or_default();
+        assert_eq!(Datetime::try_from(value)?, released);
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_to_file() -> anyhow::Result<()> {
+        let realme = realme()?;
+        let dir = tempfile::tempdir()?;
+        for name in ["config.toml", "config.json", "config.YML"] {
+            let path = dir.path().join(name);
+            realme.write_to_file(&path)?;
+            let ext = name.rsplit('.').next().unwrap_or_default();
+            let reloaded = match ext {
+                "toml" => {
+                    Realme::builder().load(Adaptor::new(
+                        FileSource::<TomlParser>::new(&path),
+                    ))
+                }
+                "json" => {
+                    Realme::builder().load(Adaptor::new(
+                        FileSource::<JsonParser>::new(&path),
+                    ))
+                }
+                _ => {
+                    Realme::builder().load(Adaptor::new(
+                        FileSource::<YamlParser>::new(&path),
+                    ))
+                }
+            }
+            .build()?;
+            assert_eq!(
+                reloaded.get("server.port"),
+                Some(&Value::Integer(8080))
+            );
+            assert_eq!(reloaded.get("server.debug"), Some(&Value::from(true)));
+            assert_eq!(reloaded.get("name"), Some(&Value::from("realme")));
+        }
+
+        let err = realme
+            .write_to_file(dir.path().join("config.ini"))
+            .expect_err("no emitter for .ini")
+            .to_string();
+        assert!(err.contains("can not detect the format"), "{err}");
+
+        let err = realme.write_to_file(dir.path().join("missing/config.toml"));
+        assert!(matches!(err, Err(Error::WriteFileError(_))), "{err:?}");
+        Ok(())
+    }
+}
@@ -1,3 +1,4 @@
+pub(crate) mod base64;
 pub mod map;
 #[cfg(feature = "placeholder")]
 pub(crate) mod tera;
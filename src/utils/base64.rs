@@ -0,0 +1,78 @@
+//! Standard base64 encoding with padding, used to store binary data in
+//! string values.
+
+const ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Encodes `bytes` as padded base64.
+pub(crate) fn encode(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, b)| n | u32::from(*b) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(char::from(
+                    ALPHABET[(n >> (18 - 6 * i)) as usize & 63],
+                ));
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+/// Decodes padded base64, ignoring ASCII whitespace such as line breaks.
+pub(crate) fn decode(input: &str) -> Result<Vec<u8>, String> {
+    let digits: Vec<u8> =
+        input.bytes().filter(|b| !b.is_ascii_whitespace()).collect();
+    if !digits.len().is_multiple_of(4) {
+        return Err("invalid base64 length".to_string());
+    }
+    let mut out = Vec::with_capacity(digits.len() / 4 * 3);
+    for (i, chunk) in digits.chunks(4).enumerate() {
+        let last = i + 1 == digits.len() / 4;
+        let padding = chunk.iter().rev().take_while(|b| **b == b'=').count();
+        if padding > 2 || (padding > 0 && !last) {
+            return Err("invalid base64 padding".to_string());
+        }
+        let mut n = 0u32;
+        for (j, b) in chunk[..4 - padding].iter().enumerate() {
+            let digit =
+                ALPHABET.iter().position(|a| a == b).ok_or_else(|| {
+                    format!("invalid base64 character `{}`", char::from(*b))
+                })?;
+            n |= (digit as u32) << (18 - 6 * j);
+        }
+        out.extend(n.to_be_bytes()[1..4 - padding].iter());
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() -> Result<(), String> {
+        for (bytes, encoded) in [
+            (&b""[..], ""),
+            (b"f", "Zg=="),
+            (b"fo", "Zm8="),
+            (b"foo", "Zm9v"),
+            (b"foob", "Zm9vYg=="),
+            (&[0xff, 0x00, 0xfe, 0x80], "/wD+gA=="),
+        ] {
+            assert_eq!(encode(bytes), encoded);
+            assert_eq!(decode(encoded)?, bytes);
+        }
+        assert_eq!(decode("Zm9v\nYg==")?, b"foob");
+        assert!(decode("Zm9").is_err());
+        assert!(decode("Zg==Zm8=").is_err());
+        assert!(decode("Zm9*").is_err());
+        Ok(())
+    }
+}
@@ -7,10 +7,18 @@ use std::{
     hash::Hash,
 };
 
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
 type InnerMap<K, V> = HashMap<K, V>;
 type InnerEntry<'a, K, V> = Entry<'a, K, V>;
 
-#[derive(Debug, Clone, Default, PartialEq, Eq)]
+/// A map serialized like the `HashMap` it wraps, so that it can hold the
+/// catch-all field of a `#[serde(flatten)]` struct.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
 pub struct Map<K, V>
 where
     K: Hash + Eq,
@@ -22,25 +30,29 @@ impl<K, V> Map<K, V>
 where
     K: Hash + Eq,
 {
-    pub(crate) fn new() -> Self {
+    /// Creates an empty `Map`.
+    pub fn new() -> Self {
         Self {
             inner: InnerMap::new(),
         }
     }
 
-    pub(crate) fn with_capacity(capacity: usize) -> Self {
+    /// Creates an empty `Map` with room for at least `capacity` entries.
+    pub fn with_capacity(capacity: usize) -> Self {
         Self {
             inner: InnerMap::with_capacity(capacity),
         }
     }
 
+    /// Inserts a key-value pair, returning the previous value of the key.
     #[inline]
-    pub(crate) fn insert(&mut self, k: K, v: V) -> Option<V> {
+    pub fn insert(&mut self, k: K, v: V) -> Option<V> {
         self.inner.insert(k, v)
     }
 
+    /// Returns a reference to the value of the key.
     #[inline]
-    pub(crate) fn get<Q>(&self, k: &Q) -> Option<&V>
+    pub fn get<Q>(&self, k: &Q) -> Option<&V>
     where
         K: Borrow<Q>,
         Q: Hash + Eq + ?Sized,
@@ -48,7 +60,8 @@ where
         self.inner.get(k)
     }
 
-    pub(crate) fn get_mut<Q>(&mut self, k: &Q) -> Option<&mut V>
+    /// Returns a mutable reference to the value of the key.
+    pub fn get_mut<Q>(&mut self, k: &Q) -> Option<&mut V>
     where
         K: Borrow<Q>,
         Q: Hash + Eq + ?Sized,
@@ -56,19 +69,23 @@ where
         self.inner.get_mut(k)
     }
 
-    pub(crate) fn len(&self) -> usize {
+    /// Returns the number of entries.
+    pub fn len(&self) -> usize {
         self.inner.len()
     }
 
-    pub(crate) fn is_empty(&self) -> bool {
+    /// Returns whether the map has no entries.
+    pub fn is_empty(&self) -> bool {
         self.inner.is_empty()
     }
 
-    pub(crate) fn clear(&mut self) {
+    /// Removes all entries.
+    pub fn clear(&mut self) {
         self.inner.clear();
     }
 
-    pub(crate) fn contains_key<Q>(&self, k: &Q) -> bool
+    /// Returns whether the map holds the key.
+    pub fn contains_key<Q>(&self, k: &Q) -> bool
     where
         K: Borrow<Q>,
         Q: Hash + Eq + ?Sized,
@@ -76,13 +93,47 @@ where
         self.inner.contains_key(k)
     }
 
-    pub(crate) fn entry(&mut self, k: K) -> InnerEntry<'_, K, V> {
+    /// Returns the entry of the key for in-place manipulation.
+    pub fn entry(&mut self, k: K) -> InnerEntry<'_, K, V> {
         self.inner.entry(k)
     }
 
-    pub(crate) fn iter(&self) -> <&Self as IntoIterator>::IntoIter {
+    /// Removes the key, returning its value if it was present.
+    pub fn remove<Q>(&mut self, k: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.inner.remove(k)
+    }
+
+    /// Keeps only the entries for which `f` returns `true`.
+    pub fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(&K, &mut V) -> bool,
+    {
+        self.inner.retain(f);
+    }
+
+    /// Returns an iterator over the keys.
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.inner.keys()
+    }
+
+    /// Returns an iterator over the values.
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.inner.values()
+    }
+
+    /// Returns an iterator over the entries.
+    pub fn iter(&self) -> <&Self as IntoIterator>::IntoIter {
         <&Self as IntoIterator>::into_iter(self)
     }
+
+    /// Returns an iterator over the entries with mutable values.
+    pub fn iter_mut(&mut self) -> <&mut Self as IntoIterator>::IntoIter {
+        <&mut Self as IntoIterator>::into_iter(self)
+    }
 }
 
 impl<K, V> FromIterator<(K, V)> for Map<K, V>
@@ -122,3 +173,15 @@ where
         self.inner.iter()
     }
 }
+
+impl<'a, K, V> IntoIterator for &'a mut Map<K, V>
+where
+    K: Hash + Eq,
+{
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = <&'a mut InnerMap<K, V> as IntoIterator>::IntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.inner.iter_mut()
+    }
+}
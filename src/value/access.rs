@@ -1,4 +1,20 @@
 mod expr;
 mod get;
 mod key;
+mod remove;
 mod set;
+
+pub use expr::Expression;
+
+/// Resolves an array index against an array of length `len`.
+///
+/// Negative indices count from the end, so `-1` is the last element. Returns
+/// `None` for a negative index reaching before the start of the array;
+/// non-negative indices are returned as is, even when out of range.
+const fn resolve_index(idx: isize, len: usize) -> Option<usize> {
+    if idx >= 0 {
+        Some(idx.unsigned_abs())
+    } else {
+        len.checked_sub(idx.unsigned_abs())
+    }
+}
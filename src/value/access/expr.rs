@@ -14,9 +14,32 @@ pub enum Expression {
     /// A simple identifier, e.g., `variable`.
     Identifier(String),
     /// A nested expression, e.g., `parent.child`.
-    Child(Vec<Expression>),
+    Child(Vec<Self>),
     /// An indexed access into an identifier, e.g., `array[2]`.
     Subscript(String, isize),
+    /// A wildcard matching every element of an array or every value of a
+    /// table, e.g., `servers.*` or `servers[*]`.
+    Wildcard,
+}
+
+impl Expression {
+    /// Returns an identifier, or `Expression::Wildcard` for `*`.
+    fn segment(id: String) -> Self {
+        if id == "*" {
+            Self::Wildcard
+        } else {
+            Self::Identifier(id)
+        }
+    }
+
+    /// Returns whether the expression contains a wildcard.
+    pub(crate) fn has_wildcard(&self) -> bool {
+        match self {
+            Self::Wildcard => true,
+            Self::Child(exprs) => exprs.iter().any(Self::has_wildcard),
+            Self::Identifier(_) | Self::Subscript(..) => false,
+        }
+    }
 }
 
 impl std::fmt::Display for Expression {
@@ -35,6 +58,7 @@ impl std::fmt::Display for Expression {
                 Ok(())
             }
             Self::Subscript(id, index) => write!(f, "{id}[{index}]"),
+            Self::Wildcard => f.write_str("*"),
         }
     }
 }
@@ -51,7 +75,7 @@ impl FromStr for Expression {
             match ch {
                 '.' => {
                     if !current.is_empty() {
-                        stack.push(Self::Identifier(current.clone()));
+                        stack.push(Self::segment(current.clone()));
                         current.clear();
                     }
                 }
@@ -65,6 +89,12 @@ impl FromStr for Expression {
                     let identifier = sub_stack.pop().ok_or_else(|| {
                         Error::ExprError("Unmatched ']' found".to_string())
                     })?;
+                    if current == "*" {
+                        stack.push(Self::Identifier(identifier));
+                        stack.push(Self::Wildcard);
+                        current.clear();
+                        continue;
+                    }
                     let index = current.parse::<isize>().map_err(|e| {
                         Error::ExprError(format!(
                             "Invalid number format for subscript: {e}"
@@ -78,7 +108,7 @@ impl FromStr for Expression {
 
             // Handle the case where ']' is the last character
             if i == s.len() - 1 && !current.is_empty() {
-                stack.push(Self::Identifier(current.clone()));
+                stack.push(Self::segment(current.clone()));
             }
         }
 
@@ -165,4 +195,24 @@ mod tests {
         assert_eq!(parsed, expected);
         Ok(())
     }
+
+    #[test]
+    fn test_wildcard() -> anyhow::Result<()> {
+        let expected = Expression::Child(vec![
+            Expression::Identifier("servers".into()),
+            Expression::Wildcard,
+            Expression::Identifier("host".into()),
+        ]);
+        let parsed: Expression = "servers[*].host".parse()?;
+        assert_eq!(parsed, expected);
+        let parsed: Expression = "servers.*.host".parse()?;
+        assert_eq!(parsed, expected);
+        assert!(parsed.has_wildcard());
+        assert_eq!(parsed.to_string(), "servers.*.host");
+
+        let parsed: Expression = "*".parse()?;
+        assert_eq!(parsed, Expression::Wildcard);
+        assert!(!"servers[0].host".parse::<Expression>()?.has_wildcard());
+        Ok(())
+    }
 }
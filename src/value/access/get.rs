@@ -1,6 +1,9 @@
 use serde::Deserialize;
 
-use super::expr::Expression;
+use super::{
+    expr::Expression,
+    resolve_index,
+};
 use crate::{
     Result,
     Value,
@@ -93,6 +96,10 @@ impl Key for &Expression {
 }
 
 impl Value {
+    /// Returns the value at `key`, such as `servers[0].host`.
+    ///
+    /// A key with a `*` wildcard matches any number of values and returns
+    /// `None`, see `get_all`.
     #[allow(clippy::needless_pass_by_value)]
     pub fn get<K: Key>(&self, key: K) -> Option<&Self> {
         let expr = key.to_key().ok()?;
@@ -105,21 +112,104 @@ impl Value {
         self.get_mut_internal(&expr)
     }
 
+    /// Returns every value matching `key`, in order.
+    ///
+    /// A `*` wildcard matches every element of an array, or every value of
+    /// a table in the order of their keys, so `servers[*].host` or
+    /// `servers.*.host` returns the host of every server. Matches missing
+    /// the rest of the key are skipped. A key without a wildcard returns the
+    /// value of `get`, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::collections::HashMap;
+    ///
+    /// use realme::prelude::*;
+    ///
+    /// let servers = vec![
+    ///     HashMap::from([("host", "a")]),
+    ///     HashMap::from([("host", "b")]),
+    /// ];
+    /// let value = Value::try_serialize(&HashMap::from([("servers", servers)]))
+    ///     .expect("serialize servers");
+    /// assert_eq!(value.get_all("servers[*].host"), vec![
+    ///     &Value::from("a"),
+    ///     &Value::from("b")
+    /// ]);
+    /// ```
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn get_all<K: Key>(&self, key: K) -> Vec<&Self> {
+        let Ok(expr) = key.to_key() else {
+            return Vec::new();
+        };
+        let mut matches = Vec::new();
+        let exprs = match &expr {
+            Expression::Child(exprs) => exprs.as_slice(),
+            expr => std::slice::from_ref(expr),
+        };
+        self.collect_matches(exprs, &mut matches);
+        matches
+    }
+
+    /// Returns the value at `key` deserialized into `T`.
+    ///
+    /// A key with a `*` wildcard deserializes the array of the values
+    /// matching it, see `get_all`.
     #[allow(clippy::needless_pass_by_value)]
     pub fn get_as<'de, K: Key, T: Deserialize<'de>>(
         &'de self,
         key: K,
     ) -> Option<T> {
         let expr = key.to_key().ok()?;
+        if expr.has_wildcard() {
+            let matches = self.get_all(&expr).into_iter().cloned().collect();
+            return Self::Array(matches).try_deserialize().ok();
+        }
         self.get_internal(&expr)
             .and_then(|v| v.clone().try_deserialize().ok())
     }
 
+    /// Pushes the values matching the path `exprs` to `matches`.
+    fn collect_matches<'a>(
+        &'a self,
+        exprs: &[Expression],
+        matches: &mut Vec<&'a Self>,
+    ) {
+        let Some((first, rest)) = exprs.split_first() else {
+            matches.push(self);
+            return;
+        };
+        if *first != Expression::Wildcard {
+            if let Some(child) = self.get_internal(first) {
+                child.collect_matches(rest, matches);
+            }
+            return;
+        }
+        match self {
+            Self::Array(arr) => {
+                for child in arr {
+                    child.collect_matches(rest, matches);
+                }
+            }
+            Self::Table(table) => {
+                let mut children: Vec<_> = table.iter().collect();
+                children.sort_by_key(|(key, _)| *key);
+                for (_, child) in children {
+                    child.collect_matches(rest, matches);
+                }
+            }
+            _ => {}
+        }
+    }
+
     fn get_internal<'a>(&'a self, key: &Expression) -> Option<&'a Self> {
         match key {
             Expression::Identifier(id) => match self {
                 Self::Table(table) => table.get(id).or(None),
-                Self::Array(arr) => arr.get(id.parse::<usize>().ok()?),
+                Self::Array(arr) => {
+                    arr.get(resolve_index(id.parse().ok()?, arr.len())?)
+                }
                 _ => None,
             },
             Expression::Subscript(id, idx) => {
@@ -127,12 +217,7 @@ impl Value {
                     self.get_internal(&Expression::Identifier(id.clone()))?;
                 match v {
                     Self::Array(arr) => {
-                        let index = if *idx >= 0 {
-                            *idx as usize
-                        } else {
-                            arr.len().wrapping_add(*idx as usize)
-                        };
-                        arr.get(index).or(None)
+                        arr.get(resolve_index(*idx, arr.len())?)
                     }
                     _ => None,
                 }
@@ -140,6 +225,7 @@ impl Value {
             Expression::Child(exprs) => exprs
                 .iter()
                 .try_fold(self, |acc, expr| acc.get_internal(expr)),
+            Expression::Wildcard => None,
         }
     }
 
@@ -149,7 +235,10 @@ impl Value {
                 Self::Table(table) => {
                     Some(table.entry(id.clone()).or_insert_with(|| Self::Null))
                 }
-                Self::Array(arr) => arr.get_mut(id.parse::<usize>().ok()?),
+                Self::Array(arr) => {
+                    let index = resolve_index(id.parse().ok()?, arr.len())?;
+                    arr.get_mut(index)
+                }
                 _ => None,
             },
             Expression::Subscript(id, idx) => {
@@ -158,11 +247,7 @@ impl Value {
                         .entry(id.clone())
                         .or_insert_with(|| Self::Array(Vec::new()));
                     if let Self::Array(arr) = entry {
-                        let index = if *idx >= 0 {
-                            *idx as usize
-                        } else {
-                            arr.len().wrapping_add(*idx as usize)
-                        };
+                        let index = resolve_index(*idx, arr.len())?;
                         // Ensure the array has enough capacity
                         if index >= arr.len() {
                             arr.resize(index + 1, Self::Null);
@@ -179,6 +264,7 @@ impl Value {
                 }
                 Some(current)
             }
+            Expression::Wildcard => None,
         }
     }
 }
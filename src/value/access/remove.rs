@@ -0,0 +1,86 @@
+use super::{
+    expr::Expression,
+    key::Key,
+    resolve_index,
+};
+use crate::Value;
+
+impl Value {
+    /// Removes the value at `key`, returning it if it was present.
+    ///
+    /// `key` accepts the syntax of `get`, such as `server.hosts[-1]`.
+    /// Removing an array element shifts the following elements, while
+    /// intermediate tables left empty are kept. Nothing is created along the
+    /// path: `None` is returned when a part of it is missing.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn remove<K: Key>(&mut self, key: K) -> Option<Self> {
+        let expr = key.to_key().ok()?;
+        let (parents, last) = match &expr {
+            Expression::Child(exprs) => {
+                let (last, parents) = exprs.split_last()?;
+                (parents, last)
+            }
+            expr => (&[][..], expr),
+        };
+        let mut current = self;
+        for parent in parents {
+            current = current.child_mut(parent)?;
+        }
+        current.remove_child(last)
+    }
+
+    /// Returns the value at `key` like `get_mut`, but without inserting
+    /// anything when a part of the path is missing.
+    #[allow(clippy::needless_pass_by_value)]
+    pub(crate) fn find_mut<K: Key>(&mut self, key: K) -> Option<&mut Self> {
+        let expr = key.to_key().ok()?;
+        match &expr {
+            Expression::Child(exprs) => exprs
+                .iter()
+                .try_fold(self, |current, expr| current.child_mut(expr)),
+            expr => self.child_mut(expr),
+        }
+    }
+
+    /// Returns the child at `key` without inserting anything.
+    fn child_mut(&mut self, key: &Expression) -> Option<&mut Self> {
+        match (self, key) {
+            (Self::Table(table), Expression::Identifier(id)) => {
+                table.get_mut(id)
+            }
+            (Self::Array(arr), Expression::Identifier(id)) => {
+                let index = resolve_index(id.parse().ok()?, arr.len())?;
+                arr.get_mut(index)
+            }
+            (Self::Table(table), Expression::Subscript(id, idx)) => {
+                let Self::Array(arr) = table.get_mut(id)? else {
+                    return None;
+                };
+                let index = resolve_index(*idx, arr.len())?;
+                arr.get_mut(index)
+            }
+            _ => None,
+        }
+    }
+
+    /// Removes the child at `key`, the last part of a path.
+    fn remove_child(&mut self, key: &Expression) -> Option<Self> {
+        let (arr, idx) = match (self, key) {
+            (Self::Table(table), Expression::Identifier(id)) => {
+                return table.remove(id);
+            }
+            (Self::Array(arr), Expression::Identifier(id)) => {
+                (arr, id.parse().ok()?)
+            }
+            (Self::Table(table), Expression::Subscript(id, idx)) => {
+                let Self::Array(arr) = table.get_mut(id)? else {
+                    return None;
+                };
+                (arr, *idx)
+            }
+            _ => return None,
+        };
+        let index = resolve_index(idx, arr.len())?;
+        (index < arr.len()).then(|| arr.remove(index))
+    }
+}
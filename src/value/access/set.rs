@@ -1,6 +1,7 @@
 use super::{
     expr::Expression,
     key::Key,
+    resolve_index,
 };
 use crate::{
     Error,
@@ -15,6 +16,9 @@ impl Value {
         let expr = key.to_key()?;
 
         match expr {
+            expr if expr.has_wildcard() => Err(Error::SetValueError(format!(
+                "can not set `{expr}`: wildcards only match existing values"
+            ))),
             Expression::Identifier(id) => {
                 match self {
                     Self::Table(table) => {
@@ -24,13 +28,7 @@ impl Value {
                         let idx = id
                             .parse::<isize>()
                             .map_err(|e| Error::SetValueError(e.to_string()))?;
-                        let idx = idx.rem_euclid(arr.len() as isize) as usize;
-                        // if idx >= arr.len() {
-                        //     return Err(Error::SetValueError(format!("Index {}
-                        // out of bounds for array of length {}", idx,
-                        // arr.len()))); }
-                        arr.resize(idx.max(arr.len()), Self::Null);
-                        arr[idx] = value;
+                        set_index(arr, idx, value)?;
                     }
                     _ => {
                         *self =
@@ -39,62 +37,39 @@ impl Value {
                 }
                 Ok(self)
             }
-            Expression::Subscript(id, idx) => {
-                match self {
-                    Self::Table(table) => {
-                        let arr = if let Some(existing) = table.get_mut(&id) {
-                            existing
-                        } else {
-                            table.insert(id.clone(), Self::Array(Vec::new()));
-                            table.get_mut(&id).expect("Failed to get mut table")
-                        };
+            Expression::Subscript(id, idx) => match self {
+                Self::Table(table) => {
+                    let arr = if let Some(existing) = table.get_mut(&id) {
+                        existing
+                    } else {
+                        table.insert(id.clone(), Self::Array(Vec::new()));
+                        table.get_mut(&id).expect("Failed to get mut table")
+                    };
 
-                        if let Self::Array(arr) = arr {
-                            let idx =
-                                idx.rem_euclid(arr.len() as isize) as usize;
-                            // if idx >= arr.len() {
-                            //     return
-                            // Err(Error::SetValueError(format!("Index {} out of
-                            // bounds for array of length {}", idx,
-                            // arr.len()))); }
-                            arr.resize(idx.max(arr.len()), Self::Null);
-                            arr[idx] = value;
-                            Ok(self)
-                        } else {
-                            *arr = Self::Array(vec![value]);
-                            Ok(self)
-                        }
+                    if let Self::Array(arr) = arr {
+                        set_index(arr, idx, value)?;
+                        Ok(self)
+                    } else {
+                        *arr = Self::Array(vec![value]);
+                        Ok(self)
                     }
-                    _ => Err(Error::SetValueError(format!(
-                        "Expected a table, got {}",
-                        self.value_type()
-                    ))),
                 }
-            }
+                _ => Err(Error::SetValueError(format!(
+                    "Expected a table, got {}",
+                    self.value_type()
+                ))),
+            },
             Expression::Child(exprs) => {
                 let mut current = self;
                 for (i, e) in exprs.iter().enumerate() {
                     if i == exprs.len() - 1 {
                         return current.set(e.clone(), value);
                     }
-                    current = if let Self::Table(table) = current {
-                        if !table.contains_key(&e.to_string()) {
-                            table
-                                .insert(e.to_string(), Self::Table(Map::new()));
-                        }
-                        table
-                            .get_mut(&e.to_string())
-                            .expect("Failed to get mut table")
-                    } else {
-                        let mut new_table = Map::new();
-                        new_table
-                            .insert(e.to_string(), Self::Table(Map::new()));
-                        *current = Self::Table(new_table);
-                        current.get_mut(e).expect("Failed to get mut table")
-                    };
+                    current = descend(current, e)?;
                 }
                 Ok(current)
             }
+            Expression::Wildcard => unreachable!("wildcards are rejected"),
         }
     }
 
@@ -123,3 +98,80 @@ impl Value {
     //     self
     // }
 }
+
+/// Returns the child of `current` at the intermediate segment `expr` of a
+/// path being set, creating it when missing.
+///
+/// A value that is not a table, or not an array for a subscript, is replaced
+/// so that the rest of the path can be set. Arrays grow with `Value::Null`
+/// like `set_index`.
+fn descend<'a>(
+    current: &'a mut Value,
+    expr: &Expression,
+) -> Result<&'a mut Value> {
+    match expr {
+        Expression::Identifier(id) => {
+            match (&*current, id.parse::<isize>()) {
+                (Value::Array(_), Ok(idx)) => {
+                    let Value::Array(arr) = current else {
+                        unreachable!("matched an array")
+                    };
+                    return grow_to(arr, idx);
+                }
+                (Value::Table(_), _) => {}
+                _ => *current = Value::Table(Map::new()),
+            }
+            let Value::Table(table) = current else {
+                unreachable!("replaced with a table")
+            };
+            Ok(table
+                .entry(id.clone())
+                .or_insert_with(|| Value::Table(Map::new())))
+        }
+        Expression::Subscript(id, idx) => {
+            if !matches!(current, Value::Table(_)) {
+                *current = Value::Table(Map::new());
+            }
+            let Value::Table(table) = current else {
+                unreachable!("replaced with a table")
+            };
+            let entry = table
+                .entry(id.clone())
+                .or_insert_with(|| Value::Array(Vec::new()));
+            if !matches!(entry, Value::Array(_)) {
+                *entry = Value::Array(Vec::new());
+            }
+            let Value::Array(arr) = entry else {
+                unreachable!("replaced with an array")
+            };
+            grow_to(arr, *idx)
+        }
+        Expression::Child(_) | Expression::Wildcard => Err(
+            Error::SetValueError(format!("can not set through `{expr}`")),
+        ),
+    }
+}
+
+/// Returns the element at `idx`, growing the array with `Value::Null` when
+/// a non-negative index is past its end.
+fn grow_to(arr: &mut Vec<Value>, idx: isize) -> Result<&mut Value> {
+    let index = resolve_index(idx, arr.len()).ok_or_else(|| {
+        Error::SetValueError(format!(
+            "Index {idx} out of bounds for array of length {}",
+            arr.len()
+        ))
+    })?;
+    if index >= arr.len() {
+        arr.resize(index + 1, Value::Null);
+    }
+    Ok(&mut arr[index])
+}
+
+/// Stores `value` at `idx`, counting negative indices from the end.
+///
+/// The array grows with `Value::Null` when a non-negative index is past its
+/// end, while a negative index before its start is an error.
+fn set_index(arr: &mut Vec<Value>, idx: isize, value: Value) -> Result<()> {
+    *grow_to(arr, idx)? = value;
+    Ok(())
+}
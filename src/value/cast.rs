@@ -20,6 +20,7 @@ impl TryFrom<Value> for String {
             Value::Integer(i) => Ok(i.to_string()),
             Value::Float(f) => Ok(f.to_string()),
             Value::String(s) => Ok(s),
+            Value::Datetime(d) => Ok(d.to_string()),
             Value::Array(a) => Err(Error::new_cast_error(
                 format!("{a:?}"),
                 "Cannot cast array to string".to_string(),
@@ -42,6 +43,7 @@ impl TryFrom<&Value> for String {
             Value::Integer(i) => Ok(i.to_string()),
             Value::Float(f) => Ok(f.to_string()),
             Value::String(s) => Ok(s.clone()),
+            Value::Datetime(d) => Ok(d.to_string()),
             Value::Array(a) => Err(Error::new_cast_error(
                 format!("{a:?}"),
                 "Cannot cast array to string".to_string(),
@@ -79,6 +81,10 @@ macro_rules! impl_try_from_value_for_integer {
                             .to_string(),
                         )
                     }),
+                    Value::Datetime(d) => Err(Error::new_cast_error(
+                        d.to_string(),
+                        stringify!($type).to_string(),
+                    )),
                     Value::Array(_) => Err(Error::new_cast_error(
                         "array".to_string(),
                         stringify!($type).to_string(),
@@ -120,6 +126,10 @@ macro_rules! impl_try_from_value_for_float {
                             .to_string(),
                         )
                     }),
+                    Value::Datetime(d) => Err(Error::new_cast_error(
+                        d.to_string(),
+                        stringify!($type).to_string(),
+                    )),
                     Value::Array(_) => Err(Error::new_cast_error(
                         "array".to_string(),
                         stringify!($type).to_string(),
@@ -158,6 +168,10 @@ macro_rules! impl_try_from_value_for_uinteger {
                             .to_string(),
                         )
                     }),
+                    Value::Datetime(d) => Err(Error::new_cast_error(
+                        d.to_string(),
+                        stringify!($type).to_string(),
+                    )),
                     Value::Array(_) => Err(Error::new_cast_error(
                         "array".to_string(),
                         stringify!($type).to_string(),
@@ -192,6 +206,10 @@ impl TryFrom<Value> for bool {
                     "Cannot cast string to bool".to_string(),
                 )),
             },
+            Value::Datetime(d) => Err(Error::new_cast_error(
+                d.to_string(),
+                "Cannot cast datetime to bool".to_string(),
+            )),
             Value::Array(a) => Err(Error::new_cast_error(
                 format!("{a:?}"),
                 "Cannot cast array to bool".to_string(),
@@ -221,6 +239,10 @@ impl TryFrom<&Value> for bool {
                     "Cannot cast string to bool".to_string(),
                 )),
             },
+            Value::Datetime(d) => Err(Error::new_cast_error(
+                d.to_string(),
+                "Cannot cast datetime to bool".to_string(),
+            )),
             Value::Array(a) => Err(Error::new_cast_error(
                 format!("{a:?}"),
                 "Cannot cast array to bool".to_string(),
@@ -246,6 +268,7 @@ impl TryFrom<Value> for Array {
             Value::Integer(i) => Ok(vec![Value::Integer(i)]),
             Value::Float(f) => Ok(vec![Value::Float(f)]),
             Value::String(s) => Ok(vec![Value::String(s)]),
+            Value::Datetime(d) => Ok(vec![Value::Datetime(d)]),
             Value::Array(a) => Ok(a),
             Value::Table(t) => t.try_into(),
         }
@@ -262,6 +285,7 @@ impl TryFrom<&Value> for Array {
             Value::Integer(i) => Ok(vec![Value::Integer(*i)]),
             Value::Float(f) => Ok(vec![Value::Float(*f)]),
             Value::String(s) => Ok(vec![Value::String(s.clone())]),
+            Value::Datetime(d) => Ok(vec![Value::Datetime(d.clone())]),
             Value::Array(a) => Ok(a.clone()),
             Value::Table(t) => t.clone().try_into(),
         }
@@ -344,6 +368,7 @@ impl<T: TryFrom<Value, Error = Error>> TryFrom<Value> for Vec<T> {
             Value::Integer(i) => Ok(vec![T::try_from(Value::Integer(i))?]),
             Value::Float(f) => Ok(vec![T::try_from(Value::Float(f))?]),
             Value::String(s) => Ok(vec![T::try_from(Value::String(s))?]),
+            Value::Datetime(d) => Ok(vec![T::try_from(Value::Datetime(d))?]),
             Value::Array(a) => a.into_iter().map(T::try_from).collect(),
             Value::Table(t) => {
                 t.into_iter().map(|(_, v)| T::try_from(v)).collect()
@@ -364,6 +389,9 @@ impl<T: TryFrom<Value, Error = Error>> TryFrom<&Value> for Vec<T> {
             Value::String(s) => {
                 Ok(vec![T::try_from(Value::String(s.clone()))?])
             }
+            Value::Datetime(d) => {
+                Ok(vec![T::try_from(Value::Datetime(d.clone()))?])
+            }
             Value::Array(a) => {
                 a.iter().map(|v| T::try_from(v.clone())).collect()
             }
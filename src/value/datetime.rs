@@ -0,0 +1,524 @@
+use std::{
+    fmt,
+    str::FromStr,
+};
+
+use super::Value;
+use crate::Error;
+
+/// Name of the newtype struct a `Value::Datetime` serializes as, so the
+/// `ValueSerializer` can tell it from a plain string.
+pub(crate) const DATETIME_TOKEN: &str = "$__realme_private_datetime";
+
+/// Key of the single field TOML datetimes serialize and deserialize as.
+#[cfg(feature = "toml")]
+pub(crate) const TOML_DATETIME_KEY: &str = "$__toml_private_datetime";
+
+/// A date, a time or both, as found in TOML documents.
+///
+/// A datetime holds a date `1979-05-27`, a time `07:32:00`, or both with an
+/// optional offset `1979-05-27T07:32:00-08:00`, following RFC 3339. It is
+/// kept as its text, with `T` between the date and the time and `Z` for
+/// UTC, and deserializes from it: any type reading a string, such as the
+/// `chrono` types, can be read from a `Value::Datetime`.
+///
+/// # Examples
+///
+/// ```rust
+/// use realme::value::Datetime;
+///
+/// let datetime: Datetime =
+///     "1979-05-27 07:32:00z".parse().expect("parse datetime");
+/// assert_eq!(datetime.as_str(), "1979-05-27T07:32:00Z");
+/// assert!(
+///     datetime.has_date() && datetime.has_time() && datetime.has_offset()
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Datetime {
+    text:   String,
+    date:   bool,
+    time:   bool,
+    offset: bool,
+}
+
+impl Datetime {
+    /// Returns the datetime as RFC 3339 text.
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns whether the datetime has a date.
+    pub const fn has_date(&self) -> bool {
+        self.date
+    }
+
+    /// Returns whether the datetime has a time.
+    pub const fn has_time(&self) -> bool {
+        self.time
+    }
+
+    /// Returns whether the datetime has an offset, `Z` included.
+    pub const fn has_offset(&self) -> bool {
+        self.offset
+    }
+}
+
+impl fmt::Display for Datetime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+impl FromStr for Datetime {
+    type Err = Error;
+
+    /// Parses a local date, a local time, a local datetime or an offset
+    /// datetime. The date and the time may be separated by `T`, `t` or a
+    /// space, and the offset is `Z`, `z` or `+HH:MM`/`-HH:MM`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse(s).ok_or_else(|| {
+            Error::new_cast_error(
+                s.to_string(),
+                "Cannot parse string as datetime".to_string(),
+            )
+        })
+    }
+}
+
+/// Parses `s` as a datetime, normalizing its separators.
+fn parse(s: &str) -> Option<Datetime> {
+    if !s.is_ascii() {
+        return None;
+    }
+    let mut text = String::with_capacity(s.len());
+    let (date, rest) = match s.get(..10) {
+        Some(date) if s.as_bytes().get(4) == Some(&b'-') => {
+            check_date(date)?;
+            text.push_str(date);
+            (true, &s[10..])
+        }
+        _ => (false, s),
+    };
+    let rest = if date {
+        match rest.as_bytes().first() {
+            None => {
+                return Some(Datetime {
+                    text,
+                    date,
+                    time: false,
+                    offset: false,
+                });
+            }
+            Some(b'T' | b't' | b' ') => {
+                text.push('T');
+                &rest[1..]
+            }
+            Some(_) => return None,
+        }
+    } else {
+        rest
+    };
+    let time_len = time_len(rest)?;
+    text.push_str(&rest[..time_len]);
+    let offset = &rest[time_len..];
+    match offset {
+        "" => {}
+        "Z" | "z" if date => text.push('Z'),
+        _ if date && check_offset(offset) => text.push_str(offset),
+        _ => return None,
+    }
+    Some(Datetime {
+        text,
+        date,
+        time: true,
+        offset: !offset.is_empty(),
+    })
+}
+
+/// Checks a `YYYY-MM-DD` date.
+fn check_date(date: &str) -> Option<()> {
+    let b = date.as_bytes();
+    (b[4] == b'-' && b[7] == b'-').then_some(())?;
+    number(&date[..4])?;
+    let month = number(&date[5..7])?;
+    let day = number(&date[8..10])?;
+    ((1..=12).contains(&month) && (1..=31).contains(&day)).then_some(())
+}
+
+/// Returns the length of the `HH:MM:SS[.fraction]` time starting `s`.
+fn time_len(s: &str) -> Option<usize> {
+    let b = s.as_bytes();
+    if b.len() < 8 || b[2] != b':' || b[5] != b':' {
+        return None;
+    }
+    let hour = number(&s[..2])?;
+    let minute = number(&s[3..5])?;
+    let second = number(&s[6..8])?;
+    if hour > 23 || minute > 59 || second > 60 {
+        return None;
+    }
+    if b.get(8) != Some(&b'.') {
+        return Some(8);
+    }
+    let digits = b[9..].iter().take_while(|b| b.is_ascii_digit()).count();
+    (digits > 0).then_some(9 + digits)
+}
+
+/// Checks a `+HH:MM` or `-HH:MM` offset.
+fn check_offset(offset: &str) -> bool {
+    let b = offset.as_bytes();
+    b.len() == 6 &&
+        matches!(b[0], b'+' | b'-') &&
+        b[3] == b':' &&
+        number(&offset[1..3]).is_some_and(|hour| hour < 24) &&
+        number(&offset[4..6]).is_some_and(|minute| minute < 60)
+}
+
+/// Parses a fixed width decimal number made of ASCII digits only.
+fn number(s: &str) -> Option<u32> {
+    s.bytes()
+        .all(|b| b.is_ascii_digit())
+        .then(|| s.parse().ok())
+        .flatten()
+}
+
+impl From<Datetime> for Value {
+    fn from(value: Datetime) -> Self {
+        Self::Datetime(value)
+    }
+}
+
+/// Attempts to convert a `Value` into a `Datetime`, parsing strings.
+impl TryFrom<Value> for Datetime {
+    type Error = Error;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Datetime(datetime) => Ok(datetime),
+            Value::String(s) => s.parse(),
+            value => Err(Error::new_cast_error(
+                value.to_string(),
+                format!("Cannot cast {} to datetime", value.value_type()),
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "chrono")]
+mod chrono_impls {
+    use chrono::{
+        DateTime,
+        FixedOffset,
+        NaiveDate,
+        NaiveDateTime,
+        NaiveTime,
+        SecondsFormat,
+        TimeZone,
+    };
+
+    use super::Datetime;
+    use crate::{
+        Error,
+        Value,
+    };
+
+    /// Converts a value holding a datetime, or a string parsed as one, with
+    /// `convert`, naming `target` in errors.
+    fn convert<T>(
+        value: Value,
+        target: &str,
+        convert: impl FnOnce(&Datetime) -> Option<T>,
+    ) -> Result<T, Error> {
+        let datetime = Datetime::try_from(value)?;
+        convert(&datetime).ok_or_else(|| {
+            Error::new_cast_error(
+                datetime.to_string(),
+                format!("Cannot cast datetime to {target}"),
+            )
+        })
+    }
+
+    impl TryFrom<Value> for DateTime<FixedOffset> {
+        type Error = Error;
+
+        /// Converts an offset datetime.
+        fn try_from(value: Value) -> Result<Self, Self::Error> {
+            convert(value, "DateTime<FixedOffset>", |datetime| {
+                Self::parse_from_rfc3339(datetime.as_str()).ok()
+            })
+        }
+    }
+
+    impl TryFrom<Value> for NaiveDateTime {
+        type Error = Error;
+
+        /// Converts a local datetime.
+        fn try_from(value: Value) -> Result<Self, Self::Error> {
+            convert(value, "NaiveDateTime", |datetime| {
+                (!datetime.has_offset())
+                    .then(|| {
+                        Self::parse_from_str(
+                            datetime.as_str(),
+                            "%Y-%m-%dT%H:%M:%S%.f",
+                        )
+                        .ok()
+                    })
+                    .flatten()
+            })
+        }
+    }
+
+    impl TryFrom<Value> for NaiveDate {
+        type Error = Error;
+
+        /// Converts a local date.
+        fn try_from(value: Value) -> Result<Self, Self::Error> {
+            convert(value, "NaiveDate", |datetime| {
+                Self::parse_from_str(datetime.as_str(), "%Y-%m-%d").ok()
+            })
+        }
+    }
+
+    impl TryFrom<Value> for NaiveTime {
+        type Error = Error;
+
+        /// Converts a local time.
+        fn try_from(value: Value) -> Result<Self, Self::Error> {
+            convert(value, "NaiveTime", |datetime| {
+                Self::parse_from_str(datetime.as_str(), "%H:%M:%S%.f").ok()
+            })
+        }
+    }
+
+    impl<Tz: TimeZone> From<DateTime<Tz>> for Value
+    where
+        Tz::Offset: std::fmt::Display,
+    {
+        fn from(value: DateTime<Tz>) -> Self {
+            datetime(&value.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+        }
+    }
+
+    impl From<NaiveDateTime> for Value {
+        fn from(value: NaiveDateTime) -> Self {
+            datetime(&value.format("%Y-%m-%dT%H:%M:%S%.f").to_string())
+        }
+    }
+
+    impl From<NaiveDate> for Value {
+        fn from(value: NaiveDate) -> Self {
+            datetime(&value.format("%Y-%m-%d").to_string())
+        }
+    }
+
+    impl From<NaiveTime> for Value {
+        fn from(value: NaiveTime) -> Self {
+            datetime(&value.format("%H:%M:%S%.f").to_string())
+        }
+    }
+
+    /// Builds a `Value::Datetime` from text chrono formatted, which always
+    /// parses.
+    fn datetime(text: &str) -> Value {
+        text.parse::<Datetime>()
+            .map_or_else(|_| Value::String(text.to_string()), Value::Datetime)
+    }
+}
+
+#[cfg(feature = "time")]
+mod time_impls {
+    use time::{
+        Date,
+        OffsetDateTime,
+        PrimitiveDateTime,
+        Time,
+        format_description::{
+            BorrowedFormatItem,
+            well_known::Rfc3339,
+        },
+        macros::format_description,
+    };
+
+    use super::Datetime;
+    use crate::{
+        Error,
+        Value,
+    };
+
+    const DATE: &[BorrowedFormatItem<'_>] =
+        format_description!("[year]-[month]-[day]");
+    const TIME: &[BorrowedFormatItem<'_>] = format_description!(
+        "[hour]:[minute]:[second][optional [.[subsecond]]]"
+    );
+    const PRIMITIVE: &[BorrowedFormatItem<'_>] = format_description!(
+        "[year]-[month]-[day]T[hour]:[minute]:[second][optional \
+         [.[subsecond]]]"
+    );
+
+    /// Converts a value holding a datetime, or a string parsed as one, with
+    /// `convert`, naming `target` in errors.
+    fn convert<T>(
+        value: Value,
+        target: &str,
+        convert: impl FnOnce(&Datetime) -> Option<T>,
+    ) -> Result<T, Error> {
+        let datetime = Datetime::try_from(value)?;
+        convert(&datetime).ok_or_else(|| {
+            Error::new_cast_error(
+                datetime.to_string(),
+                format!("Cannot cast datetime to {target}"),
+            )
+        })
+    }
+
+    impl TryFrom<Value> for OffsetDateTime {
+        type Error = Error;
+
+        /// Converts an offset datetime.
+        fn try_from(value: Value) -> Result<Self, Self::Error> {
+            convert(value, "OffsetDateTime", |datetime| {
+                Self::parse(datetime.as_str(), &Rfc3339).ok()
+            })
+        }
+    }
+
+    impl TryFrom<Value> for PrimitiveDateTime {
+        type Error = Error;
+
+        /// Converts a local datetime.
+        fn try_from(value: Value) -> Result<Self, Self::Error> {
+            convert(value, "PrimitiveDateTime", |datetime| {
+                Self::parse(datetime.as_str(), PRIMITIVE).ok()
+            })
+        }
+    }
+
+    impl TryFrom<Value> for Date {
+        type Error = Error;
+
+        /// Converts a local date.
+        fn try_from(value: Value) -> Result<Self, Self::Error> {
+            convert(value, "Date", |datetime| {
+                Self::parse(datetime.as_str(), DATE).ok()
+            })
+        }
+    }
+
+    impl TryFrom<Value> for Time {
+        type Error = Error;
+
+        /// Converts a local time.
+        fn try_from(value: Value) -> Result<Self, Self::Error> {
+            convert(value, "Time", |datetime| {
+                Self::parse(datetime.as_str(), TIME).ok()
+            })
+        }
+    }
+
+    impl From<OffsetDateTime> for Value {
+        fn from(value: OffsetDateTime) -> Self {
+            datetime(value.format(&Rfc3339).ok())
+        }
+    }
+
+    impl From<PrimitiveDateTime> for Value {
+        fn from(value: PrimitiveDateTime) -> Self {
+            datetime(value.format(PRIMITIVE).ok())
+        }
+    }
+
+    impl From<Date> for Value {
+        fn from(value: Date) -> Self {
+            datetime(value.format(DATE).ok())
+        }
+    }
+
+    impl From<Time> for Value {
+        fn from(value: Time) -> Self {
+            datetime(value.format(TIME).ok())
+        }
+    }
+
+    /// Builds a `Value::Datetime` from text `time` formatted, or
+    /// `Value::Null` when formatting failed.
+    fn datetime(text: Option<String>) -> Value {
+        text.map_or(Value::Null, |text| {
+            text.parse::<Datetime>()
+                .map_or(Value::String(text), Value::Datetime)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_datetime() -> anyhow::Result<()> {
+        for (input, expected, parts) in [
+            ("1979-05-27", "1979-05-27", (true, false, false)),
+            ("07:32:00", "07:32:00", (false, true, false)),
+            ("07:32:00.999", "07:32:00.999", (false, true, false)),
+            (
+                "1979-05-27 07:32:00",
+                "1979-05-27T07:32:00",
+                (true, true, false),
+            ),
+            (
+                "1979-05-27t07:32:00z",
+                "1979-05-27T07:32:00Z",
+                (true, true, true),
+            ),
+            (
+                "1979-05-27T00:32:00.5-07:00",
+                "1979-05-27T00:32:00.5-07:00",
+                (true, true, true),
+            ),
+        ] {
+            let datetime: Datetime = input.parse()?;
+            assert_eq!(datetime.as_str(), expected);
+            assert_eq!(
+                (
+                    datetime.has_date(),
+                    datetime.has_time(),
+                    datetime.has_offset()
+                ),
+                parts
+            );
+        }
+        for input in [
+            "",
+            "1979-13-27",
+            "1979-05-27T",
+            "1979-05-27T25:00:00",
+            "07:32",
+            "07:32:00Z",
+            "1979-05-27T07:32:00+8",
+            "1979-05-27T07:32:00.",
+            "tomorrow",
+        ] {
+            assert!(input.parse::<Datetime>().is_err(), "{input}");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_datetime_value() -> anyhow::Result<()> {
+        let value = Value::Datetime("1979-05-27T07:32:00Z".parse()?);
+        assert_eq!(Value::try_serialize(&value)?, value);
+        assert_eq!(value.to_string(), "1979-05-27T07:32:00Z");
+        assert_eq!(String::try_from(value.clone())?, "1979-05-27T07:32:00Z");
+        assert_eq!(
+            Datetime::try_from(Value::from("1979-05-27"))?.as_str(),
+            "1979-05-27"
+        );
+        assert!(Datetime::try_from(Value::Integer(1)).is_err());
+        #[cfg(feature = "json")]
+        assert_eq!(
+            value.to_json(crate::JsonFormat::Compact)?,
+            "\"1979-05-27T07:32:00Z\""
+        );
+        Ok(())
+    }
+}
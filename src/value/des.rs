@@ -10,11 +10,16 @@ use serde::{
     },
 };
 
+#[cfg(feature = "toml")]
+use super::datetime::TOML_DATETIME_KEY;
 use super::{
     Table,
     Value,
 };
-use crate::utils::Map;
+use crate::{
+    errors::DeserializeError,
+    utils::Map,
+};
 
 /// Represents a custom deserializer for `Value` type.
 impl<'de> Deserialize<'de> for Value {
@@ -72,6 +77,14 @@ impl<'de> Visitor<'de> for ValueVisitor {
         Ok(Value::String(value))
     }
 
+    fn visit_none<E>(self) -> Result<Value, E> {
+        Ok(Value::Null)
+    }
+
+    fn visit_unit<E>(self) -> Result<Value, E> {
+        Ok(Value::Null)
+    }
+
     fn visit_some<D>(self, deserializer: D) -> Result<Value, D::Error>
     where
         D: de::Deserializer<'de>,
@@ -96,7 +109,17 @@ impl<'de> Visitor<'de> for ValueVisitor {
     {
         let mut map = Map::new();
 
-        while let Some((key, value)) = access.next_entry()? {
+        while let Some(key) = access.next_key::<String>()? {
+            // TOML datetimes are maps holding their text under this key
+            #[cfg(feature = "toml")]
+            if key == TOML_DATETIME_KEY {
+                let text: String = access.next_value()?;
+                return text
+                    .parse()
+                    .map(Value::Datetime)
+                    .map_err(de::Error::custom);
+            }
+            let value = access.next_value()?;
             map.insert(key, value);
         }
 
@@ -104,26 +127,180 @@ impl<'de> Visitor<'de> for ValueVisitor {
     }
 }
 
+/// Options controlling how a `Value` is deserialized.
+#[derive(Debug, Clone, Copy, Default)]
+struct Options {
+    /// Reject integer/float coercion between numeric types.
+    strict_numbers: bool,
+}
+
+/// A deserializer for `Value` with configurable coercion rules.
+///
+/// `Value` itself implements `Deserializer` with the default, lenient rules.
+/// Use this type to opt in to stricter behavior.
+///
+/// # Examples
+///
+/// ```rust
+/// use realme::{
+///     Value,
+///     value::ValueDeserializer,
+/// };
+/// use serde::Deserialize;
+///
+/// let lenient = i64::deserialize(Value::Float(3.0));
+/// assert_eq!(lenient.ok(), Some(3));
+///
+/// let strict = i64::deserialize(
+///     ValueDeserializer::new(Value::Float(3.0)).strict_numbers(),
+/// );
+/// assert!(strict.is_err());
+/// ```
+#[derive(Debug, Clone)]
+pub struct ValueDeserializer {
+    value:   Value,
+    options: Options,
+}
+
+impl ValueDeserializer {
+    /// Constructs a new `ValueDeserializer` with the default, lenient rules.
+    pub fn new(value: Value) -> Self {
+        Self {
+            value,
+            options: Options::default(),
+        }
+    }
+
+    /// Makes numbers deserialize only into targets of their own kind.
+    ///
+    /// A `Value::Float` is then rejected for integer targets, even when it
+    /// has no fractional part, and a `Value::Integer` is rejected for float
+    /// targets.
+    #[must_use]
+    pub const fn strict_numbers(mut self) -> Self {
+        self.options.strict_numbers = true;
+        self
+    }
+
+    #[must_use]
+    pub(crate) const fn with_strict_numbers(mut self, strict: bool) -> Self {
+        self.options.strict_numbers = strict;
+        self
+    }
+
+    const fn child(value: Value, options: Options) -> Self {
+        Self { value, options }
+    }
+
+    fn check_integer(&self) -> Result<(), DeserializeError> {
+        match self.value {
+            Value::Float(f) if self.options.strict_numbers => {
+                Err(de::Error::custom(format!(
+                    "expected an integer, found float {f} (strict numbers)"
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn check_float(&self) -> Result<(), DeserializeError> {
+        match self.value {
+            Value::Integer(i) if self.options.strict_numbers => {
+                Err(de::Error::custom(format!(
+                    "expected a float, found integer {i} (strict numbers)"
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Forwards the `Deserializer` methods of `Value` to `ValueDeserializer`.
+macro_rules! forward_to_value_deserializer {
+    ($($method:ident($($arg:ident: $ty:ty),*))*) => {
+        $(
+            fn $method<V>(
+                self,
+                $($arg: $ty,)*
+                visitor: V,
+            ) -> Result<V::Value, Self::Error>
+            where
+                V: Visitor<'de>,
+            {
+                ValueDeserializer::new(self).$method($($arg,)* visitor)
+            }
+        )*
+    };
+}
+
 /// Custom deserializer implementation for `Value`.
 impl<'de> serde::Deserializer<'de> for Value {
+    type Error = DeserializeError;
+
+    forward_to_value_deserializer! {
+        deserialize_any()
+        deserialize_bool()
+        deserialize_i8()
+        deserialize_i16()
+        deserialize_i32()
+        deserialize_i64()
+        deserialize_u8()
+        deserialize_u16()
+        deserialize_u32()
+        deserialize_u64()
+        deserialize_f32()
+        deserialize_f64()
+        deserialize_char()
+        deserialize_str()
+        deserialize_string()
+        deserialize_bytes()
+        deserialize_byte_buf()
+        deserialize_option()
+        deserialize_unit()
+        deserialize_unit_struct(name: &'static str)
+        deserialize_newtype_struct(name: &'static str)
+        deserialize_seq()
+        deserialize_tuple(len: usize)
+        deserialize_tuple_struct(name: &'static str, len: usize)
+        deserialize_map()
+        deserialize_struct(
+            name: &'static str,
+            fields: &'static [&'static str]
+        )
+        deserialize_enum(
+            name: &'static str,
+            variants: &'static [&'static str]
+        )
+        deserialize_identifier()
+        deserialize_ignored_any()
+    }
+}
+
+/// Deserializer implementation for `ValueDeserializer`.
+impl<'de> serde::Deserializer<'de> for ValueDeserializer {
     type Error = crate::errors::DeserializeError;
     fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
         #[allow(unreachable_patterns)]
-        match self {
-            Self::Null => visitor.visit_none(),
-            Self::String(s) => visitor.visit_str(&s),
-            Self::Integer(i) => visitor.visit_i64(i),
-            Self::Boolean(b) => visitor.visit_bool(b),
-            Self::Float(f) => visitor.visit_f64(f),
-            Self::Array(a) => visitor.visit_seq(SeqDeserializer::new(a)),
-            Self::Table(t) => visitor.visit_map(MapDeserializer::new(t)),
+        match self.value {
+            Value::Null => visitor.visit_none(),
+            Value::String(s) => visitor.visit_str(&s),
+            Value::Datetime(d) => visitor.visit_str(d.as_str()),
+            Value::Integer(i) => visitor.visit_i64(i),
+            Value::Boolean(b) => visitor.visit_bool(b),
+            Value::Float(f) => visitor.visit_f64(f),
+            Value::Array(a) => {
+                visitor.visit_seq(SeqDeserializer::new(a, self.options))
+            }
+            Value::Table(t) => {
+                visitor.visit_map(MapDeserializer::new(t, self.options))
+            }
             _ => Err(de::Error::custom(format!(
                 "unsupported type for any: {}, value: {:?}",
-                self.value_type(),
-                self
+                self.value.value_type(),
+                self.value
             ))),
         }
     }
@@ -133,6 +310,7 @@ impl<'de> serde::Deserializer<'de> for Value {
         V: Visitor<'de>,
     {
         let s: String = self
+            .value
             .try_into()
             .map_err(|e: crate::Error| de::Error::custom(e.to_string()))?;
         visitor.visit_str(&s)
@@ -142,7 +320,9 @@ impl<'de> serde::Deserializer<'de> for Value {
     where
         V: Visitor<'de>,
     {
+        self.check_integer()?;
         let i: i64 = self
+            .value
             .try_into()
             .map_err(|e: crate::Error| de::Error::custom(e.to_string()))?;
         visitor.visit_i64(i)
@@ -153,6 +333,7 @@ impl<'de> serde::Deserializer<'de> for Value {
         V: Visitor<'de>,
     {
         let i: bool = self
+            .value
             .try_into()
             .map_err(|e: crate::Error| de::Error::custom(e.to_string()))?;
         visitor.visit_bool(i)
@@ -162,7 +343,9 @@ impl<'de> serde::Deserializer<'de> for Value {
     where
         V: Visitor<'de>,
     {
+        self.check_integer()?;
         let i: i64 = self
+            .value
             .try_into()
             .map_err(|e: crate::Error| de::Error::custom(e.to_string()))?;
         visitor.visit_i8(i as i8)
@@ -172,7 +355,9 @@ impl<'de> serde::Deserializer<'de> for Value {
     where
         V: Visitor<'de>,
     {
+        self.check_integer()?;
         let i: i64 = self
+            .value
             .try_into()
             .map_err(|e: crate::Error| de::Error::custom(e.to_string()))?;
         visitor.visit_i16(i as i16)
@@ -182,7 +367,9 @@ impl<'de> serde::Deserializer<'de> for Value {
     where
         V: Visitor<'de>,
     {
+        self.check_integer()?;
         let i: i64 = self
+            .value
             .try_into()
             .map_err(|e: crate::Error| de::Error::custom(e.to_string()))?;
         visitor.visit_i32(i as i32)
@@ -192,7 +379,9 @@ impl<'de> serde::Deserializer<'de> for Value {
     where
         V: Visitor<'de>,
     {
+        self.check_integer()?;
         let u: u64 = self
+            .value
             .try_into()
             .map_err(|e: crate::Error| de::Error::custom(e.to_string()))?;
         visitor.visit_u8(u as u8)
@@ -202,7 +391,9 @@ impl<'de> serde::Deserializer<'de> for Value {
     where
         V: Visitor<'de>,
     {
+        self.check_integer()?;
         let u: u64 = self
+            .value
             .try_into()
             .map_err(|e: crate::Error| de::Error::custom(e.to_string()))?;
         visitor.visit_u16(u as u16)
@@ -212,7 +403,9 @@ impl<'de> serde::Deserializer<'de> for Value {
     where
         V: Visitor<'de>,
     {
+        self.check_integer()?;
         let u: u64 = self
+            .value
             .try_into()
             .map_err(|e: crate::Error| de::Error::custom(e.to_string()))?;
         visitor.visit_u32(u as u32)
@@ -222,7 +415,9 @@ impl<'de> serde::Deserializer<'de> for Value {
     where
         V: Visitor<'de>,
     {
+        self.check_integer()?;
         let u: u64 = self
+            .value
             .try_into()
             .map_err(|e: crate::Error| de::Error::custom(e.to_string()))?;
         visitor.visit_u64(u)
@@ -232,7 +427,9 @@ impl<'de> serde::Deserializer<'de> for Value {
     where
         V: Visitor<'de>,
     {
+        self.check_float()?;
         let f: f64 = self
+            .value
             .try_into()
             .map_err(|e: crate::Error| de::Error::custom(e.to_string()))?;
         visitor.visit_f32(f as f32)
@@ -242,7 +439,9 @@ impl<'de> serde::Deserializer<'de> for Value {
     where
         V: Visitor<'de>,
     {
+        self.check_float()?;
         let f: f64 = self
+            .value
             .try_into()
             .map_err(|e: crate::Error| de::Error::custom(e.to_string()))?;
         visitor.visit_f64(f)
@@ -253,6 +452,7 @@ impl<'de> serde::Deserializer<'de> for Value {
         V: Visitor<'de>,
     {
         let s: String = self
+            .value
             .try_into()
             .map_err(|e: crate::Error| de::Error::custom(e.to_string()))?;
         visitor.visit_str(&s)
@@ -263,6 +463,7 @@ impl<'de> serde::Deserializer<'de> for Value {
         V: Visitor<'de>,
     {
         let s: String = self
+            .value
             .try_into()
             .map_err(|e: crate::Error| de::Error::custom(e.to_string()))?;
         visitor.visit_str(&s)
@@ -272,12 +473,10 @@ impl<'de> serde::Deserializer<'de> for Value {
     where
         V: Visitor<'de>,
     {
-        let s: String = self
-            .try_into()
-            .map_err(|e: crate::Error| de::Error::custom(e.to_string()))?;
-        visitor.visit_str(&s)
+        self.deserialize_byte_buf(visitor)
     }
 
+    /// Reads bytes from a base64 string, see `Value::as_bytes_base64`.
     fn deserialize_byte_buf<V>(
         self,
         visitor: V,
@@ -285,17 +484,18 @@ impl<'de> serde::Deserializer<'de> for Value {
     where
         V: Visitor<'de>,
     {
-        let s: String = self
-            .try_into()
-            .map_err(|e: crate::Error| de::Error::custom(e.to_string()))?;
-        visitor.visit_str(&s)
+        let bytes = self
+            .value
+            .as_bytes_base64()
+            .map_err(|e| de::Error::custom(e.to_string()))?;
+        visitor.visit_byte_buf(bytes)
     }
 
     fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        if self == Self::Null {
+        if self.value == Value::Null {
             visitor.visit_none()
         } else {
             visitor.visit_some(self)
@@ -317,7 +517,7 @@ impl<'de> serde::Deserializer<'de> for Value {
     where
         V: Visitor<'de>,
     {
-        visitor.visit_unit()
+        self.deserialize_unit(visitor)
     }
 
     fn deserialize_newtype_struct<V>(
@@ -336,9 +536,10 @@ impl<'de> serde::Deserializer<'de> for Value {
         V: Visitor<'de>,
     {
         let seq = self
+            .value
             .try_into()
             .map_err(|e: crate::Error| de::Error::custom(e.to_string()))?;
-        visitor.visit_seq(SeqDeserializer::new(seq))
+        visitor.visit_seq(SeqDeserializer::new(seq, self.options))
     }
 
     fn deserialize_tuple<V>(
@@ -350,37 +551,50 @@ impl<'de> serde::Deserializer<'de> for Value {
         V: Visitor<'de>,
     {
         let seq = self
+            .value
             .try_into()
             .map_err(|e: crate::Error| de::Error::custom(e.to_string()))?;
-        visitor.visit_seq(SeqDeserializer::new(seq))
+        visitor.visit_seq(SeqDeserializer::new(seq, self.options))
     }
 
     fn deserialize_tuple_struct<V>(
         self,
-        _name: &'static str,
-        _len: usize,
-        _visitor: V,
+        name: &'static str,
+        len: usize,
+        visitor: V,
     ) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        Err(de::Error::custom(format!(
-            "unsupported type for tuple struct: {}, value: {:?}",
-            self.value_type(),
-            self
-        )))
+        match self.value {
+            Value::Array(arr) if arr.len() == len => {
+                visitor.visit_seq(SeqDeserializer::new(arr, self.options))
+            }
+            Value::Array(arr) => Err(de::Error::custom(format!(
+                "invalid length for tuple struct {name}: expected {len} \
+                 elements, found {}",
+                arr.len()
+            ))),
+            _ => Err(de::Error::custom(format!(
+                "unsupported type for tuple struct {name}: {}, value: {:?}",
+                self.value.value_type(),
+                self.value
+            ))),
+        }
     }
 
     fn deserialize_map<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        match self {
-            Self::Table(t) => visitor.visit_map(MapDeserializer::new(t)),
+        match self.value {
+            Value::Table(t) => {
+                visitor.visit_map(MapDeserializer::new(t, self.options))
+            }
             _ => Err(de::Error::custom(format!(
                 "expected a table, got {}, value: {:?}",
-                self.value_type(),
-                self
+                self.value.value_type(),
+                self.value
             ))),
         }
     }
@@ -394,12 +608,20 @@ impl<'de> serde::Deserializer<'de> for Value {
     where
         V: Visitor<'de>,
     {
-        match self {
-            Self::Table(t) => visitor.visit_map(MapDeserializer::new(t)),
+        match self.value {
+            Value::Table(t) => {
+                visitor.visit_map(MapDeserializer::new(t, self.options))
+            }
+            // A null struct has no fields, which suits empty structs and
+            // structs whose fields all have defaults.
+            Value::Null => visitor.visit_map(MapDeserializer::new(
+                Table::default(),
+                self.options,
+            )),
             _ => Err(de::Error::custom(format!(
                 "expected a table, got {}, value: {:?}",
-                self.value_type(),
-                self
+                self.value.value_type(),
+                self.value
             ))),
         }
     }
@@ -413,18 +635,18 @@ impl<'de> serde::Deserializer<'de> for Value {
     where
         V: Visitor<'de>,
     {
-        match self {
-            Self::String(s) => {
+        match self.value {
+            Value::String(s) => {
                 visitor.visit_enum(EnumDeserializer::new(EnumVariant::Unit(s)))
             }
-            Self::Table(_) => Err(de::Error::custom(
+            Value::Table(_) => Err(de::Error::custom(
                 "enum with tuple or struct variant is not supported"
                     .to_string(),
             )),
             _ => Err(de::Error::custom(format!(
                 "expected a string, got {}, value: {:?}",
-                self.value_type(),
-                self
+                self.value.value_type(),
+                self.value
             ))),
         }
     }
@@ -436,12 +658,12 @@ impl<'de> serde::Deserializer<'de> for Value {
     where
         V: Visitor<'de>,
     {
-        match self {
-            Self::String(s) => visitor.visit_str(&s),
+        match self.value {
+            Value::String(s) => visitor.visit_str(&s),
             _ => Err(de::Error::custom(format!(
                 "expected a string, got {}, value: {:?}",
-                self.value_type(),
-                self
+                self.value.value_type(),
+                self.value
             ))),
         }
     }
@@ -453,29 +675,36 @@ impl<'de> serde::Deserializer<'de> for Value {
     where
         V: Visitor<'de>,
     {
-        match self {
-            Self::Null => visitor.visit_none(),
-            Self::Boolean(b) => visitor.visit_bool(b),
-            Self::Integer(i) => visitor.visit_i64(i),
-            Self::Float(f) => visitor.visit_f64(f),
-            Self::String(s) => visitor.visit_str(&s),
-            Self::Array(a) => visitor.visit_seq(SeqDeserializer::new(a)),
-            Self::Table(t) => visitor.visit_map(MapDeserializer::new(t)),
+        match self.value {
+            Value::Null => visitor.visit_none(),
+            Value::Boolean(b) => visitor.visit_bool(b),
+            Value::Integer(i) => visitor.visit_i64(i),
+            Value::Float(f) => visitor.visit_f64(f),
+            Value::String(s) => visitor.visit_str(&s),
+            Value::Datetime(d) => visitor.visit_str(d.as_str()),
+            Value::Array(a) => {
+                visitor.visit_seq(SeqDeserializer::new(a, self.options))
+            }
+            Value::Table(t) => {
+                visitor.visit_map(MapDeserializer::new(t, self.options))
+            }
         }
     }
 }
 
 /// A helper struct to facilitate map deserialization.
 struct MapDeserializer {
-    iter:  <Map<String, Value> as IntoIterator>::IntoIter,
-    value: Option<(String, Value)>,
+    iter:    <Map<String, Value> as IntoIterator>::IntoIter,
+    value:   Option<(String, Value)>,
+    options: Options,
 }
 
 impl MapDeserializer {
-    fn new(map: Map<String, Value>) -> Self {
+    fn new(map: Map<String, Value>, options: Options) -> Self {
         Self {
-            iter:  map.into_iter(),
+            iter: map.into_iter(),
             value: None,
+            options,
         }
     }
 }
@@ -504,7 +733,10 @@ impl<'de> de::MapAccess<'de> for MapDeserializer {
         T: de::DeserializeSeed<'de>,
     {
         let (_, res) = match self.value.take() {
-            Some((key, value)) => (key, seed.deserialize(value)),
+            Some((key, value)) => (
+                key,
+                seed.deserialize(ValueDeserializer::child(value, self.options)),
+            ),
             None => return Err(de::Error::custom("value is missing")),
         };
         res.map_err(de::Error::custom)
@@ -520,7 +752,10 @@ impl<'de> de::MapAccess<'de> for MapDeserializer {
 
 /// A helper struct to facilitate sequence deserialization.
 struct SeqDeserializer {
-    iter: <Vec<Value> as IntoIterator>::IntoIter,
+    iter:    <Vec<Value> as IntoIterator>::IntoIter,
+    /// Index of the next element, used in error messages.
+    index:   usize,
+    options: Options,
 }
 
 impl<'de> de::SeqAccess<'de> for SeqDeserializer {
@@ -534,7 +769,19 @@ impl<'de> de::SeqAccess<'de> for SeqDeserializer {
         T: de::DeserializeSeed<'de>,
     {
         match self.iter.next() {
-            Some(value) => seed.deserialize(value).map(Some),
+            Some(value) => {
+                let index = self.index;
+                let value_type = value.value_type();
+                self.index += 1;
+                seed.deserialize(ValueDeserializer::child(value, self.options))
+                    .map(Some)
+                    .map_err(|e| {
+                        de::Error::custom(format!(
+                            "invalid element at index {index} ({value_type}): \
+                             {e}"
+                        ))
+                    })
+            }
             None => Ok(None),
         }
     }
@@ -548,9 +795,11 @@ impl<'de> de::SeqAccess<'de> for SeqDeserializer {
 }
 
 impl SeqDeserializer {
-    fn new(seq: Vec<Value>) -> Self {
+    fn new(seq: Vec<Value>, options: Options) -> Self {
         Self {
             iter: seq.into_iter(),
+            index: 0,
+            options,
         }
     }
 }
@@ -782,6 +1031,240 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_deserialize_strict_numbers() -> anyhow::Result<()> {
+        let lenient: i64 = Value::Float(3.0).try_deserialize()?;
+        assert_eq!(lenient, 3);
+
+        let strict = i64::deserialize(
+            ValueDeserializer::new(Value::Float(3.0)).strict_numbers(),
+        );
+        assert!(strict.is_err());
+        let strict = f64::deserialize(
+            ValueDeserializer::new(Value::Integer(3)).strict_numbers(),
+        );
+        assert!(strict.is_err());
+
+        let nested = Value::Array(vec![Value::Integer(1), Value::Float(2.0)]);
+        let strict = Vec::<i64>::deserialize(
+            ValueDeserializer::new(nested.clone()).strict_numbers(),
+        );
+        assert!(strict.is_err());
+        let lenient: Vec<i64> = nested.try_deserialize()?;
+        assert_eq!(lenient, vec![1, 2]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_deserialize_heterogeneous_array() {
+        let value = Value::Array(vec![
+            Value::Integer(1),
+            Value::String("two".to_string()),
+            Value::Integer(3),
+        ]);
+        let result: Result<Vec<i64>, Error> = value.try_deserialize();
+        let err = result.expect_err("string element");
+        assert!(
+            err.to_string()
+                .contains("invalid element at index 1 (string)")
+        );
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Point(i64, i64);
+
+    #[test]
+    fn test_deserialize_tuple_struct() -> anyhow::Result<()> {
+        let value = Value::Array(vec![Value::Integer(1), Value::Integer(2)]);
+        let result: Point = value.try_deserialize()?;
+        assert_eq!(result, Point(1, 2));
+        Ok(())
+    }
+
+    #[test]
+    fn test_deserialize_tuple_struct_wrong_length() {
+        let value = Value::Array(vec![Value::Integer(1)]);
+        let result: Result<Point, Error> = value.try_deserialize();
+        let err = result.expect_err("too short array");
+        assert!(
+            err.to_string()
+                .contains("tuple struct Point: expected 2 elements, found 1")
+        );
+
+        let value = Value::Integer(1);
+        let result: Result<Point, Error> = value.try_deserialize();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_deserialize_rename_all_camel_case() -> anyhow::Result<()> {
+        #[derive(Debug, Deserialize, PartialEq)]
+        #[serde(rename_all = "camelCase")]
+        struct Server {
+            host_name:       String,
+            max_connections: u32,
+            tls_settings:    Tls,
+        }
+
+        #[derive(Debug, Deserialize, PartialEq)]
+        #[serde(rename_all = "camelCase")]
+        struct Tls {
+            cert_path: String,
+        }
+
+        let value = Value::from_dotted([
+            ("hostName", Value::from("localhost")),
+            ("maxConnections", Value::from(64)),
+            ("tlsSettings.certPath", Value::from("/etc/tls/cert.pem")),
+        ])?;
+        let server: Server = value.clone().try_deserialize()?;
+        assert_eq!(server, Server {
+            host_name:       "localhost".to_string(),
+            max_connections: 64,
+            tls_settings:    Tls {
+                cert_path: "/etc/tls/cert.pem".to_string(),
+            },
+        });
+
+        let snake = Value::from_dotted([
+            ("host_name", Value::from("localhost")),
+            ("max_connections", Value::from(64)),
+            ("tls_settings.cert_path", Value::from("/etc/tls/cert.pem")),
+        ])?;
+        assert!(snake.try_deserialize::<Server>().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_deserialize_newtype_wrappers() -> anyhow::Result<()> {
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Port(u16);
+
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Url(String);
+
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Server {
+            port: Port,
+            url:  Url,
+        }
+
+        assert_eq!(Value::Integer(8080).try_deserialize::<Port>()?, Port(8080));
+        assert_eq!(
+            Value::from("https://example.com").try_deserialize::<Url>()?,
+            Url("https://example.com".to_string())
+        );
+
+        let server = Value::from_dotted([
+            ("port", Value::from(443)),
+            ("url", Value::from("https://example.com")),
+        ])?;
+        assert_eq!(server.try_deserialize::<Server>()?, Server {
+            port: Port(443),
+            url:  Url("https://example.com".to_string()),
+        });
+        Ok(())
+    }
+
+    #[test]
+    fn test_serde_with_round_trip() -> anyhow::Result<()> {
+        use std::time::Duration;
+
+        use serde::Serialize;
+
+        /// A duration written as whole seconds, either as an integer or as
+        /// a string such as `"30s"`.
+        mod seconds {
+            use std::time::Duration;
+
+            use serde::{
+                Deserializer,
+                Serializer,
+                de,
+            };
+
+            pub fn serialize<S: Serializer>(
+                duration: &Duration,
+                serializer: S,
+            ) -> Result<S::Ok, S::Error> {
+                serializer.serialize_u64(duration.as_secs())
+            }
+
+            pub fn deserialize<'de, D: Deserializer<'de>>(
+                deserializer: D,
+            ) -> Result<Duration, D::Error> {
+                struct Seconds;
+
+                impl de::Visitor<'_> for Seconds {
+                    type Value = Duration;
+
+                    fn expecting(
+                        &self,
+                        f: &mut std::fmt::Formatter<'_>,
+                    ) -> std::fmt::Result {
+                        f.write_str("a number of seconds")
+                    }
+
+                    fn visit_u64<E: de::Error>(
+                        self,
+                        v: u64,
+                    ) -> Result<Duration, E> {
+                        Ok(Duration::from_secs(v))
+                    }
+
+                    fn visit_i64<E: de::Error>(
+                        self,
+                        v: i64,
+                    ) -> Result<Duration, E> {
+                        u64::try_from(v)
+                            .map(Duration::from_secs)
+                            .map_err(E::custom)
+                    }
+
+                    fn visit_str<E: de::Error>(
+                        self,
+                        v: &str,
+                    ) -> Result<Duration, E> {
+                        v.trim_end_matches('s')
+                            .parse()
+                            .map(Duration::from_secs)
+                            .map_err(E::custom)
+                    }
+                }
+
+                deserializer.deserialize_any(Seconds)
+            }
+        }
+
+        #[derive(Debug, Serialize, Deserialize, PartialEq)]
+        struct Client {
+            #[serde(with = "seconds")]
+            timeout: Duration,
+            #[serde(with = "seconds", default = "Duration::default")]
+            idle:    Duration,
+        }
+
+        let client = Client {
+            timeout: Duration::from_secs(30),
+            idle:    Duration::from_secs(90),
+        };
+        let value = Value::try_serialize(&client)?;
+        assert_eq!(value.get("timeout"), Some(&Value::Integer(30)));
+        assert_eq!(value.try_deserialize::<Client>()?, client);
+
+        let value = Value::from_dotted([("timeout", "45s")])?;
+        assert_eq!(value.try_deserialize::<Client>()?, Client {
+            timeout: Duration::from_secs(45),
+            idle:    Duration::ZERO,
+        });
+        assert!(
+            Value::from_dotted([("timeout", -1)])?
+                .try_deserialize::<Client>()
+                .is_err()
+        );
+        Ok(())
+    }
+
     #[cfg(test)]
     mod enum_deserialization_tests {
         use super::*;
@@ -832,4 +1315,73 @@ mod tests {
             assert!(result.is_err());
         }
     }
+
+    #[test]
+    fn test_deserialize_null_into_unit_types() -> anyhow::Result<()> {
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Empty;
+
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Braced {}
+
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Config {
+            marker: Empty,
+            port:   Option<i64>,
+        }
+
+        Value::Null.try_deserialize::<()>()?;
+        assert_eq!(Value::Null.try_deserialize::<Empty>()?, Empty);
+        assert_eq!(Value::Null.try_deserialize::<Braced>()?, Braced {});
+        assert_eq!(Value::Null.try_deserialize::<Option<i64>>()?, None);
+
+        let config = Value::from_dotted([("marker", Value::Null)])?;
+        assert_eq!(config.try_deserialize::<Config>()?, Config {
+            marker: Empty,
+            port:   None,
+        });
+
+        // Other values are discarded, as they always were.
+        Value::from(1).try_deserialize::<()>()?;
+        assert_eq!(Value::from("x").try_deserialize::<Empty>()?, Empty);
+        Ok(())
+    }
+
+    #[test]
+    fn test_flatten_catch_all() -> anyhow::Result<()> {
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Server {
+            host:  String,
+            port:  u16,
+            #[serde(flatten)]
+            extra: Map<String, Value>,
+        }
+
+        let value = Value::from_dotted([
+            ("host", Value::from("localhost")),
+            ("port", Value::from(8080)),
+            ("timeout", Value::from(30)),
+            ("tls.enabled", Value::from(true)),
+            ("tags", Value::from(vec!["a", "b"])),
+            ("note", Value::Null),
+        ])?;
+        let server: Server = value.try_deserialize()?;
+        assert_eq!(server.host, "localhost");
+        assert_eq!(server.port, 8080);
+
+        let mut keys: Vec<_> = server.extra.keys().cloned().collect();
+        keys.sort();
+        assert_eq!(keys, ["note", "tags", "timeout", "tls"]);
+        assert_eq!(server.extra.get("timeout"), Some(&Value::Integer(30)));
+        assert_eq!(
+            server.extra.get("tags"),
+            Some(&Value::from(vec!["a", "b"]))
+        );
+        assert_eq!(
+            server.extra.get("tls").and_then(|tls| tls.get("enabled")),
+            Some(&Value::Boolean(true))
+        );
+        assert_eq!(server.extra.get("note"), Some(&Value::Null));
+        Ok(())
+    }
 }
@@ -0,0 +1,145 @@
+use super::Value;
+use crate::{
+    Error,
+    Map,
+    Result,
+};
+
+impl Value {
+    /// Builds a nested `Value::Table` from `(dotted path, value)` pairs.
+    ///
+    /// Each path is split on `.` and intermediate tables are created as
+    /// needed. When the same path appears twice the later value wins.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when two paths conflict, that is when one path is a
+    /// strict prefix of another, such as `a` and `a.b`: a key can not hold a
+    /// value and a nested table at the same time.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use realme::Value;
+    ///
+    /// let value =
+    ///     Value::from_dotted([("a.b", 1), ("a.c", 2)]).expect("build value");
+    /// assert_eq!(value.get("a.b"), Some(&Value::Integer(1)));
+    /// assert_eq!(value.get("a.c"), Some(&Value::Integer(2)));
+    ///
+    /// assert!(Value::from_dotted([("a", 1), ("a.b", 2)]).is_err());
+    /// ```
+    pub fn from_dotted<I, K, V>(iter: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: Into<Self>,
+    {
+        let mut map = Map::new();
+        for (path, value) in iter {
+            let path = path.as_ref();
+            let parts: Vec<&str> = path.split('.').collect();
+            check_conflict(&map, &parts, path)?;
+            insert_nested(&mut map, &parts, value.into());
+        }
+        Ok(Self::Table(map))
+    }
+}
+
+/// Inserts `value` at the path made of `parts`, creating intermediate tables.
+///
+/// A value already present at the path is replaced, while the value is
+/// dropped when an intermediate key holds something other than a table.
+pub(crate) fn insert_nested(
+    map: &mut Map<String, Value>,
+    parts: &[&str],
+    value: Value,
+) {
+    match parts {
+        [head] => {
+            map.insert((*head).to_string(), value);
+        }
+        [head, tail @ ..] => {
+            let entry = map
+                .entry((*head).to_string())
+                .or_insert_with(|| Value::Table(Map::new()));
+            if let Value::Table(nested_map) = entry {
+                insert_nested(nested_map, tail, value);
+            }
+        }
+        [] => {}
+    }
+}
+
+/// Checks that inserting at `parts` does not conflict with a shorter or
+/// longer path already present in `map`.
+fn check_conflict(
+    map: &Map<String, Value>,
+    parts: &[&str],
+    path: &str,
+) -> Result<()> {
+    let mut current = map;
+    for (i, part) in parts.iter().enumerate() {
+        let is_leaf = i + 1 == parts.len();
+        match current.get(*part) {
+            Some(Value::Table(nested)) if !is_leaf => current = nested,
+            Some(Value::Table(_)) => {
+                return Err(Error::new_build_error(format!(
+                    "path `{path}` conflicts with a longer path under it"
+                )));
+            }
+            Some(_) if !is_leaf => {
+                return Err(Error::new_build_error(format!(
+                    "path `{path}` conflicts with the value at `{}`",
+                    parts[..=i].join(".")
+                )));
+            }
+            None | Some(_) => return Ok(()),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_dotted_two_levels() -> anyhow::Result<()> {
+        let value = Value::from_dotted([
+            ("a.b", Value::Integer(1)),
+            ("a.c", Value::Integer(2)),
+            ("d", Value::String("top".to_string())),
+        ])?;
+        let expected = Value::Table(Map::from_iter([
+            (
+                "a".to_string(),
+                Value::Table(Map::from_iter([
+                    ("b".to_string(), Value::Integer(1)),
+                    ("c".to_string(), Value::Integer(2)),
+                ])),
+            ),
+            ("d".to_string(), Value::String("top".to_string())),
+        ]));
+        assert_eq!(value, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_dotted_same_path_overrides() -> anyhow::Result<()> {
+        let value = Value::from_dotted([("a.b", 1), ("a.b", 2)])?;
+        assert_eq!(value.get("a.b"), Some(&Value::Integer(2)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_dotted_prefix_conflict() {
+        let err = Value::from_dotted([("a", 1), ("a.b", 2)])
+            .expect_err("value then nested path");
+        assert!(err.to_string().contains("conflicts with the value at `a`"));
+
+        let err = Value::from_dotted([("a.b.c", 1), ("a.b", 2)])
+            .expect_err("nested path then value");
+        assert!(err.to_string().contains("path `a.b` conflicts"));
+    }
+}
@@ -0,0 +1,138 @@
+use std::hash::Hasher;
+
+use super::Value;
+
+/// 64-bit FNV-1a hasher, stable across platforms and releases unlike
+/// `std::collections::hash_map::DefaultHasher`.
+pub(crate) struct Fnv(u64);
+
+impl Default for Fnv {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for Fnv {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = (self.0 ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
+
+impl Value {
+    /// Returns a hash of the content of `self`, for detecting changes and
+    /// keying caches.
+    ///
+    /// Tables are hashed with their keys sorted, so equal trees hash the
+    /// same whatever the iteration order of their maps. The hash is stable
+    /// across runs and platforms, and `0.0` and `-0.0`, which compare
+    /// equal, hash the same.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use realme::Value;
+    ///
+    /// let a = Value::from_dotted([("host", "localhost"), ("port", "80")])
+    ///     .expect("build value");
+    /// let b = Value::from_dotted([("port", "80"), ("host", "localhost")])
+    ///     .expect("build value");
+    /// assert_eq!(a.content_hash(), b.content_hash());
+    /// ```
+    #[must_use]
+    pub fn content_hash(&self) -> u64 {
+        let mut hasher = Fnv::default();
+        hash_value(self, &mut hasher);
+        hasher.finish()
+    }
+}
+
+/// Feeds a canonical encoding of `value` to `hasher`: a tag byte per kind,
+/// little-endian numbers and length-prefixed strings and containers.
+fn hash_value(value: &Value, hasher: &mut Fnv) {
+    match value {
+        Value::Null => hasher.write(&[0]),
+        Value::Boolean(b) => hasher.write(&[1, u8::from(*b)]),
+        Value::Integer(i) => {
+            hasher.write(&[2]);
+            hasher.write(&i.to_le_bytes());
+        }
+        Value::Float(f) => {
+            let f = if f.is_nan() { f64::NAN } else { f + 0.0 };
+            hasher.write(&[3]);
+            hasher.write(&f.to_bits().to_le_bytes());
+        }
+        Value::String(s) => {
+            hasher.write(&[4]);
+            hash_str(s, hasher);
+        }
+        Value::Datetime(d) => {
+            hasher.write(&[7]);
+            hash_str(d.as_str(), hasher);
+        }
+        Value::Array(array) => {
+            hasher.write(&[5]);
+            hasher.write(&(array.len() as u64).to_le_bytes());
+            for value in array {
+                hash_value(value, hasher);
+            }
+        }
+        Value::Table(table) => {
+            hasher.write(&[6]);
+            hasher.write(&(table.len() as u64).to_le_bytes());
+            let mut entries: Vec<_> = table.iter().collect();
+            entries.sort_unstable_by_key(|(key, _)| *key);
+            for (key, value) in entries {
+                hash_str(key, hasher);
+                hash_value(value, hasher);
+            }
+        }
+    }
+}
+
+fn hash_str(s: &str, hasher: &mut Fnv) {
+    hasher.write(&(s.len() as u64).to_le_bytes());
+    hasher.write(s.as_bytes());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Map;
+
+    #[test]
+    fn test_content_hash() {
+        let mut a = Map::new();
+        let mut b = Map::new();
+        for i in 0..32 {
+            a.insert(format!("key{i}"), Value::from(i));
+        }
+        for i in (0..32).rev() {
+            b.insert(format!("key{i}"), Value::from(i));
+        }
+        let mut a = Value::Table(a);
+        let b = Value::Table(b);
+        assert_eq!(a.content_hash(), b.content_hash());
+
+        a.set("key7", Value::from(8)).expect("set key7");
+        assert_ne!(a.content_hash(), b.content_hash());
+
+        assert_eq!(
+            Value::Float(0.0).content_hash(),
+            Value::Float(-0.0).content_hash()
+        );
+        assert_ne!(
+            Value::from("1").content_hash(),
+            Value::from(1).content_hash()
+        );
+        assert_ne!(
+            Value::from(vec!["ab", "c"]).content_hash(),
+            Value::from(vec!["a", "bc"]).content_hash()
+        );
+    }
+}
@@ -0,0 +1,106 @@
+use serde::ser::Error as _;
+
+use super::Value;
+use crate::{
+    Result,
+    errors::SerializeError,
+};
+
+/// Layout of the JSON produced by `Value::to_json`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum JsonFormat {
+    /// Indented over several lines.
+    Pretty,
+    /// On a single line without whitespace.
+    #[default]
+    Compact,
+}
+
+impl Value {
+    /// Serializes the value to a JSON string.
+    ///
+    /// Table keys are sorted, so the output is stable across runs.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::SerializeError` naming the key of the first NaN or
+    /// infinite float found, since JSON can not represent them and would
+    /// otherwise turn them into `null` silently.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use realme::prelude::*;
+    ///
+    /// let value =
+    ///     Value::from_dotted([("server.port", 8080)]).expect("build value");
+    /// assert_eq!(
+    ///     value.to_json(JsonFormat::Compact).expect("serialize value"),
+    ///     r#"{"server":{"port":8080}}"#
+    /// );
+    /// ```
+    pub fn to_json(&self, format: JsonFormat) -> Result<String> {
+        if let Some((path, ..)) = self.leaves().find(
+            |(_, _, leaf)| matches!(leaf, Self::Float(f) if !f.is_finite()),
+        ) {
+            return Err(SerializeError::custom(format!(
+                "`{path}` is not a finite number and can not be written as \
+                 JSON"
+            ))
+            .into());
+        }
+        let json =
+            serde_json::to_value(self).map_err(SerializeError::custom)?;
+        match format {
+            JsonFormat::Pretty => serde_json::to_string_pretty(&json),
+            JsonFormat::Compact => serde_json::to_string(&json),
+        }
+        .map_err(|e| SerializeError::custom(e).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_json() -> anyhow::Result<()> {
+        let value = Value::from_dotted([
+            ("name", Value::from("realme")),
+            ("server.port", Value::from(8080)),
+            ("server.hosts", Value::from(vec!["a", "b"])),
+            ("server.ratio", Value::from(0.5)),
+        ])?;
+
+        assert_eq!(
+            value.to_json(JsonFormat::Compact)?,
+            r#"{"name":"realme","server":{"hosts":["a","b"],"port":8080,"ratio":0.5}}"#
+        );
+        assert_eq!(
+            value.to_json(JsonFormat::Pretty)?,
+            r#"{
+  "name": "realme",
+  "server": {
+    "hosts": [
+      "a",
+      "b"
+    ],
+    "port": 8080,
+    "ratio": 0.5
+  }
+}"#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_json_rejects_non_finite_floats() -> anyhow::Result<()> {
+        let value = Value::from_dotted([("limits.max", f64::INFINITY)])?;
+        let err = value
+            .to_json(JsonFormat::Compact)
+            .expect_err("infinity is not valid JSON")
+            .to_string();
+        assert!(err.contains("`limits.max` is not a finite number"), "{err}");
+        Ok(())
+    }
+}
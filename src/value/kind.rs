@@ -0,0 +1,145 @@
+use super::{
+    Value,
+    join_key,
+};
+
+/// The kind of a `Value`, without its content.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ValueKind {
+    Null,
+    Boolean,
+    Integer,
+    Float,
+    String,
+    Datetime,
+    Array,
+    Table,
+}
+
+impl ValueKind {
+    /// Returns the lowercase name of the kind, as used in error messages.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Null => "null",
+            Self::Boolean => "boolean",
+            Self::Integer => "integer",
+            Self::Float => "float",
+            Self::String => "string",
+            Self::Datetime => "datetime",
+            Self::Array => "array",
+            Self::Table => "table",
+        }
+    }
+}
+
+impl std::fmt::Display for ValueKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Value {
+    /// Returns the kind of this value.
+    pub const fn kind(&self) -> ValueKind {
+        match self {
+            Self::Null => ValueKind::Null,
+            Self::Boolean(_) => ValueKind::Boolean,
+            Self::Integer(_) => ValueKind::Integer,
+            Self::Float(_) => ValueKind::Float,
+            Self::String(_) => ValueKind::String,
+            Self::Datetime(_) => ValueKind::Datetime,
+            Self::Array(_) => ValueKind::Array,
+            Self::Table(_) => ValueKind::Table,
+        }
+    }
+
+    /// Returns the leaves of this value with their path and kind.
+    ///
+    /// Leaves are the values that are neither tables nor arrays. Paths use
+    /// the key syntax accepted by `get`, such as `server.hosts[0]`, so each
+    /// leaf can be looked up again. Empty tables and arrays have no leaves.
+    /// The order of table entries is unspecified.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use realme::{
+    ///     Value,
+    ///     ValueKind,
+    /// };
+    ///
+    /// let value =
+    ///     Value::from_dotted([("server.port", 8080)]).expect("build value");
+    /// let leaves: Vec<_> = value.leaves().collect();
+    /// assert_eq!(leaves, vec![(
+    ///     "server.port".to_string(),
+    ///     ValueKind::Integer,
+    ///     &Value::Integer(8080)
+    /// )]);
+    /// ```
+    pub fn leaves(&self) -> impl Iterator<Item = (String, ValueKind, &Self)> {
+        let mut leaves = Vec::new();
+        collect_leaves(String::new(), self, &mut leaves);
+        leaves.into_iter()
+    }
+}
+
+fn collect_leaves<'a>(
+    path: String,
+    value: &'a Value,
+    leaves: &mut Vec<(String, ValueKind, &'a Value)>,
+) {
+    match value {
+        Value::Table(table) => {
+            for (key, value) in table {
+                let path = join_key(&path, key);
+                collect_leaves(path, value, leaves);
+            }
+        }
+        Value::Array(array) => {
+            for (i, value) in array.iter().enumerate() {
+                collect_leaves(format!("{path}[{i}]"), value, leaves);
+            }
+        }
+        _ => leaves.push((path, value.kind(), value)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_leaves_of_nested_value() -> anyhow::Result<()> {
+        let value = Value::try_serialize(&serde_json::json!({
+            "name": "realme",
+            "server": {
+                "port": 8080,
+                "ratio": 0.5,
+                "hosts": ["a", "b"],
+                "tls": { "enabled": true, "cert": null },
+                "empty": {},
+            },
+        }))?;
+
+        let mut leaves: Vec<_> = value.leaves().collect();
+        leaves.sort_by(|a, b| a.0.cmp(&b.0));
+        let leaves: Vec<_> = leaves
+            .into_iter()
+            .map(|(path, kind, leaf)| {
+                assert_eq!(value.get(path.as_str()), Some(leaf));
+                (path, kind)
+            })
+            .collect();
+        assert_eq!(leaves, vec![
+            ("name".to_string(), ValueKind::String),
+            ("server.hosts[0]".to_string(), ValueKind::String),
+            ("server.hosts[1]".to_string(), ValueKind::String),
+            ("server.port".to_string(), ValueKind::Integer),
+            ("server.ratio".to_string(), ValueKind::Float),
+            ("server.tls.cert".to_string(), ValueKind::Null),
+            ("server.tls.enabled".to_string(), ValueKind::Boolean),
+        ]);
+        Ok(())
+    }
+}
@@ -1,24 +1,51 @@
 mod access;
 mod cast;
+mod datetime;
 mod des;
+mod dotted;
+mod hash;
+#[cfg(feature = "json")]
+mod json;
+mod kind;
 mod ser;
+mod validate;
 
 use std::fmt::{
     Display,
     Formatter,
 };
 
+pub use access::Expression;
+pub use datetime::Datetime;
+pub use des::ValueDeserializer;
+pub(crate) use dotted::insert_nested;
+#[cfg(feature = "cache")]
+pub(crate) use hash::Fnv;
+#[cfg(feature = "json")]
+pub use json::JsonFormat;
+pub use kind::ValueKind;
 use ser::ValueSerializer;
 use serde::{
     Deserialize,
     Serialize,
+    de::DeserializeOwned,
+};
+pub use validate::{
+    ValidationError,
+    Validator,
 };
 
 use crate::{
+    Error,
     Map,
     Result,
 };
 
+/// Number of elements below which `Value::try_deserialize_par` does not
+/// bother spreading the work across threads.
+#[cfg(feature = "rayon")]
+const PARALLEL_THRESHOLD: usize = 1024;
+
 pub type Array = Vec<Value>;
 pub type Table = Map<String, Value>;
 
@@ -31,6 +58,7 @@ pub enum Value {
     Integer(i64),
     Float(f64),
     String(String),
+    Datetime(Datetime),
     Array(Array),
     Table(Table),
 }
@@ -43,33 +71,507 @@ impl Display for Value {
             Self::Integer(i) => write!(f, "{i}"),
             Self::Float(fl) => write!(f, "{fl}"),
             Self::String(s) => write!(f, "{s}"),
+            Self::Datetime(d) => write!(f, "{d}"),
             Self::Array(a) => write!(f, "{a:?}"),
             Self::Table(t) => write!(f, "{t:?}"),
         }
     }
 }
 
+macro_rules! impl_from_for_value {
+    ($variant:ident: $($t:ty),*) => {
+        $(
+            impl From<$t> for Value {
+                fn from(value: $t) -> Self {
+                    Self::$variant(value.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_from_for_value!(Boolean: bool);
+impl_from_for_value!(Integer: i8, i16, i32, i64, u8, u16, u32);
+impl_from_for_value!(Float: f32, f64);
+impl_from_for_value!(String: &str, String);
+
+impl<T: Into<Self>> From<Vec<T>> for Value {
+    fn from(value: Vec<T>) -> Self {
+        Self::Array(value.into_iter().map(Into::into).collect())
+    }
+}
+
 impl Value {
     pub fn try_deserialize<'de, T: Deserialize<'de>>(self) -> Result<T> {
         T::deserialize(self).map_err(std::convert::Into::into)
     }
 
+    /// Deserializes an array into a `Vec<T>`, spreading the elements across
+    /// the threads of the rayon thread pool.
+    ///
+    /// The order of the elements is preserved. Arrays of fewer than 1024
+    /// elements are deserialized sequentially, where threads cost more than
+    /// they save, and any other value is deserialized as with
+    /// `try_deserialize`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an element can not be deserialized into `T`. When
+    /// several elements fail, which error is returned is unspecified.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use realme::Value;
+    ///
+    /// let ports = Value::from(vec![80, 443]);
+    /// assert_eq!(
+    ///     ports
+    ///         .try_deserialize_par::<u16>()
+    ///         .expect("deserialize ports"),
+    ///     vec![80, 443]
+    /// );
+    /// ```
+    #[cfg(feature = "rayon")]
+    pub fn try_deserialize_par<T>(self) -> Result<Vec<T>>
+    where
+        T: DeserializeOwned + Send,
+    {
+        use rayon::prelude::*;
+
+        match self {
+            Self::Array(array) if array.len() >= PARALLEL_THRESHOLD => {
+                array.into_par_iter().map(Self::try_deserialize).collect()
+            }
+            Self::Array(array) => {
+                array.into_iter().map(Self::try_deserialize).collect()
+            }
+            value => value.try_deserialize(),
+        }
+    }
+
+    /// Deserializes a copy of this value into `T`.
+    ///
+    /// This is a shorthand for deserializing a scalar or a subtree obtained
+    /// from `Realme::get` or `Value::get` without giving up the reference.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidCast` naming the value type and the target type
+    /// when the value can not be deserialized into `T`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use realme::Value;
+    ///
+    /// let value = Value::Integer(8080);
+    /// assert_eq!(value.coerce_into::<u16>().expect("coerce port"), 8080);
+    /// assert!(Value::from("http").coerce_into::<u16>().is_err());
+    /// ```
+    pub fn coerce_into<T: DeserializeOwned>(&self) -> Result<T> {
+        T::deserialize(self.clone()).map_err(|e| {
+            Error::new_cast_error(
+                self.value_type().to_string(),
+                format!(
+                    "can not coerce into {}: {e}",
+                    std::any::type_name::<T>()
+                ),
+            )
+        })
+    }
+
     pub fn try_serialize<T: Serialize>(from: &T) -> Result<Self> {
         from.serialize(ValueSerializer)
             .map_err(std::convert::Into::into)
     }
 
-    pub const fn value_type(&self) -> &'static str {
+    /// Returns the underlying `Map` of a table, or `None` for other values.
+    ///
+    /// The map is handed over as is: it bypasses the key path handling of
+    /// `get` and `set` as well as the merge logic.
+    pub fn into_table(self) -> Option<Table> {
+        match self {
+            Self::Table(table) => Some(table),
+            _ => None,
+        }
+    }
+
+    /// Returns a mutable reference to the underlying `Map` of a table, or
+    /// `None` for other values.
+    ///
+    /// Changes made through the map bypass the key path handling of `get`
+    /// and `set` as well as the merge logic: keys are inserted verbatim, so a
+    /// key containing `.` is not split into nested tables.
+    pub const fn as_table_mut(&mut self) -> Option<&mut Table> {
+        match self {
+            Self::Table(table) => Some(table),
+            _ => None,
+        }
+    }
+
+    /// Stores binary data as a base64 string.
+    ///
+    /// Values have no binary variant: binary data such as certificates or
+    /// keys is kept as a standard, padded base64 string, which survives
+    /// merging and every text format. Serializing bytes into a `Value`
+    /// follows the same convention.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use realme::Value;
+    ///
+    /// let value = Value::from_bytes_base64(&[0xde, 0xad, 0xbe, 0xef]);
+    /// assert_eq!(value, Value::String("3q2+7w==".to_string()));
+    /// assert_eq!(value.as_bytes_base64().expect("decode bytes"), [
+    ///     0xde, 0xad, 0xbe, 0xef
+    /// ]);
+    /// ```
+    pub fn from_bytes_base64(bytes: &[u8]) -> Self {
+        Self::String(crate::utils::base64::encode(bytes))
+    }
+
+    /// Decodes the binary data of a base64 string value.
+    ///
+    /// Whitespace within the string, such as line breaks, is ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidCast` if the value is not a string or not
+    /// valid base64.
+    pub fn as_bytes_base64(&self) -> Result<Vec<u8>> {
+        match self {
+            Self::String(s) => crate::utils::base64::decode(s)
+                .map_err(|e| Error::new_cast_error("string".to_string(), e)),
+            _ => Err(Error::new_cast_error(
+                self.value_type().to_string(),
+                "expected a base64 string".to_string(),
+            )),
+        }
+    }
+
+    /// Returns whether this value contains `expected`.
+    ///
+    /// Tables match when every key of `expected` is present with a matching
+    /// value, recursively, extra keys being ignored. Arrays and scalars must
+    /// be equal: an array does not match a subset of its elements.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use realme::Value;
+    ///
+    /// let config = Value::from_dotted([("a.b", 1), ("a.c", 2), ("d", 3)])
+    ///     .expect("build value");
+    /// let expected = Value::from_dotted([("a.b", 1)]).expect("build value");
+    /// assert!(config.contains(&expected));
+    /// assert!(!expected.contains(&config));
+    /// ```
+    pub fn contains(&self, expected: &Self) -> bool {
+        match (self, expected) {
+            (Self::Table(table), Self::Table(expected)) => {
+                expected.iter().all(|(key, expected)| {
+                    table.get(key).is_some_and(|value| value.contains(expected))
+                })
+            }
+            _ => self == expected,
+        }
+    }
+
+    /// Keeps only the table entries for which `keep` returns true.
+    ///
+    /// `keep` is called with the dotted path and value of each table entry,
+    /// parents before children, and an entry it rejects is removed along
+    /// with everything under it. Tables left empty once their entries are
+    /// removed are pruned, while tables that were empty to begin with are
+    /// kept. Array elements are not filtered: an array is kept or removed as
+    /// a whole. Calling this on a value that is not a table does nothing.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use realme::Value;
+    ///
+    /// let mut config = Value::from_dotted([
+    ///     ("server.port", 8080),
+    ///     ("internal.token", 1),
+    ///     ("internal.salt", 2),
+    /// ])
+    /// .expect("build value");
+    /// config.retain(|path, _| !path.starts_with("internal."));
+    /// assert_eq!(config.get("server.port"), Some(&Value::Integer(8080)));
+    /// assert_eq!(config.get("internal"), None);
+    /// ```
+    pub fn retain<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(&str, &Self) -> bool,
+    {
+        if let Self::Table(table) = self {
+            retain_entries(table, "", &mut keep);
+        }
+    }
+
+    /// Returns a new tree with `f` applied to every scalar leaf.
+    ///
+    /// Arrays and tables are recursed into, keeping their keys and order,
+    /// and are never passed to `f`. `Value::Null` counts as a scalar.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use realme::Value;
+    ///
+    /// let config = Value::from_dotted([("name", " realme "), ("host", " a ")])
+    ///     .expect("build value");
+    /// let trimmed = config.map_values(|v| match v {
+    ///     Value::String(s) => Value::String(s.trim().to_string()),
+    ///     v => v,
+    /// });
+    /// assert_eq!(trimmed.get("name"), Some(&Value::from("realme")));
+    /// ```
+    #[must_use]
+    pub fn map_values<F>(self, mut f: F) -> Self
+    where
+        F: FnMut(Self) -> Self,
+    {
+        map_leaves(self, &mut f)
+    }
+
+    /// Converts a scalar into a `String`, or returns `None` for an array or
+    /// a table.
+    ///
+    /// Unlike `Display`, which also formats containers, this only accepts
+    /// scalars: strings are returned as is, numbers and booleans are
+    /// formatted and `Value::Null` becomes an empty string.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use realme::Value;
+    ///
+    /// assert_eq!(Value::from(8080).to_string_lossy().as_deref(), Some("8080"));
+    /// assert_eq!(Value::Null.to_string_lossy().as_deref(), Some(""));
+    /// assert_eq!(Value::from(vec![1, 2]).to_string_lossy(), None);
+    /// ```
+    pub fn to_string_lossy(&self) -> Option<String> {
+        match self {
+            Self::Null => Some(String::new()),
+            Self::String(s) => Some(s.clone()),
+            Self::Boolean(_) |
+            Self::Integer(_) |
+            Self::Float(_) |
+            Self::Datetime(_) => Some(self.to_string()),
+            Self::Array(_) | Self::Table(_) => None,
+        }
+    }
+
+    /// Interprets a loosely written boolean, or returns `None` if `self` is
+    /// not one.
+    ///
+    /// Besides `Value::Boolean`, the strings `true`, `yes`, `on` and `1` are
+    /// true and `false`, `no`, `off` and `0` are false, ignoring case and
+    /// surrounding whitespace. The integers `1` and `0` are accepted too.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use realme::Value;
+    ///
+    /// assert_eq!(Value::from(" Yes ").as_bool_loose(), Some(true));
+    /// assert_eq!(Value::from("off").as_bool_loose(), Some(false));
+    /// assert_eq!(Value::from("maybe").as_bool_loose(), None);
+    /// ```
+    #[must_use]
+    pub fn as_bool_loose(&self) -> Option<bool> {
+        match self {
+            Self::Boolean(b) => Some(*b),
+            Self::Integer(1) => Some(true),
+            Self::Integer(0) => Some(false),
+            Self::String(s) => match s.trim().to_ascii_lowercase().as_str() {
+                "true" | "yes" | "on" | "1" => Some(true),
+                "false" | "no" | "off" | "0" => Some(false),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Replaces the values at `paths` that `as_bool_loose` recognizes with
+    /// a `Value::Boolean`.
+    ///
+    /// Missing paths and unrecognized values are left as is, see
+    /// `try_coerce_booleans` to reject the latter.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use realme::Value;
+    ///
+    /// let mut config =
+    ///     Value::from_dotted([("feature.enabled", "on"), ("name", "yes")])
+    ///         .expect("build value");
+    /// config.coerce_booleans(&["feature.enabled"]);
+    /// assert_eq!(config.get("feature.enabled"), Some(&Value::Boolean(true)));
+    /// assert_eq!(config.get("name"), Some(&Value::from("yes")));
+    /// ```
+    pub fn coerce_booleans(&mut self, paths: &[&str]) {
+        for path in paths {
+            if let Some(value) = self.find_mut(*path) {
+                if let Some(b) = value.as_bool_loose() {
+                    *value = Self::Boolean(b);
+                }
+            }
+        }
+    }
+
+    /// Like `coerce_booleans`, but fails on a value that is present and not
+    /// a recognized boolean.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidCast` naming the first path whose value is not
+    /// a boolean. The values at the paths before it are already coerced.
+    pub fn try_coerce_booleans(&mut self, paths: &[&str]) -> Result<()> {
+        for path in paths {
+            if let Some(value) = self.find_mut(*path) {
+                let Some(b) = value.as_bool_loose() else {
+                    return Err(Error::new_cast_error(
+                        value.to_string(),
+                        format!("`{path}` is not a boolean"),
+                    ));
+                };
+                *value = Self::Boolean(b);
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns a mutable reference to the value of `key` in this table,
+    /// inserting `default` first if `key` is absent.
+    ///
+    /// Like `as_table_mut`, `key` is a key of this table and not a path:
+    /// `a.b` is inserted verbatim rather than as a nested table.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::SetValueError` if `self` is not a table.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use realme::{
+    ///     Map,
+    ///     Value,
+    /// };
+    ///
+    /// let mut config = Value::Table(Map::new());
+    /// let cache = config
+    ///     .entry_or_insert("cache", Value::Table(Map::new()))
+    ///     .expect("config is a table");
+    /// cache.set("ttl", Value::from(60)).expect("set ttl");
+    /// assert_eq!(config.get("cache.ttl"), Some(&Value::Integer(60)));
+    /// ```
+    pub fn entry_or_insert<K: Into<String>>(
+        &mut self,
+        key: K,
+        default: Self,
+    ) -> Result<&mut Self> {
+        match self {
+            Self::Table(table) => {
+                Ok(table.entry(key.into()).or_insert(default))
+            }
+            _ => Err(Error::SetValueError(format!(
+                "Expected a table, got {}",
+                self.value_type()
+            ))),
+        }
+    }
+
+    /// Returns how deeply `self` is nested.
+    ///
+    /// Scalars have a depth of 0, and an array or a table is one level
+    /// deeper than its deepest element, so `{"a": {"b": 1}}` has a depth of
+    /// 2.
+    #[must_use]
+    pub fn depth(&self) -> usize {
         match self {
-            Self::Null => "null",
-            Self::Boolean(_) => "boolean",
-            Self::Integer(_) => "integer",
-            Self::Float(_) => "float",
-            Self::String(_) => "string",
-            Self::Array(_) => "array",
-            Self::Table(_) => "table",
+            Self::Array(array) => {
+                1 + array.iter().map(Self::depth).max().unwrap_or_default()
+            }
+            Self::Table(table) => {
+                1 + table.values().map(Self::depth).max().unwrap_or_default()
+            }
+            _ => 0,
+        }
+    }
+
+    /// Returns the number of values in `self`, counting `self` and every
+    /// nested array, table and scalar.
+    #[must_use]
+    pub fn node_count(&self) -> usize {
+        match self {
+            Self::Array(array) => {
+                1 + array.iter().map(Self::node_count).sum::<usize>()
+            }
+            Self::Table(table) => {
+                1 + table.values().map(Self::node_count).sum::<usize>()
+            }
+            _ => 1,
         }
     }
+
+    pub const fn value_type(&self) -> &'static str {
+        self.kind().as_str()
+    }
+}
+
+/// Applies `f` to the scalar leaves of `value`, see `Value::map_values`.
+fn map_leaves<F>(value: Value, f: &mut F) -> Value
+where
+    F: FnMut(Value) -> Value,
+{
+    match value {
+        Value::Array(array) => {
+            Value::Array(array.into_iter().map(|v| map_leaves(v, f)).collect())
+        }
+        Value::Table(table) => Value::Table(
+            table
+                .into_iter()
+                .map(|(k, v)| (k, map_leaves(v, f)))
+                .collect(),
+        ),
+        scalar => f(scalar),
+    }
+}
+
+/// Appends `key` to the dotted `path`.
+pub(crate) fn join_key(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{path}.{key}")
+    }
+}
+
+/// Filters the entries of `table` found at `path`, see `Value::retain`.
+fn retain_entries<F>(table: &mut Table, path: &str, keep: &mut F)
+where
+    F: FnMut(&str, &Value) -> bool,
+{
+    table.retain(|key, value| {
+        let path = join_key(path, key);
+        if !keep(&path, value) {
+            return false;
+        }
+        match value {
+            Value::Table(nested) if !nested.is_empty() => {
+                retain_entries(nested, &path, keep);
+                !nested.is_empty()
+            }
+            _ => true,
+        }
+    });
 }
 
 #[cfg(test)]
@@ -142,6 +644,25 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_set_through_subscript() -> anyhow::Result<()> {
+        let mut value = Value::Table(Table::new());
+        value.set("servers[0].host", Value::from("a"))?;
+        value.set("servers[1].host", Value::from("b"))?;
+        value.set("servers[0].port", Value::Integer(80))?;
+        assert_eq!(value.get("servers[0].host"), Some(&Value::from("a")));
+        assert_eq!(value.get("servers[1].host"), Some(&Value::from("b")));
+        assert_eq!(value.get("servers[0].port"), Some(&Value::Integer(80)));
+        assert!(
+            matches!(value.get("servers[0]"), Some(Value::Table(t)) if t.len() == 2)
+        );
+        assert!(matches!(&value, Value::Table(t) if t.len() == 1));
+
+        value.set("servers.1.host", Value::from("c"))?;
+        assert_eq!(value.get("servers[1].host"), Some(&Value::from("c")));
+        Ok(())
+    }
+
     // #[test]
     // fn test_replace_value() {
     //     let value = Value::Table(Table::from_iter(vec![(
@@ -162,6 +683,82 @@ mod tests {
     //     );
     // }
 
+    #[test]
+    fn test_negative_index() -> anyhow::Result<()> {
+        let mut value = prepare_value();
+        assert_eq!(value.get("a.b[-1]"), Some(&Value::Integer(3)));
+        assert_eq!(value.get("a.b[-2]"), Some(&Value::Integer(2)));
+        assert_eq!(value.get("a.b[-3]"), Some(&Value::Integer(1)));
+        assert_eq!(value.get("a.b[-4]"), None);
+        assert_eq!(
+            value.get("a.b").and_then(|b| b.get(-2isize)),
+            Some(&Value::Integer(2))
+        );
+        assert_eq!(value.get_mut("a.b[-2]"), Some(&mut Value::Integer(2)));
+        assert_eq!(value.get_mut("a.b[-4]"), None);
+
+        value.set("a.b[-1]", Value::Integer(30))?;
+        assert_eq!(value.get("a.b[2]"), Some(&Value::Integer(30)));
+        assert!(value.set("a.b[-4]", Value::Integer(0)).is_err());
+        assert_eq!(
+            value.get("a.b"),
+            Some(&Value::Array(vec![
+                Value::Integer(1),
+                Value::Integer(2),
+                Value::Integer(30)
+            ]))
+        );
+
+        value.set("c[1]", Value::Integer(1))?;
+        assert_eq!(
+            value.get("c"),
+            Some(&Value::Array(vec![Value::Null, Value::Integer(1)]))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut value = prepare_value();
+        assert_eq!(value.remove("a.b[0]"), Some(Value::Integer(1)));
+        assert_eq!(value.remove("a.b.-1"), Some(Value::Integer(3)));
+        assert_eq!(
+            value.get("a.b"),
+            Some(&Value::Array(vec![Value::Integer(2)]))
+        );
+        assert_eq!(value.remove("a.b[5]"), None);
+        assert_eq!(value.remove("a.c.d"), None);
+        assert_eq!(value.get("a.c"), None);
+        assert_eq!(
+            value.remove("a.b"),
+            Some(Value::Array(vec![Value::Integer(2)]))
+        );
+        assert_eq!(value.get("a"), Some(&Value::Table(Table::new())));
+    }
+
+    #[test]
+    fn test_get_all() -> anyhow::Result<()> {
+        let mut value = prepare_value();
+        value.set("t.y.n", Value::Integer(2))?;
+        value.set("t.x.n", Value::Integer(1))?;
+        value.set("t.z", Value::Integer(3))?;
+        assert_eq!(value.get_all("a.b[*]"), vec![
+            &Value::Integer(1),
+            &Value::Integer(2),
+            &Value::Integer(3)
+        ]);
+        assert_eq!(value.get_all("t.*.n"), vec![
+            &Value::Integer(1),
+            &Value::Integer(2)
+        ]);
+        assert_eq!(value.get_all("a.b[-1]"), vec![&Value::Integer(3)]);
+        assert!(value.get_all("a.missing.*").is_empty());
+        assert_eq!(value.get("a.b[*]"), None);
+        assert_eq!(value.get_as::<_, Vec<i64>>("t.*.n"), Some(vec![1, 2]));
+        assert_eq!(value.remove("a.b[*]"), None);
+        Ok(())
+    }
+
     #[test]
     fn test_get_mut() -> anyhow::Result<()> {
         let mut value = Value::Table(Table::new());
@@ -342,4 +939,342 @@ mod tests {
     //         Some(Value::Integer(43))
     //     );
     // }
+
+    #[test]
+    fn test_coerce_into_scalar() -> anyhow::Result<()> {
+        let value = Value::from_dotted([("server.port", 8080)])?;
+        let port = value.get("server.port").expect("port is set");
+        assert_eq!(port.coerce_into::<u16>()?, 8080);
+
+        let err = Value::from("http")
+            .coerce_into::<u16>()
+            .expect_err("not a number");
+        assert!(matches!(err, Error::InvalidCast(_)));
+        assert!(err.to_string().contains("Cast from string"));
+        assert!(err.to_string().contains("u16"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_coerce_into_subtree() -> anyhow::Result<()> {
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Server {
+            host: String,
+            port: u16,
+        }
+
+        let value = Value::from_dotted([
+            ("server.host", Value::from("localhost")),
+            ("server.port", Value::from(8080)),
+        ])?;
+        let server = value.get("server").expect("server is set");
+        assert_eq!(server.coerce_into::<Server>()?, Server {
+            host: "localhost".to_string(),
+            port: 8080,
+        });
+        Ok(())
+    }
+
+    #[test]
+    fn test_as_table_mut() -> anyhow::Result<()> {
+        let mut value = Value::from_dotted([("a.b", 1), ("a.c", 2), ("d", 3)])?;
+        let table = value.as_table_mut().expect("value is a table");
+        table.retain(|key, _| key != "d");
+        table.entry("e.f".to_string()).or_insert(Value::Integer(4));
+        if let Some(Value::Table(a)) = table.get_mut("a") {
+            a.remove("c");
+        }
+
+        let table = value.into_table().expect("value is a table");
+        assert_eq!(table.len(), 2);
+        assert_eq!(table.get("e.f"), Some(&Value::Integer(4)));
+        assert_eq!(table.get("a"), Some(&Value::from_dotted([("b", 1)])?));
+        assert!(!table.contains_key("d"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_into_table_non_table() {
+        assert_eq!(Value::Integer(1).into_table(), None);
+        assert_eq!(Value::Array(vec![]).as_table_mut(), None);
+    }
+
+    #[test]
+    fn test_contains() -> anyhow::Result<()> {
+        let config = Value::from_dotted([
+            ("server.host", Value::from("localhost")),
+            ("server.port", Value::from(8080)),
+            ("server.tags", Value::from(vec!["a", "b"])),
+            ("debug", Value::from(true)),
+        ])?;
+
+        let subset = Value::from_dotted([
+            ("server.port", Value::from(8080)),
+            ("server.tags", Value::from(vec!["a", "b"])),
+        ])?;
+        assert!(config.contains(&subset));
+        assert!(config.contains(&Value::Table(Table::new())));
+
+        let missing = Value::from_dotted([("server.user", "root")])?;
+        assert!(!config.contains(&missing));
+
+        let differing = Value::from_dotted([("server.port", 9090)])?;
+        assert!(!config.contains(&differing));
+
+        let partial_array = Value::from_dotted([("server.tags", vec!["a"])])?;
+        assert!(!config.contains(&partial_array));
+        Ok(())
+    }
+
+    #[test]
+    fn test_retain() -> anyhow::Result<()> {
+        let mut config = Value::from_dotted([
+            ("server.host", Value::from("localhost")),
+            ("server.port", Value::from(8080)),
+            ("server.tags", Value::from(vec!["a", "b"])),
+            ("internal.token", Value::from("secret")),
+            ("internal.keys.signing", Value::from("key")),
+            ("debug", Value::from(true)),
+        ])?;
+        if let Value::Table(table) = &mut config {
+            table.insert("empty".to_string(), Value::Table(Table::new()));
+        }
+
+        let mut seen = Vec::new();
+        config.retain(|path, _| {
+            seen.push(path.to_string());
+            path != "server.port" &&
+                !path.ends_with("token") &&
+                path != "internal.keys.signing"
+        });
+
+        let expected = Value::from_dotted([
+            ("server.host", Value::from("localhost")),
+            ("server.tags", Value::from(vec!["a", "b"])),
+            ("debug", Value::from(true)),
+        ])?;
+        assert!(config.contains(&expected));
+        assert_eq!(config.get("server.port"), None);
+        assert_eq!(config.get("internal"), None);
+        assert_eq!(config.get("empty"), Some(&Value::Table(Table::new())));
+        assert!(seen.contains(&"internal.keys".to_string()));
+        assert!(!seen.iter().any(|path| path.contains('[')));
+
+        config.retain(|path, _| path != "server");
+        assert_eq!(config.get("server"), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_coerce_booleans() -> anyhow::Result<()> {
+        for truthy in ["true", "YES", " on ", "1"] {
+            assert_eq!(Value::from(truthy).as_bool_loose(), Some(true));
+        }
+        for falsy in ["false", "No", "off", "0"] {
+            assert_eq!(Value::from(falsy).as_bool_loose(), Some(false));
+        }
+        assert_eq!(Value::from(1).as_bool_loose(), Some(true));
+        assert_eq!(Value::from(2).as_bool_loose(), None);
+        assert_eq!(Value::from("enabled").as_bool_loose(), None);
+
+        let mut config = Value::from_dotted([
+            ("feature.enabled", "yes"),
+            ("feature.beta", "off"),
+            ("feature.mode", "enabled"),
+        ])?;
+        let paths = ["feature.enabled", "feature.beta", "feature.mode", "x"];
+        config.coerce_booleans(&paths);
+        assert_eq!(config.get("feature.enabled"), Some(&Value::Boolean(true)));
+        assert_eq!(config.get("feature.beta"), Some(&Value::Boolean(false)));
+        assert_eq!(config.get("feature.mode"), Some(&Value::from("enabled")));
+
+        let err = config
+            .try_coerce_booleans(&paths)
+            .expect_err("`enabled` is not a boolean");
+        assert!(err.to_string().contains("`feature.mode` is not a boolean"));
+
+        let mut config = Value::from_dotted([("a", "x")])?;
+        config.coerce_booleans(&["feature.enabled"]);
+        config.try_coerce_booleans(&["feature.enabled", "b[0]"])?;
+        assert_eq!(config, Value::from_dotted([("a", "x")])?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_string_lossy() {
+        assert_eq!(Value::Null.to_string_lossy().as_deref(), Some(""));
+        assert_eq!(
+            Value::Boolean(true).to_string_lossy().as_deref(),
+            Some("true")
+        );
+        assert_eq!(
+            Value::Integer(-42).to_string_lossy().as_deref(),
+            Some("-42")
+        );
+        assert_eq!(Value::Float(0.5).to_string_lossy().as_deref(), Some("0.5"));
+        assert_eq!(
+            Value::from("realme").to_string_lossy().as_deref(),
+            Some("realme")
+        );
+        assert_eq!(Value::Array(vec![]).to_string_lossy(), None);
+        assert_eq!(prepare_value().to_string_lossy(), None);
+    }
+
+    #[test]
+    fn test_entry_or_insert() -> anyhow::Result<()> {
+        let mut value = Value::Table(Table::new());
+        let cache =
+            value.entry_or_insert("cache", Value::Table(Table::new()))?;
+        assert_eq!(cache, &Value::Table(Table::new()));
+        cache.set("ttl", Value::from(60))?;
+        assert_eq!(value.get("cache.ttl"), Some(&Value::Integer(60)));
+
+        let existing = value.entry_or_insert("cache", Value::Null)?;
+        assert_eq!(existing.get("ttl"), Some(&Value::Integer(60)));
+
+        let dotted = value.entry_or_insert("a.b", Value::from(1))?;
+        assert_eq!(dotted, &Value::Integer(1));
+        assert_eq!(value.get("a"), None);
+
+        assert!(matches!(
+            Value::from(1).entry_or_insert("cache", Value::Null),
+            Err(Error::SetValueError(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_depth_and_node_count() {
+        let value = prepare_value();
+        assert_eq!(value.depth(), 3);
+        assert_eq!(value.node_count(), 6);
+        assert_eq!(Value::Integer(1).depth(), 0);
+        assert_eq!(Value::Integer(1).node_count(), 1);
+        assert_eq!(Value::Table(Table::new()).depth(), 1);
+    }
+
+    #[test]
+    fn test_map_values() -> anyhow::Result<()> {
+        let config = Value::from_dotted([
+            ("name", Value::from("  realme ")),
+            ("server.port", Value::from(8080)),
+            ("server.hosts", Value::from(vec![" a", "b "])),
+            ("db.password", Value::from("hunter2")),
+            ("db.user", Value::from(" admin ")),
+        ])?;
+
+        let trimmed = config.clone().map_values(|v| match v {
+            Value::String(s) => Value::String(s.trim().to_string()),
+            v => v,
+        });
+        let expected = Value::from_dotted([
+            ("name", Value::from("realme")),
+            ("server.port", Value::from(8080)),
+            ("server.hosts", Value::from(vec!["a", "b"])),
+            ("db.password", Value::from("hunter2")),
+            ("db.user", Value::from("admin")),
+        ])?;
+        assert_eq!(trimmed, expected);
+
+        let mut seen = 0;
+        let redacted = config.map_values(|v| {
+            seen += 1;
+            match v {
+                Value::String(_) => Value::from("***"),
+                v => v,
+            }
+        });
+        assert_eq!(seen, 6);
+        assert_eq!(redacted.get("db.password"), Some(&Value::from("***")));
+        assert_eq!(redacted.get("server.hosts[1]"), Some(&Value::from("***")));
+        assert_eq!(redacted.get("server.port"), Some(&Value::Integer(8080)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_bytes_round_trip() -> anyhow::Result<()> {
+        #[derive(Debug, PartialEq)]
+        struct Blob(Vec<u8>);
+
+        impl Serialize for Blob {
+            fn serialize<S: serde::Serializer>(
+                &self,
+                serializer: S,
+            ) -> std::result::Result<S::Ok, S::Error> {
+                serializer.serialize_bytes(&self.0)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for Blob {
+            fn deserialize<D: serde::Deserializer<'de>>(
+                deserializer: D,
+            ) -> std::result::Result<Self, D::Error> {
+                struct BlobVisitor;
+
+                impl serde::de::Visitor<'_> for BlobVisitor {
+                    type Value = Blob;
+
+                    fn expecting(
+                        &self,
+                        f: &mut Formatter<'_>,
+                    ) -> std::fmt::Result {
+                        f.write_str("bytes")
+                    }
+
+                    fn visit_byte_buf<E>(
+                        self,
+                        v: Vec<u8>,
+                    ) -> std::result::Result<Blob, E> {
+                        Ok(Blob(v))
+                    }
+                }
+
+                deserializer.deserialize_byte_buf(BlobVisitor)
+            }
+        }
+
+        let blob = Blob(vec![0x00, 0xff, 0xc3, 0x28, b'\n', 0x80]);
+        let mut value = Value::try_serialize(&blob)?;
+        assert_eq!(value, Value::String("AP/DKAqA".to_string()));
+        value.merge(&Value::from_bytes_base64(&blob.0));
+        assert_eq!(value.as_bytes_base64()?, blob.0);
+        assert_eq!(value.try_deserialize::<Blob>()?, blob);
+
+        assert!(Value::from("not base64!").as_bytes_base64().is_err());
+        assert!(Value::Integer(1).as_bytes_base64().is_err());
+        Ok(())
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_try_deserialize_par() -> anyhow::Result<()> {
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Item {
+            id:   u32,
+            name: String,
+            tags: Vec<String>,
+        }
+
+        let items = Value::Array(
+            (0..5_000)
+                .map(|id| {
+                    Value::from_dotted([
+                        ("id", Value::from(id)),
+                        ("name", Value::from(format!("item-{id}"))),
+                        ("tags", Value::from(vec!["a", "b"])),
+                    ])
+                })
+                .collect::<Result<_>>()?,
+        );
+        let sequential: Vec<Item> = items.clone().try_deserialize()?;
+        let parallel: Vec<Item> = items.clone().try_deserialize_par()?;
+        assert_eq!(parallel, sequential);
+
+        let mut broken = items;
+        if let Value::Array(array) = &mut broken {
+            array[4_321] = Value::from("not an item");
+        }
+        assert!(broken.try_deserialize_par::<Item>().is_err());
+        Ok(())
+    }
 }
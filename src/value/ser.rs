@@ -13,7 +13,13 @@ use serde::{
     },
 };
 
-use super::Value;
+#[cfg(feature = "toml")]
+use super::datetime::TOML_DATETIME_KEY;
+use super::{
+    Datetime,
+    Value,
+    datetime::DATETIME_TOKEN,
+};
 use crate::utils::Map;
 
 pub struct ValueSerializer;
@@ -30,6 +36,9 @@ impl Serialize for Value {
             Self::Integer(i) => serializer.serialize_i64(*i),
             Self::Float(f) => serializer.serialize_f64(*f),
             Self::String(s) => serializer.serialize_str(s),
+            Self::Datetime(d) => {
+                serializer.serialize_newtype_struct(DATETIME_TOKEN, d.as_str())
+            }
             Self::Array(arr) => {
                 let mut seq = serializer.serialize_seq(Some(arr.len()))?;
                 for value in arr {
@@ -101,8 +110,12 @@ impl Serializer for ValueSerializer {
         }
     }
 
+    /// Widens `v` to the `f64` closest to its shortest decimal form, so that
+    /// `1.1f32` becomes `1.1` rather than `1.100000023841858`.
     fn serialize_f32(self, v: f32) -> Result<Self::Ok, Self::Error> {
-        Ok(Value::Float(f64::from(v)))
+        Ok(Value::Float(
+            v.to_string().parse().unwrap_or_else(|_| f64::from(v)),
+        ))
     }
 
     fn serialize_f64(self, v: f64) -> Result<Self::Ok, Self::Error> {
@@ -117,8 +130,9 @@ impl Serializer for ValueSerializer {
         Ok(Value::String(v.to_string()))
     }
 
+    /// Stores bytes as a base64 string, see `Value::from_bytes_base64`.
     fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
-        Ok(Value::String(String::from_utf8_lossy(v).into_owned()))
+        Ok(Value::from_bytes_base64(v))
     }
 
     fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
@@ -154,10 +168,16 @@ impl Serializer for ValueSerializer {
 
     fn serialize_newtype_struct<T: ?Sized + Serialize>(
         self,
-        _name: &'static str,
+        name: &'static str,
         value: &T,
     ) -> Result<Self::Ok, Self::Error> {
-        value.serialize(self)
+        let value = value.serialize(self)?;
+        if name != DATETIME_TOKEN {
+            return Ok(value);
+        }
+        Datetime::try_from(value)
+            .map(Value::Datetime)
+            .map_err(ser::Error::custom)
     }
 
     fn serialize_newtype_variant<T: ?Sized + Serialize>(
@@ -380,13 +400,22 @@ impl SerializeMap for MapSerializer {
         &mut self,
         key: &K,
     ) -> Result<(), Self::Error> {
-        let serialized_key = key.serialize(ValueSerializer)?;
-        if let Value::String(key_str) = serialized_key {
-            self.current_key = Some(key_str);
-            Ok(())
-        } else {
-            Err(ser::Error::custom("Only string keys are supported"))
-        }
+        // Like serde_json, scalar keys are stringified so that maps such as
+        // `HashMap<u32, T>` can be represented as a table.
+        let key_str = match key.serialize(ValueSerializer)? {
+            Value::String(key_str) => key_str,
+            Value::Integer(key) => key.to_string(),
+            Value::Float(key) => key.to_string(),
+            Value::Boolean(key) => key.to_string(),
+            other => {
+                return Err(ser::Error::custom(format!(
+                    "map key must be a string or a scalar, found {}",
+                    other.value_type()
+                )));
+            }
+        };
+        self.current_key = Some(key_str);
+        Ok(())
     }
 
     fn serialize_value<V: ?Sized + Serialize>(
@@ -425,8 +454,10 @@ impl SerializeStruct for StructSerializer {
         // serialized datetime value. This is only relevant when the
         // "toml" feature is enabled.
         #[cfg(feature = "toml")]
-        if key == "$__toml_private_datetime" {
-            self.fields = serialized_value;
+        if key == TOML_DATETIME_KEY {
+            self.fields = Datetime::try_from(serialized_value)
+                .map(Value::Datetime)
+                .map_err(ser::Error::custom)?;
             return Ok(());
         }
 
@@ -522,4 +553,55 @@ mod test {
         assert_eq!(val, output);
         Ok(())
     }
+
+    #[test]
+    fn test_map_with_integer_keys() -> anyhow::Result<()> {
+        let mut map = std::collections::HashMap::new();
+        map.insert(1u32, "one".to_string());
+        map.insert(42u32, "forty-two".to_string());
+
+        let value = Value::try_serialize(&map)?;
+        assert_eq!(
+            value.get("42"),
+            Some(&Value::String("forty-two".to_string()))
+        );
+        let actual: std::collections::HashMap<u32, String> =
+            value.try_deserialize()?;
+        assert_eq!(map, actual);
+        Ok(())
+    }
+
+    #[test]
+    fn test_map_with_struct_keys() {
+        #[derive(Serialize, PartialEq, Eq, Hash)]
+        struct Key {
+            id: u32,
+        }
+
+        let mut map = std::collections::HashMap::new();
+        map.insert(Key { id: 1 }, "one");
+        assert!(Value::try_serialize(&map).is_err());
+    }
+
+    #[test]
+    fn test_float_round_trip() -> anyhow::Result<()> {
+        #[derive(Serialize)]
+        struct Ratios {
+            single: f32,
+            double: f64,
+        }
+
+        let value = Value::try_serialize(&Ratios {
+            single: 1.1,
+            double: 1.1,
+        })?;
+        assert_eq!(value.get("single"), Some(&Value::Float(1.1)));
+        assert_eq!(value.get("double"), Some(&Value::Float(1.1)));
+
+        let loaded = Value::try_serialize(&serde_json::from_str::<
+            serde_json::Value,
+        >(r#"{"ratio": 1.1}"#)?)?;
+        assert_eq!(serde_json::to_string(&loaded)?, r#"{"ratio":1.1}"#);
+        Ok(())
+    }
 }
@@ -0,0 +1,290 @@
+use std::{
+    fmt::{
+        Debug,
+        Display,
+        Formatter,
+    },
+    ops::RangeBounds,
+    sync::Arc,
+};
+
+use serde::de::DeserializeOwned;
+
+use super::Value;
+
+/// An error found while validating a `Value`, citing the offending key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+    /// The dotted path of the offending value.
+    pub key:     String,
+    /// What is wrong with the value.
+    pub message: String,
+}
+
+impl ValidationError {
+    /// Creates a new `ValidationError`.
+    pub fn new<K: Into<String>, M: Into<String>>(key: K, message: M) -> Self {
+        Self {
+            key:     key.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "`{}` {}", self.key, self.message)
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+type Check = dyn Fn(&Value) -> Result<(), Vec<ValidationError>> + Send + Sync;
+
+/// A check of the values found in a `Value`.
+///
+/// Validators are built for a key with `range`, `one_of` or `matches`, and
+/// combined with `and`, which runs both validators and reports the errors
+/// of both. A key that is missing or `Value::Null` passes every validator,
+/// so presence is checked separately with `required`.
+///
+/// # Examples
+///
+/// ```rust
+/// use realme::prelude::*;
+///
+/// let validator = Validator::range("server.port", 1..=65535)
+///     .and(Validator::one_of("log.level", ["debug", "info", "warn"]));
+/// let config = Value::from_dotted([
+///     ("server.port", Value::from(8080)),
+///     ("log.level", Value::from("trace")),
+/// ])
+/// .expect("build value");
+///
+/// let errors = validator.validate(&config).expect_err("invalid level");
+/// assert_eq!(errors[0].key, "log.level");
+/// ```
+#[derive(Clone)]
+pub struct Validator {
+    check: Arc<Check>,
+}
+
+impl Debug for Validator {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Validator").finish_non_exhaustive()
+    }
+}
+
+impl Validator {
+    /// Creates a validator from a function checking a whole `Value`.
+    pub fn new<F>(check: F) -> Self
+    where
+        F: Fn(&Value) -> Result<(), Vec<ValidationError>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        Self {
+            check: Arc::new(check),
+        }
+    }
+
+    /// Creates a validator checking the value at `key` with `check`, which
+    /// returns a message describing the problem. Missing and null values
+    /// are not checked.
+    fn key<K, F>(key: K, check: F) -> Self
+    where
+        K: Into<String>,
+        F: Fn(&Value) -> Result<(), String> + Send + Sync + 'static,
+    {
+        let key = key.into();
+        Self::new(move |value| match value.get(key.as_str()) {
+            None | Some(Value::Null) => Ok(()),
+            Some(found) => check(found)
+                .map_err(|message| vec![ValidationError::new(&key, message)]),
+        })
+    }
+
+    /// Checks that `key` is set to a value other than `Value::Null`.
+    pub fn required<K: Into<String>>(key: K) -> Self {
+        let key = key.into();
+        Self::new(move |value| match value.get(key.as_str()) {
+            None | Some(Value::Null) => {
+                Err(vec![ValidationError::new(&key, "is required")])
+            }
+            Some(_) => Ok(()),
+        })
+    }
+
+    /// Checks that the value at `key` is a number within `range`.
+    ///
+    /// The value is coerced into the type of the range bounds, so an
+    /// integer or a numeric string is accepted for `1..=65535`.
+    pub fn range<K, T, R>(key: K, range: R) -> Self
+    where
+        K: Into<String>,
+        T: DeserializeOwned + PartialOrd + Debug,
+        R: RangeBounds<T> + Debug + Send + Sync + 'static,
+    {
+        Self::key(key, move |found| match found.coerce_into::<T>() {
+            Ok(number) if range.contains(&number) => Ok(()),
+            _ => Err(format!("is {found}, expected a value in {range:?}")),
+        })
+    }
+
+    /// Checks that the value at `key` is one of `options`.
+    pub fn one_of<K, I, V>(key: K, options: I) -> Self
+    where
+        K: Into<String>,
+        I: IntoIterator<Item = V>,
+        V: Into<Value>,
+    {
+        let options: Vec<Value> = options.into_iter().map(Into::into).collect();
+        Self::key(key, move |found| {
+            if options.contains(found) {
+                Ok(())
+            } else {
+                let expected = options
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                Err(format!("is {found}, expected one of {expected}"))
+            }
+        })
+    }
+
+    /// Checks that the value at `key` is a string matching `regex`.
+    #[cfg(feature = "regex")]
+    pub fn matches<K: Into<String>>(key: K, regex: regex::Regex) -> Self {
+        Self::key(key, move |found| match found {
+            Value::String(s) if regex.is_match(s) => Ok(()),
+            _ => Err(format!("is {found}, expected to match `{regex}`")),
+        })
+    }
+
+    /// Combines `self` with `other`, running both and reporting the errors
+    /// of both.
+    #[must_use]
+    pub fn and(self, other: Self) -> Self {
+        Self::new(move |value| {
+            match (self.validate(value), other.validate(value)) {
+                (Ok(()), Ok(())) => Ok(()),
+                (Err(errors), Ok(())) | (Ok(()), Err(errors)) => Err(errors),
+                (Err(mut errors), Err(more)) => {
+                    errors.extend(more);
+                    Err(errors)
+                }
+            }
+        })
+    }
+
+    /// Runs the validator against `value`.
+    ///
+    /// # Errors
+    ///
+    /// Returns every `ValidationError` found in `value`.
+    pub fn validate(&self, value: &Value) -> Result<(), Vec<ValidationError>> {
+        (self.check)(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> anyhow::Result<Value> {
+        Ok(Value::from_dotted([
+            ("server.port", Value::from(8080)),
+            ("server.workers", Value::from("4")),
+            ("log.level", Value::from("info")),
+            ("email", Value::from("admin@example.com")),
+        ])?)
+    }
+
+    fn keys(result: Result<(), Vec<ValidationError>>) -> Vec<String> {
+        result
+            .expect_err("the validation fails")
+            .into_iter()
+            .map(|e| e.key)
+            .collect()
+    }
+
+    #[test]
+    fn test_range() -> anyhow::Result<()> {
+        let config = config()?;
+        assert!(
+            Validator::range("server.port", 1..=65535)
+                .validate(&config)
+                .is_ok()
+        );
+        assert!(
+            Validator::range("server.workers", 1..=8)
+                .validate(&config)
+                .is_ok()
+        );
+        assert!(Validator::range("missing", 1..=8).validate(&config).is_ok());
+
+        let errors = Validator::range("server.port", 1..1024)
+            .validate(&config)
+            .expect_err("the port is out of range");
+        assert_eq!(errors, vec![ValidationError::new(
+            "server.port",
+            "is 8080, expected a value in 1..1024"
+        )]);
+        assert_eq!(
+            keys(Validator::range("log.level", 1..=8).validate(&config)),
+            vec!["log.level"]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_one_of() -> anyhow::Result<()> {
+        let config = config()?;
+        assert!(
+            Validator::one_of("log.level", ["debug", "info", "warn"])
+                .validate(&config)
+                .is_ok()
+        );
+
+        let errors = Validator::one_of("log.level", ["debug", "warn"])
+            .validate(&config)
+            .expect_err("the level is not allowed");
+        assert_eq!(
+            errors.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            vec!["`log.level` is info, expected one of debug, warn"]
+        );
+        Ok(())
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn test_matches() -> anyhow::Result<()> {
+        let config = config()?;
+        let email = regex::Regex::new(r"^[^@\s]+@[^@\s]+$")?;
+        assert!(
+            Validator::matches("email", email.clone())
+                .validate(&config)
+                .is_ok()
+        );
+        assert_eq!(
+            keys(Validator::matches("log.level", email).validate(&config)),
+            vec!["log.level"]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_required_and_composition() -> anyhow::Result<()> {
+        let config = config()?;
+        let validator = Validator::required("server.host")
+            .and(Validator::range("server.port", 1..=65535))
+            .and(Validator::one_of("log.level", ["warn"]));
+        assert_eq!(keys(validator.validate(&config)), vec![
+            "server.host",
+            "log.level"
+        ]);
+        Ok(())
+    }
+}
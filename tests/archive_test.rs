@@ -0,0 +1,140 @@
+#![cfg(all(feature = "archive", feature = "toml"))]
+
+use std::io::{
+    Cursor,
+    Write,
+};
+
+use realme::{
+    Error,
+    prelude::*,
+};
+
+const CONFIG: &str = r#"
+[server]
+host = "localhost"
+port = 8080
+"#;
+
+fn zip(entries: &[(&str, &str)]) -> anyhow::Result<Vec<u8>> {
+    let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    for (name, content) in entries {
+        writer.start_file(*name, zip::write::SimpleFileOptions::default())?;
+        writer.write_all(content.as_bytes())?;
+    }
+    Ok(writer.finish()?.into_inner())
+}
+
+#[test]
+fn test_zip_entry() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("bundle.zip");
+    std::fs::write(
+        &path,
+        zip(&[("README", "not a config"), ("config/app.toml", CONFIG)])?,
+    )?;
+
+    let realme = Realme::builder()
+        .load(Adaptor::new(ArchiveSource::<TomlParser>::new(
+            path,
+            "config/app.toml",
+        )))
+        .build()?;
+
+    assert_eq!(
+        realme.get_as::<String, _>("server.host").as_deref(),
+        Some("localhost")
+    );
+    assert_eq!(realme.get_as::<u16, _>("server.port"), Some(8080));
+    Ok(())
+}
+
+#[test]
+fn test_tar_entry() -> anyhow::Result<()> {
+    let mut builder = tar::Builder::new(Vec::new());
+    let mut header = tar::Header::new_gnu();
+    header.set_size(CONFIG.len() as u64);
+    header.set_mode(0o644);
+    builder.append_data(&mut header, "config/app.toml", CONFIG.as_bytes())?;
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("bundle.tar");
+    std::fs::write(&path, builder.into_inner()?)?;
+
+    let realme = Realme::builder()
+        .load(Adaptor::new(ArchiveSource::<TomlParser>::new(
+            path,
+            "config/app.toml",
+        )))
+        .build()?;
+
+    assert_eq!(realme.get_as::<u16, _>("server.port"), Some(8080));
+    Ok(())
+}
+
+#[test]
+fn test_missing_entry() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("bundle.zip");
+    std::fs::write(&path, zip(&[("config/app.toml", CONFIG)])?)?;
+
+    let err = Realme::builder()
+        .load(Adaptor::new(ArchiveSource::<TomlParser>::new(
+            path,
+            "config/missing.toml",
+        )))
+        .build()
+        .expect_err("entry is missing");
+
+    assert!(matches!(err, Error::Unavailable(_)));
+    assert!(err.to_string().contains("`config/missing.toml`"));
+    assert!(err.to_string().contains("no such entry"));
+    Ok(())
+}
+
+#[test]
+fn test_optional_archive() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("bundle.zip");
+    std::fs::write(&path, zip(&[("config/app.toml", CONFIG)])?)?;
+    let build = |path: &std::path::Path, entry: &str| {
+        Realme::builder()
+            .load(
+                Adaptor::new(ArchiveSource::<TomlParser>::new(path, entry))
+                    .optional(),
+            )
+            .build()
+    };
+
+    let realme = build(&path, "config/missing.toml")?;
+    assert_eq!(realme.get_as::<u16, _>("server.port"), None);
+    build(&dir.path().join("missing.zip"), "config/app.toml")?;
+
+    std::fs::write(&path, b"PK\x03\x04 not a zip archive")?;
+    let err = build(&path, "config/app.toml")
+        .expect_err("a corrupt archive is not skipped");
+    assert!(matches!(err, Error::ArchiveError(_)), "{err}");
+    Ok(())
+}
+
+#[test]
+fn test_entry_size_limit() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("bundle.zip");
+    std::fs::write(&path, zip(&[("config/app.toml", CONFIG)])?)?;
+    let build = |max_size: usize| {
+        Realme::builder()
+            .load(Adaptor::new(
+                ArchiveSource::<TomlParser>::new(&path, "config/app.toml")
+                    .max_size(max_size as u64),
+            ))
+            .build()
+    };
+
+    let err = build(CONFIG.len() - 1).expect_err("entry is too large");
+    assert!(matches!(err, Error::ArchiveError(_)));
+    assert!(err.to_string().contains("exceeds the limit"), "{err}");
+
+    let realme = build(CONFIG.len())?;
+    assert_eq!(realme.get_as::<u16, _>("server.port"), Some(8080));
+    Ok(())
+}
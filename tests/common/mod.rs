@@ -0,0 +1,67 @@
+//! Helpers shared by the integration tests.
+#![allow(dead_code)]
+
+use std::{
+    io::{
+        BufRead,
+        BufReader,
+        Read,
+        Write,
+    },
+    net::TcpListener,
+    sync::mpsc,
+    thread,
+};
+
+/// A request received by the fake server of `serve`.
+pub struct Request {
+    pub line:    String,
+    pub headers: Vec<String>,
+    pub body:    String,
+}
+
+/// Serves one response per entry of `responses`, as `(status, body)`, and
+/// sends every request it receives.
+///
+/// Returns the base url of the server, e.g. `http://127.0.0.1:1234`.
+pub fn serve(
+    responses: Vec<(u16, &'static str)>,
+) -> anyhow::Result<(String, mpsc::Receiver<Request>)> {
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let addr = format!("http://{}", listener.local_addr()?);
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || -> std::io::Result<()> {
+        for (status, body) in responses {
+            let (mut stream, _) = listener.accept()?;
+            let mut reader = BufReader::new(&stream);
+            let mut line = String::new();
+            reader.read_line(&mut line)?;
+            let headers: Vec<String> = reader
+                .by_ref()
+                .lines()
+                .map_while(Result::ok)
+                .take_while(|line| !line.is_empty())
+                .collect();
+            let length = headers
+                .iter()
+                .find_map(|header| header.strip_prefix("Content-Length: "))
+                .and_then(|length| length.parse().ok())
+                .unwrap_or(0);
+            let mut request_body = vec![0; length];
+            reader.read_exact(&mut request_body)?;
+            let _ = sender.send(Request {
+                line: line.trim_end().to_string(),
+                headers,
+                body: String::from_utf8_lossy(&request_body).into_owned(),
+            });
+            write!(
+                stream,
+                "HTTP/1.1 {status} Status\r\nContent-Length: \
+                 {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )?;
+        }
+        Ok(())
+    });
+    Ok((addr, receiver))
+}
@@ -0,0 +1,62 @@
+#![cfg(all(feature = "compat-config", feature = "toml"))]
+
+use realme::{
+    compat::ConfigBuilder,
+    prelude::*,
+};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Settings {
+    debug:  bool,
+    server: Server,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Server {
+    host:    String,
+    port:    u16,
+    workers: u32,
+}
+
+#[test]
+fn test_add_source_set_override_build() -> anyhow::Result<()> {
+    let realme = ConfigBuilder::new()
+        .set_default("server.workers", 4)?
+        .set_default("debug", false)?
+        .add_source(Adaptor::new(StringSource::<TomlParser>::new(
+            "[server]\nhost = \"example.com\"\nport = 80\n",
+        )))
+        .add_source(Adaptor::new(StringSource::<TomlParser>::new(
+            "[server]\nport = 8080\n",
+        )))
+        .set_override("server.host", "localhost")?
+        .set_override("debug", true)?
+        .build()?;
+
+    assert_eq!(realme.try_deserialize::<Settings>()?, Settings {
+        debug:  true,
+        server: Server {
+            host:    "localhost".to_string(),
+            port:    8080,
+            workers: 4,
+        },
+    });
+    Ok(())
+}
+
+#[test]
+fn test_overrides_survive_reload() -> anyhow::Result<()> {
+    let mut realme = ConfigBuilder::new()
+        .add_source(Adaptor::new(StringSource::<TomlParser>::new(
+            "name = \"from-source\"\n",
+        )))
+        .set_override("name", "overridden")?
+        .build()?;
+    realme.reload()?;
+    assert_eq!(
+        realme.get_as::<String, _>("name").as_deref(),
+        Some("overridden")
+    );
+    Ok(())
+}
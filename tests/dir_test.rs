@@ -0,0 +1,109 @@
+#![cfg(feature = "toml")]
+
+use realme::prelude::*;
+
+#[test]
+fn dir_source_merges_in_name_order() -> anyhow::Result<()> {
+    let realme = Realme::builder()
+        .load(Adaptor::new(DirSource::<TomlParser>::glob(
+            "./tests/source/conf.d/*.toml",
+        )))
+        .build()?;
+
+    assert_eq!(realme.get("name"), Some(&Value::from("override")));
+    assert_eq!(realme.get("server.host"), Some(&Value::from("0.0.0.0")));
+    assert_eq!(realme.get("server.port"), Some(&Value::Integer(9090)));
+    assert_eq!(realme.get("log.level"), Some(&Value::from("debug")));
+    Ok(())
+}
+
+#[test]
+fn dir_source_names_the_file_of_each_key() -> anyhow::Result<()> {
+    let realme = Realme::builder()
+        .load(Adaptor::new(DirSource::<TomlParser>::glob(
+            "./tests/source/conf.d/*.toml",
+        )))
+        .build()?;
+
+    let file = |key: &str| {
+        realme
+            .origin(key)
+            .and_then(|origin| origin.path.as_ref())
+            .and_then(|path| path.file_name())
+            .and_then(|name| name.to_str())
+            .map(ToString::to_string)
+    };
+    assert_eq!(file("server.host").as_deref(), Some("10-base.toml"));
+    assert_eq!(file("server.port").as_deref(), Some("20-local.toml"));
+    assert_eq!(file("name").as_deref(), Some("30-name.toml"));
+    Ok(())
+}
+
+#[test]
+fn dir_source_uses_the_merge_strategy() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    std::fs::write(dir.path().join("a.toml"), "hosts = [\"a\"]\nport = 1")?;
+    std::fs::write(dir.path().join("b.toml"), "hosts = [\"b\"]")?;
+    let realme = Realme::builder()
+        .load(
+            Adaptor::new(DirSource::<TomlParser>::new(dir.path()))
+                .with_merge_strategy(MergeStrategy::Deep {
+                    append_arrays: true,
+                    null_erases:   false,
+                }),
+        )
+        .build()?;
+    assert_eq!(
+        realme.get_as::<Vec<String>, _>("hosts"),
+        Some(vec!["a".to_string(), "b".to_string()])
+    );
+    assert_eq!(realme.get("port"), Some(&Value::Integer(1)));
+    Ok(())
+}
+
+#[test]
+fn dir_source_filters_files() -> anyhow::Result<()> {
+    let realme = Realme::builder()
+        .load(Adaptor::new(
+            DirSource::<TomlParser>::new("./tests/source/conf.d")
+                .pattern("?0-*.toml"),
+        ))
+        .build()?;
+    assert_eq!(realme.get("name"), Some(&Value::from("override")));
+
+    let realme = Realme::builder()
+        .load(Adaptor::new(
+            DirSource::<TomlParser>::new("./tests/source/conf.d").pattern("1*"),
+        ))
+        .build()?;
+    assert_eq!(realme.get("name"), Some(&Value::from("base")));
+    assert_eq!(realme.get("log"), None);
+    Ok(())
+}
+
+#[test]
+fn dir_source_picks_up_new_files() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    std::fs::write(dir.path().join("a.toml"), "port = 1")?;
+    let mut realme = Realme::builder()
+        .load(Adaptor::new(DirSource::<TomlParser>::new(dir.path())))
+        .build()?;
+    assert_eq!(realme.get("port"), Some(&Value::Integer(1)));
+
+    std::fs::write(dir.path().join("b.toml"), "port = 2")?;
+    realme.reload()?;
+    assert_eq!(realme.get("port"), Some(&Value::Integer(2)));
+    Ok(())
+}
+
+#[test]
+fn dir_source_reports_missing_directory() {
+    let err = Realme::builder()
+        .load(Adaptor::new(DirSource::<TomlParser>::new(
+            "./tests/source/missing.d",
+        )))
+        .build()
+        .expect_err("the directory is missing")
+        .to_string();
+    assert!(err.contains("missing.d"), "{err}");
+}
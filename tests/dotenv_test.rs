@@ -0,0 +1,100 @@
+#![cfg(feature = "dotenv")]
+use realme::prelude::*;
+use serde::Deserialize;
+
+#[test]
+fn dotenv_parse() -> anyhow::Result<()> {
+    let value = DotenvParser::parse(
+        r#"
+        # database
+        export DB_HOST = localhost # inline comment
+        DB_PASS='p@ss#word'
+        GREETING="hello\n\"world\""
+        URL=http://example.com/#anchor
+        EMPTY=
+        DB_HOST=example.com
+        "#,
+    )?;
+    assert_eq!(value.get("DB_HOST"), Some(&Value::from("example.com")));
+    assert_eq!(value.get("DB_PASS"), Some(&Value::from("p@ss#word")));
+    assert_eq!(
+        value.get("GREETING"),
+        Some(&Value::from("hello\n\"world\""))
+    );
+    assert_eq!(
+        value.get("URL"),
+        Some(&Value::from("http://example.com/#anchor"))
+    );
+    assert_eq!(value.get("EMPTY"), Some(&Value::from("")));
+    Ok(())
+}
+
+#[test]
+fn dotenv_parse_errors() {
+    for (input, cause) in [
+        ("NAME", "line 1, error: expected `KEY=value`"),
+        ("\n=value", "line 2, error: invalid key ``"),
+        ("MY KEY=value", "line 1, error: invalid key `MY KEY`"),
+        ("NAME=\"value", "line 1, error: unterminated quoted value"),
+        ("NAME='value' extra", "line 1, error: unexpected `extra`"),
+    ] {
+        let err = DotenvParser::parse(input).expect_err(input).to_string();
+        assert!(err.contains(cause), "{input}: {err}");
+    }
+}
+
+#[test]
+fn dotenv_parse_error_hides_content() {
+    let input = "TOKEN=secret\nBROKEN";
+    let err = DotenvParser::parse(input).expect_err(input).to_string();
+    assert!(err.contains("line 2"), "{err}");
+    assert!(!err.contains("secret"), "{err}");
+}
+
+#[test]
+fn dotenv_nested_keys() -> anyhow::Result<()> {
+    let value = NestedDotenvParser::parse("DB__POOL__SIZE=4\nLOG=x\n")?;
+    assert_eq!(value.get("db.pool.size"), Some(&Value::from("4")));
+    assert_eq!(value.get("log"), Some(&Value::from("x")));
+
+    let value = NestedDotenvParser::parse("DB=x\nDB__HOST=localhost\n")?;
+    assert_eq!(value.get("db"), Some(&Value::from("x")));
+    Ok(())
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Config {
+    app_name: String,
+    app:      App,
+    db:       Database,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct App {
+    port: u16,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Database {
+    host:     String,
+    password: String,
+    cert:     String,
+}
+
+#[test]
+fn dotenv_file_source() -> anyhow::Result<()> {
+    let realme = Realme::builder()
+        .load(Adaptor::new(FileSource::dotenv("./tests/source/test.env")))
+        .build()?;
+    let config = realme.try_deserialize::<Config>()?;
+    assert_eq!(config, Config {
+        app_name: "demo app".to_string(),
+        app:      App { port: 8080 },
+        db:       Database {
+            host:     "localhost".to_string(),
+            password: "p@ss#word".to_string(),
+            cert:     "-----BEGIN-----\nabc\n-----END-----".to_string(),
+        },
+    });
+    Ok(())
+}
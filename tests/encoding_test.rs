@@ -0,0 +1,48 @@
+#![cfg(all(feature = "encoding", feature = "toml"))]
+
+use realme::prelude::*;
+
+#[test]
+fn test_latin1_file() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("config.toml");
+    // "café" and "Zürich" encoded in Latin-1, which is not valid UTF-8.
+    std::fs::write(
+        &path,
+        b"name = \"caf\xe9\"\ncity = \"Z\xfcrich\"\n".as_slice(),
+    )?;
+
+    let realme = Realme::builder()
+        .load(Adaptor::new(
+            FileSource::<TomlParser>::new(&path).with_encoding("latin1"),
+        ))
+        .build()?;
+    assert_eq!(realme.get_as::<String, _>("name").as_deref(), Some("café"));
+    assert_eq!(
+        realme.get_as::<String, _>("city").as_deref(),
+        Some("Zürich")
+    );
+
+    let utf8 = Realme::builder()
+        .load(Adaptor::new(FileSource::<TomlParser>::new(&path)))
+        .build();
+    assert!(matches!(utf8, Err(realme::Error::ReadFileError(_))));
+    Ok(())
+}
+
+#[test]
+fn test_unknown_encoding() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("config.toml");
+    std::fs::write(&path, "name = \"realme\"\n")?;
+
+    let err = Realme::builder()
+        .load(Adaptor::new(
+            FileSource::<TomlParser>::new(&path).with_encoding("klingon"),
+        ))
+        .build()
+        .expect_err("the encoding is unknown")
+        .to_string();
+    assert!(err.contains("unknown encoding `klingon`"), "{err}");
+    Ok(())
+}
@@ -0,0 +1,96 @@
+#![cfg(all(feature = "git", feature = "toml"))]
+use std::{
+    path::Path,
+    process::Command,
+};
+
+use realme::{
+    Error,
+    prelude::*,
+};
+
+fn git(dir: &Path, args: &[&str]) -> anyhow::Result<()> {
+    let status = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["-c", "user.name=realme", "-c", "user.email=realme@test"])
+        .args(args)
+        .status()?;
+    anyhow::ensure!(status.success(), "git {args:?} failed");
+    Ok(())
+}
+
+fn commit(work: &Path, content: &str) -> anyhow::Result<()> {
+    std::fs::write(work.join("config.toml"), content)?;
+    git(work, &["add", "config.toml"])?;
+    git(work, &["commit", "--quiet", "-m", "update config"])?;
+    git(work, &["push", "--quiet", "origin", "HEAD:refs/heads/main"])
+}
+
+#[test]
+fn git_source_reads_file_at_ref() -> anyhow::Result<()> {
+    let root = tempfile::tempdir()?;
+    let bare = root.path().join("config.git");
+    let work = root.path().join("work");
+    std::fs::create_dir_all(&bare)?;
+    std::fs::create_dir_all(&work)?;
+    git(&bare, &["init", "--quiet", "--bare"])?;
+    git(&work, &["init", "--quiet"])?;
+    git(&work, &["remote", "add", "origin", &bare.to_string_lossy()])?;
+    commit(&work, "[server]\nport = 8080\n")?;
+
+    let url = format!("file://{}", bare.display());
+    let mut realme = Realme::builder()
+        .load(Adaptor::new(
+            GitSource::<TomlParser>::new(url, "main", "config.toml")
+                .shallow(true)
+                .cache_dir(root.path().join("cache")),
+        ))
+        .build()?;
+    assert_eq!(realme.get_as::<u16, _>("server.port"), Some(8080));
+
+    commit(&work, "[server]\nport = 9090\n")?;
+    realme.reload()?;
+    assert_eq!(realme.get_as::<u16, _>("server.port"), Some(9090));
+    Ok(())
+}
+
+#[test]
+fn git_source_reports_fetch_errors() -> anyhow::Result<()> {
+    let root = tempfile::tempdir()?;
+    let bare = root.path().join("config.git");
+    std::fs::create_dir_all(&bare)?;
+    git(&bare, &["init", "--quiet", "--bare"])?;
+
+    let result = Realme::builder()
+        .load(Adaptor::new(
+            GitSource::<TomlParser>::new(
+                bare.to_string_lossy(),
+                "missing",
+                "config.toml",
+            )
+            .cache_dir(root.path().join("cache")),
+        ))
+        .build();
+    let err = result.expect_err("ref does not exist");
+    assert!(matches!(err, Error::GitError(_)));
+    assert!(err.to_string().contains("failed to fetch"));
+    Ok(())
+}
+
+#[test]
+fn git_source_does_not_read_url_as_option() -> anyhow::Result<()> {
+    let root = tempfile::tempdir()?;
+    let marker = root.path().join("marker");
+    let url = format!("--upload-pack=touch {}", marker.display());
+
+    let result = Realme::builder()
+        .load(Adaptor::new(
+            GitSource::<TomlParser>::new(url, "main", "config.toml")
+                .cache_dir(root.path().join("cache")),
+        ))
+        .build();
+    assert!(matches!(result, Err(Error::GitError(_))));
+    assert!(!marker.exists());
+    Ok(())
+}
@@ -0,0 +1,49 @@
+#![cfg(all(feature = "gzip", feature = "json"))]
+
+use std::io::Write;
+
+use flate2::{
+    Compression,
+    write::GzEncoder,
+};
+use realme::prelude::*;
+
+fn gzip(content: &str) -> anyhow::Result<Vec<u8>> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(content.as_bytes())?;
+    Ok(encoder.finish()?)
+}
+
+const CONFIG: &str = r#"{ "server": { "host": "localhost", "port": 8080 } }"#;
+
+#[test]
+fn test_gzipped_json() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("config.json.gz");
+    std::fs::write(&path, gzip(CONFIG)?)?;
+
+    let realme = Realme::builder()
+        .load(Adaptor::new(FileSource::<JsonParser>::new(path)))
+        .build()?;
+
+    assert_eq!(
+        realme.get_as::<String, _>("server.host").as_deref(),
+        Some("localhost")
+    );
+    assert_eq!(realme.get_as::<u16, _>("server.port"), Some(8080));
+    Ok(())
+}
+
+#[test]
+fn test_gzip_detected_by_magic_bytes() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("config.json");
+    std::fs::write(&path, gzip(CONFIG)?)?;
+
+    let realme = Realme::builder()
+        .load(Adaptor::new(FileSource::<JsonParser>::new(path)))
+        .build()?;
+
+    assert_eq!(realme.get_as::<u16, _>("server.port"), Some(8080));
+    Ok(())
+}
@@ -0,0 +1,60 @@
+#![cfg(feature = "hcl")]
+
+use realme::prelude::*;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Server {
+    host: String,
+    port: u16,
+}
+
+#[test]
+fn hcl_parse() -> anyhow::Result<()> {
+    let realme = Realme::builder()
+        .load(Adaptor::new(FileSource::<HclParser>::new(
+            "./tests/source/test.hcl",
+        )))
+        .build()?;
+
+    assert_eq!(realme.get_as::<String, _>("name"), Some("realme".into()));
+    assert_eq!(realme.get_as::<bool, _>("debug"), Some(true));
+    assert_eq!(realme.get_as::<f64, _>("ratio"), Some(0.75));
+    assert_eq!(
+        realme.get_as::<Vec<String>, _>("tags"),
+        Some(vec!["api".to_string(), "internal".to_string()])
+    );
+    assert_eq!(
+        realme.get_as::<Server, _>("server.http.public"),
+        Some(Server {
+            host: "0.0.0.0".to_string(),
+            port: 8080,
+        })
+    );
+    assert_eq!(
+        realme.get_as::<bool, _>("server.http.public.tls.enabled"),
+        Some(false)
+    );
+    assert_eq!(
+        realme.get_as::<u16, _>("server.grpc.internal.port"),
+        Some(9090)
+    );
+    assert_eq!(
+        realme.get_as::<Vec<String>, _>("backend[*].url"),
+        Some(vec![
+            "http://a.local".to_string(),
+            "http://b.local".to_string()
+        ])
+    );
+    assert_eq!(
+        realme.get_as::<String, _>("upstream"),
+        Some("${var.upstream_host}:443".to_string())
+    );
+    Ok(())
+}
+
+#[test]
+fn hcl_parse_error() {
+    let result = HclParser::parse("server \"web\" {\n  port = \n");
+    assert!(matches!(result, Err(realme::Error::ParseError { .. })));
+}
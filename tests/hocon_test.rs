@@ -0,0 +1,71 @@
+#![cfg(feature = "hocon")]
+
+use realme::prelude::*;
+
+#[test]
+fn hocon_parse_file() -> anyhow::Result<()> {
+    let value =
+        HoconParser::parse_file("./tests/source/hocon/application.conf")?;
+
+    assert_eq!(value.get("app.name"), Some(&Value::from("realme")));
+    assert_eq!(value.get("app.version"), Some(&Value::Float(1.5)));
+    assert_eq!(value.get("app.debug"), Some(&Value::Boolean(true)));
+    assert_eq!(
+        value.get("app.greeting"),
+        Some(&Value::from("hello realme"))
+    );
+    assert_eq!(value.get("http.port"), Some(&Value::Integer(8080)));
+    assert_eq!(value.get("http.timeout"), Some(&Value::Integer(10)));
+    assert_eq!(value.get("http.retries"), Some(&Value::Integer(3)));
+    assert_eq!(
+        value.get("database.url"),
+        Some(&Value::from("postgres://localhost/app"))
+    );
+    assert_eq!(value.get("database.pool.size"), Some(&Value::Integer(20)));
+    assert_eq!(value.get("database.pool.idle"), Some(&Value::Integer(2)));
+    assert_eq!(value.get("metrics.endpoint"), Some(&Value::Null));
+    assert_eq!(
+        value.get("tags"),
+        Some(&Value::from(vec!["api", "internal"]))
+    );
+    Ok(())
+}
+
+#[test]
+fn hocon_file_source_resolves_includes() -> anyhow::Result<()> {
+    let realme = Realme::builder()
+        .load(Adaptor::new(FileSource::<HoconParser>::new(
+            "./tests/source/hocon/application.conf",
+        )))
+        .build()?;
+    assert_eq!(
+        realme.get_as::<String, _>("app.name").as_deref(),
+        Some("realme")
+    );
+    assert_eq!(realme.get_as::<u16, _>("database.pool.idle"), Some(2));
+    Ok(())
+}
+
+#[test]
+fn hocon_string_source() -> anyhow::Result<()> {
+    let realme = Realme::builder()
+        .load(Adaptor::new(StringSource::<HoconParser>::new(
+            "server { host = localhost, port = 8080 }\nserver.port = 9090",
+        )))
+        .build()?;
+    assert_eq!(realme.get_as::<u16, _>("server.port"), Some(9090));
+    assert_eq!(
+        realme.get_as::<String, _>("server.host"),
+        Some("localhost".to_string())
+    );
+    Ok(())
+}
+
+#[test]
+fn hocon_unresolved_substitution() {
+    let result = HoconParser::parse("a = ${REALME_HOCON_TEST_UNSET_KEY}");
+    let err = result
+        .expect_err("the substitution is unresolved")
+        .to_string();
+    assert!(err.contains("`a`"), "{err}");
+}
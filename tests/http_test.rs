@@ -0,0 +1,54 @@
+#![cfg(all(feature = "http", feature = "toml"))]
+mod common;
+
+use std::time::Duration;
+
+use common::serve;
+use realme::{
+    Error,
+    prelude::*,
+};
+
+#[test]
+fn http_source_parses_body_with_headers() -> anyhow::Result<()> {
+    let (addr, requests) = serve(vec![(200, "[server]\nport = 8080\n")])?;
+    let realme = Realme::builder()
+        .load(Adaptor::new(
+            HttpSource::<TomlParser>::new(format!("{addr}/config.toml"))
+                .header("Authorization", "Bearer secret")
+                .timeout(Duration::from_secs(5)),
+        ))
+        .build()?;
+    assert_eq!(realme.get_as::<u16, _>("server.port"), Some(8080));
+
+    let headers = requests.recv()?.headers;
+    assert!(headers.contains(&"Authorization: Bearer secret".to_string()));
+    Ok(())
+}
+
+#[test]
+fn http_source_retries_transient_failures() -> anyhow::Result<()> {
+    let (addr, requests) =
+        serve(vec![(503, "unavailable"), (200, "port = 9090\n")])?;
+    let value = HttpSource::<TomlParser>::new(format!("{addr}/config.toml"))
+        .retries(2)
+        .retry_delay(Duration::from_millis(10))
+        .parse()?;
+    assert_eq!(value.get("port"), Some(&Value::Integer(9090)));
+    assert_eq!(requests.iter().count(), 2);
+    Ok(())
+}
+
+#[test]
+fn http_source_fails_on_client_error() -> anyhow::Result<()> {
+    let (addr, requests) = serve(vec![(404, "not found")])?;
+    let err = HttpSource::<TomlParser>::new(format!("{addr}/config.toml"))
+        .retries(2)
+        .retry_delay(Duration::from_millis(10))
+        .parse()
+        .expect_err("404 is not retried");
+    assert!(matches!(err, Error::Unavailable(_)));
+    assert!(err.to_string().contains("responded with status 404"));
+    assert_eq!(requests.iter().count(), 1);
+    Ok(())
+}
@@ -49,3 +49,43 @@ fn ini_parse() -> anyhow::Result<()> {
     assert_eq!(config, expected);
     Ok(())
 }
+
+#[test]
+fn ini_parse_glob_section_and_repeated_keys() -> anyhow::Result<()> {
+    let ini = "
+root = true
+
+[*.rs]
+indent_style = space
+indent_size = 4
+
+[Unit]
+Description = Example service
+After = network.target
+After = syslog.target
+After = local-fs.target
+";
+    let realme = Realme::builder()
+        .load(Adaptor::new(StringSource::<IniParser>::new(ini)))
+        .build()?;
+
+    let sections =
+        realme.try_deserialize::<std::collections::HashMap<String, Value>>()?;
+    assert_eq!(
+        sections["*.rs"].get("indent_style"),
+        Some(&Value::String("space".to_string()))
+    );
+    assert_eq!(
+        realme.get_as::<String, _>("Unit.Description"),
+        Some("Example service".to_string())
+    );
+    assert_eq!(
+        realme.get_as::<Vec<String>, _>("Unit.After"),
+        Some(vec![
+            "network.target".to_string(),
+            "syslog.target".to_string(),
+            "local-fs.target".to_string(),
+        ])
+    );
+    Ok(())
+}
@@ -181,3 +181,90 @@ fn json_parse() -> anyhow::Result<()> {
     assert_eq!(config, expected);
     Ok(())
 }
+
+const DUPLICATED: &str = r#"
+{
+    "server": { "port": 8080, "host": "localhost", "port": 9090 },
+    "name": "first",
+    "name": "last"
+}
+"#;
+
+#[test]
+fn json_duplicate_keys_error() {
+    let err = JsonParser::parse_with(DUPLICATED, DuplicateKeys::Error)
+        .expect_err("duplicate keys are rejected");
+    assert!(err.to_string().contains("duplicate key `port`"));
+
+    let err = Realme::builder()
+        .load(Adaptor::new(StringSource::<StrictJsonParser>::new(
+            DUPLICATED,
+        )))
+        .build()
+        .expect_err("duplicate keys are rejected");
+    assert!(err.to_string().contains("duplicate key"));
+}
+
+#[test]
+fn json_duplicate_keys_keep_first() -> anyhow::Result<()> {
+    let value = JsonParser::parse_with(DUPLICATED, DuplicateKeys::KeepFirst)?;
+    assert_eq!(value["server"]["port"], 8080);
+    assert_eq!(value["server"]["host"], "localhost");
+    assert_eq!(value["name"], "first");
+    Ok(())
+}
+
+#[test]
+fn json_duplicate_keys_keep_last() -> anyhow::Result<()> {
+    let value = JsonParser::parse_with(DUPLICATED, DuplicateKeys::KeepLast)?;
+    assert_eq!(value["server"]["port"], 9090);
+    assert_eq!(value["name"], "last");
+    assert_eq!(value, JsonParser::parse(DUPLICATED)?);
+    Ok(())
+}
+
+const LARGE_NUMBERS: &str = r#"
+{
+    "id": 18446744073709551616,
+    "ratio": -1.5e400,
+    "name": "12345678901234567890",
+    "port": 8080
+}
+"#;
+
+#[test]
+fn json_large_numbers_error() {
+    let err = JsonParser::parse(LARGE_NUMBERS)
+        .expect_err("large numbers are rejected");
+    assert!(err.to_string().contains(
+        "number `18446744073709551616` is out of range for a 64-bit integer"
+    ));
+
+    let err = JsonParser::parse(r#"{"ratio": 1e400}"#)
+        .expect_err("large numbers are rejected");
+    assert!(
+        err.to_string()
+            .contains("number `1e400` is out of range for a 64-bit float")
+    );
+}
+
+#[test]
+fn json_large_numbers_string() -> anyhow::Result<()> {
+    let value = LosslessJsonParser::parse(LARGE_NUMBERS)?;
+    assert_eq!(value["id"], "18446744073709551616");
+    assert_eq!(value["ratio"], "-1.5e400");
+    assert_eq!(value["name"], "12345678901234567890");
+    assert_eq!(value["port"], 8080);
+
+    let realme = Realme::builder()
+        .load(Adaptor::new(StringSource::<LosslessJsonParser>::new(
+            LARGE_NUMBERS,
+        )))
+        .build()?;
+    assert_eq!(
+        realme.get_as::<String, _>("id"),
+        Some("18446744073709551616".to_string())
+    );
+    assert_eq!(realme.get_as::<i64, _>("port"), Some(8080));
+    Ok(())
+}
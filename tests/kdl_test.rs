@@ -0,0 +1,64 @@
+#![cfg(feature = "kdl")]
+
+use realme::prelude::*;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Backend {
+    url: String,
+}
+
+#[test]
+fn kdl_parse() -> anyhow::Result<()> {
+    let realme = Realme::builder()
+        .load(Adaptor::new(FileSource::<KdlParser>::new(
+            "./tests/source/test.kdl",
+        )))
+        .build()?;
+
+    assert_eq!(realme.get("name"), Some(&Value::from("realme")));
+    assert_eq!(realme.get("version"), Some(&Value::Float(1.5)));
+    assert_eq!(realme.get("debug"), Some(&Value::Boolean(true)));
+    assert_eq!(realme.get("maintenance"), Some(&Value::Null));
+    assert_eq!(
+        realme.get("tags"),
+        Some(&Value::from(vec!["api", "internal"]))
+    );
+    assert_eq!(realme.get("server.-"), Some(&Value::from("web")));
+    assert_eq!(realme.get("server.host"), Some(&Value::from("0.0.0.0")));
+    assert_eq!(realme.get("server.port"), Some(&Value::Integer(8080)));
+    assert_eq!(
+        realme.get("server.tls.enabled"),
+        Some(&Value::Boolean(false))
+    );
+    assert_eq!(
+        realme.get("hosts"),
+        Some(&Value::from(vec!["a.local", "b.local"]))
+    );
+    assert_eq!(
+        realme.get_as::<Vec<Backend>, _>("backend"),
+        Some(vec![
+            Backend {
+                url: "http://a.local".to_string(),
+            },
+            Backend {
+                url: "http://b.local".to_string(),
+            },
+        ])
+    );
+    assert_eq!(realme.get("started"), Some(&Value::from("2024-02-20")));
+    Ok(())
+}
+
+#[test]
+fn kdl_parse_errors() {
+    for (input, cause) in [
+        ("server {", ""),
+        ("big 99999999999999999999", "`big`: integer"),
+    ] {
+        let err = KdlParser::parse(input)
+            .expect_err("the input is invalid")
+            .to_string();
+        assert!(!err.is_empty() && err.contains(cause), "{input:?}: {err}");
+    }
+}
@@ -0,0 +1,25 @@
+#![cfg(feature = "macros")]
+
+use realme::prelude::*;
+
+#[test]
+fn test_key_macro_matches_parsed_key() -> anyhow::Result<()> {
+    assert_eq!(key!("name"), "name".parse::<Expression>()?);
+    assert_eq!(key!("hosts[-1]"), "hosts[-1]".parse::<Expression>()?);
+    assert_eq!(
+        key!("servers[0].tls.cert-path"),
+        "servers[0].tls.cert-path".parse::<Expression>()?
+    );
+
+    let value = Value::from_dotted([("server.port", 8080)])?;
+    assert_eq!(value.get(key!("server.port")), Some(&Value::Integer(8080)));
+    Ok(())
+}
+
+#[test]
+fn test_key_macro_ui() {
+    let cases = trybuild::TestCases::new();
+    cases.pass("tests/ui/key_valid.rs");
+    cases.pass("tests/ui/key_without_prelude.rs");
+    cases.compile_fail("tests/ui/key_invalid.rs");
+}
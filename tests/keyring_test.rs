@@ -0,0 +1,71 @@
+#![cfg(feature = "keyring")]
+
+use std::sync::Once;
+
+use keyring_core::{
+    Entry,
+    mock,
+};
+use realme::{
+    Error,
+    prelude::*,
+};
+
+/// Installs the mock store, shared by every test of this file.
+fn mock_store() {
+    static INIT: Once = Once::new();
+    INIT.call_once(|| {
+        if let Ok(store) = mock::Store::new() {
+            keyring_core::set_default_store(store);
+        }
+    });
+}
+
+fn set_secret(service: &str, key: &str, secret: &str) -> anyhow::Result<()> {
+    Entry::new(service, key)?.set_password(secret)?;
+    Ok(())
+}
+
+#[test]
+fn test_keyring_secrets() -> anyhow::Result<()> {
+    mock_store();
+    set_secret("realme-secrets", "db.password", "hunter2")?;
+    set_secret("realme-secrets", "api_token", "t0k3n")?;
+
+    let realme = Realme::builder()
+        .load(Adaptor::new(KeyringSource::new("realme-secrets", &[
+            "db.password",
+            "api_token",
+            "db.missing",
+        ])))
+        .build()?;
+
+    assert_eq!(
+        realme.get_as::<String, _>("db.password").as_deref(),
+        Some("hunter2")
+    );
+    assert_eq!(
+        realme.get_as::<String, _>("api_token").as_deref(),
+        Some("t0k3n")
+    );
+    assert_eq!(realme.get("db.missing"), None);
+    Ok(())
+}
+
+#[test]
+fn test_keyring_required_entry() -> anyhow::Result<()> {
+    mock_store();
+    set_secret("realme-required", "present", "yes")?;
+
+    let err = Realme::builder()
+        .load(Adaptor::new(
+            KeyringSource::new("realme-required", &["present", "absent"])
+                .required(true),
+        ))
+        .build()
+        .expect_err("the entry is missing");
+
+    assert!(matches!(err, Error::Unavailable(_)));
+    assert!(err.to_string().contains("`absent`"));
+    Ok(())
+}
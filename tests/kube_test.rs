@@ -0,0 +1,94 @@
+#![cfg(feature = "kube")]
+
+use std::{
+    fs,
+    path::Path,
+};
+
+use realme::prelude::*;
+
+#[test]
+fn kube_plain_dir() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    fs::write(dir.path().join("log_level"), "debug\n")?;
+    fs::write(dir.path().join("app.properties"), "port=8080")?;
+    fs::write(dir.path().join(".hidden"), "skipped")?;
+    fs::create_dir(dir.path().join("nested"))?;
+
+    let realme = Realme::builder()
+        .load(Adaptor::new(KubeDirSource::new(dir.path())))
+        .build()?;
+    assert_eq!(realme.get("log_level"), Some(&Value::from("debug\n")));
+    let table = realme.try_deserialize::<Map<String, String>>()?;
+    assert_eq!(table.len(), 2);
+    assert_eq!(
+        table.get("app.properties").map(String::as_str),
+        Some("port=8080")
+    );
+
+    let realme = Realme::builder()
+        .load(Adaptor::new(KubeDirSource::new(dir.path()).trim_values()))
+        .build()?;
+    assert_eq!(realme.get("log_level"), Some(&Value::from("debug")));
+    Ok(())
+}
+
+#[test]
+fn kube_missing_dir() {
+    let err = Realme::builder()
+        .load(Adaptor::new(KubeDirSource::new("/nonexistent/realme/kube")))
+        .build()
+        .expect_err("missing directory")
+        .to_string();
+    assert!(err.contains("/nonexistent/realme/kube"), "{err}");
+}
+
+#[cfg(unix)]
+fn write_version(
+    dir: &Path,
+    version: &str,
+    files: &[(&str, &str)],
+) -> anyhow::Result<()> {
+    use std::os::unix::fs::symlink;
+
+    let data = dir.join(version);
+    fs::create_dir(&data)?;
+    for (key, content) in files {
+        fs::write(data.join(key), content)?;
+        let link = dir.join(key);
+        if fs::symlink_metadata(&link).is_err() {
+            symlink(Path::new("..data").join(key), link)?;
+        }
+    }
+    // Swap `..data` atomically, as the kubelet does
+    let tmp = dir.join("..data_tmp");
+    symlink(version, &tmp)?;
+    fs::rename(tmp, dir.join("..data"))?;
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn kube_atomic_update() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    write_version(dir.path(), "..2024_01_01", &[
+        ("username", "admin"),
+        ("password", "hunter2"),
+    ])?;
+
+    let mut realme = Realme::builder()
+        .load(Adaptor::new(KubeDirSource::new(dir.path()).sensitive()))
+        .build()?;
+    assert_eq!(realme.get("password"), Some(&Value::from("hunter2")));
+    assert!(realme.is_sensitive("password"));
+    assert!(!realme.reload_if_changed()?);
+
+    write_version(dir.path(), "..2024_01_02", &[
+        ("username", "admin"),
+        ("password", "correct horse"),
+    ])?;
+    assert!(realme.reload_if_changed()?);
+    assert_eq!(realme.get("password"), Some(&Value::from("correct horse")));
+    assert_eq!(realme.get("..data"), None);
+    Ok(())
+}
@@ -0,0 +1,108 @@
+#![cfg(all(feature = "msgpack", feature = "toml"))]
+
+use realme::prelude::*;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct Config {
+    name:    String,
+    debug:   bool,
+    ratio:   f64,
+    tags:    Vec<String>,
+    server:  Server,
+    comment: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct Server {
+    host: String,
+    port: u16,
+}
+
+fn config() -> Config {
+    Config {
+        name:    "realme".to_string(),
+        debug:   true,
+        ratio:   0.5,
+        tags:    vec!["api".to_string(), "internal".to_string()],
+        server:  Server {
+            host: "localhost".to_string(),
+            port: 8080,
+        },
+        comment: None,
+    }
+}
+
+#[test]
+fn msgpack_bytes() -> anyhow::Result<()> {
+    let bytes = rmp_serde::to_vec_named(&config())?;
+    let realme = Realme::builder()
+        .load(Adaptor::new(BytesSource::<MsgPackParser>::new(bytes)))
+        .build()?;
+
+    assert_eq!(realme.get("server.port"), Some(&Value::Integer(8080)));
+    assert_eq!(realme.get("comment"), Some(&Value::Null));
+    assert_eq!(realme.try_deserialize::<Config>()?, config());
+    Ok(())
+}
+
+#[test]
+fn msgpack_file() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("snapshot.msgpack");
+    std::fs::write(&path, rmp_serde::to_vec_named(&config())?)?;
+
+    let realme = Realme::builder()
+        .load(Adaptor::new(BytesSource::<MsgPackParser>::file(&path)))
+        .load(Adaptor::new(StringSource::<TomlParser>::new(
+            "debug = false",
+        )))
+        .build()?;
+    assert_eq!(realme.get("name"), Some(&Value::from("realme")));
+    assert_eq!(realme.get("debug"), Some(&Value::Boolean(false)));
+    Ok(())
+}
+
+#[cfg(feature = "watch")]
+#[test]
+fn msgpack_file_watch() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("snapshot.msgpack");
+    std::fs::write(&path, rmp_serde::to_vec_named(&config())?)?;
+
+    let realme = Realme::shared_builder()
+        .load(Adaptor::new(BytesSource::<MsgPackParser>::file(&path)))
+        .shared_build()?;
+    assert_eq!(
+        realme.current()?.get_as::<u16, _>("server.port"),
+        Some(8080)
+    );
+
+    let mut updated = config();
+    updated.server.port = 9090;
+    std::fs::write(&path, rmp_serde::to_vec_named(&updated)?)?;
+    std::thread::sleep(std::time::Duration::from_secs(2));
+    assert_eq!(
+        realme.current()?.get_as::<u16, _>("server.port"),
+        Some(9090)
+    );
+    Ok(())
+}
+
+#[test]
+fn msgpack_errors() {
+    for bytes in [&b"\x82\xa4name"[..], b"\x81\x01\x02", b"\xc1"] {
+        assert!(MsgPackParser::parse(bytes).is_err(), "{bytes:?}");
+    }
+    let err = Realme::builder()
+        .load(Adaptor::new(BytesSource::<MsgPackParser>::file(
+            "./tests/source/missing.msgpack",
+        )))
+        .build()
+        .expect_err("the file is missing")
+        .to_string();
+    assert!(err.contains("missing.msgpack"), "{err}");
+}
@@ -0,0 +1,70 @@
+#![cfg(feature = "nginx")]
+use std::collections::HashMap;
+
+use realme::prelude::*;
+
+#[test]
+fn nginx_nested_blocks() -> anyhow::Result<()> {
+    let realme = Realme::builder()
+        .load(Adaptor::new(StringSource::<NginxParser>::new(
+            r#"
+            # reverse proxy
+            worker_processes 4;
+            http {
+                sendfile;
+                server {
+                    listen 80;
+                    server_name example.com "www.example.com";
+                    location /api {
+                        proxy_pass http://backend;
+                    }
+                }
+            }
+            "#,
+        )))
+        .build()?;
+
+    assert_eq!(realme.get("worker_processes"), Some(&Value::from("4")));
+    assert_eq!(realme.get_as::<u32, _>("worker_processes"), Some(4));
+    assert_eq!(realme.get("http.sendfile"), Some(&Value::Boolean(true)));
+    assert_eq!(realme.get("http.server.listen"), Some(&Value::from("80")));
+    assert_eq!(
+        realme.get("http.server.server_name"),
+        Some(&Value::Array(vec![
+            Value::from("example.com"),
+            Value::from("www.example.com")
+        ]))
+    );
+    let locations = realme
+        .get_as::<HashMap<String, HashMap<String, String>>, _>(
+            "http.server.location",
+        )
+        .expect("locations are parsed");
+    assert_eq!(locations["/api"]["proxy_pass"], "http://backend");
+    Ok(())
+}
+
+#[test]
+fn nginx_repeated_directive() -> anyhow::Result<()> {
+    let value = NginxParser::parse(
+        "server { listen 80; listen 443 ssl; listen 8080; }
+         server { listen 81; }",
+    )?;
+
+    assert_eq!(
+        value.get("server[0].listen"),
+        Some(&Value::Array(vec![
+            Value::from("80"),
+            Value::Array(vec![Value::from("443"), Value::from("ssl")]),
+            Value::from("8080"),
+        ]))
+    );
+    assert_eq!(value.get("server[1].listen"), Some(&Value::from("81")));
+    Ok(())
+}
+
+#[test]
+fn nginx_unterminated_directive() {
+    assert!(NginxParser::parse("http { listen 80 }").is_err());
+    assert!(NginxParser::parse("http { listen 80;").is_err());
+}
@@ -0,0 +1,69 @@
+#![cfg(feature = "properties")]
+use realme::prelude::*;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Server {
+    host: String,
+    port: u16,
+}
+
+#[test]
+fn properties_parse() -> anyhow::Result<()> {
+    let realme = Realme::builder()
+        .load(Adaptor::new(FileSource::<PropertiesParser>::new(
+            "./tests/source/test.properties",
+        )))
+        .build()?;
+
+    assert_eq!(realme.get("app.name"), Some(&Value::from("Realme")));
+    assert_eq!(
+        realme.get("app.description"),
+        Some(&Value::from("A layered configuration library"))
+    );
+    assert_eq!(
+        realme.get_as::<Server, _>("server"),
+        Some(Server {
+            host: "localhost".to_string(),
+            port: 8080,
+        })
+    );
+    assert_eq!(
+        realme.get("server.path with spaces"),
+        Some(&Value::from("/srv/app"))
+    );
+    assert_eq!(realme.get("greeting"), Some(&Value::from("café 😀")));
+    assert_eq!(
+        realme.get("paths.windows"),
+        Some(&Value::from("C:\\Program Files\\App"))
+    );
+    assert_eq!(realme.get("escaped=key"), Some(&Value::from("value:colon")));
+    assert_eq!(realme.get("tabs"), Some(&Value::from("a\tb")));
+    assert_eq!(realme.get("empty"), Some(&Value::from("")));
+    Ok(())
+}
+
+#[test]
+fn properties_parse_overrides_and_conflicts() -> anyhow::Result<()> {
+    let value = PropertiesParser::parse(
+        "db=plain\ndb.host=dropped\nport=1\nport=2\nlast=end\\",
+    )?;
+    assert_eq!(value.get("db"), Some(&Value::from("plain")));
+    assert_eq!(value.get("port"), Some(&Value::from("2")));
+    assert_eq!(value.get("last"), Some(&Value::from("end")));
+    Ok(())
+}
+
+#[test]
+fn properties_parse_errors() {
+    for (input, cause) in [
+        ("key=\\u12", "line 1: malformed \\u12 escape"),
+        ("\nkey=\\uZZZZ", "line 2: malformed \\uZZZZ escape"),
+        ("key=\\uD83D!", "line 1: unpaired surrogate \\uD83D"),
+    ] {
+        let err = PropertiesParser::parse(input)
+            .expect_err("the escape is malformed")
+            .to_string();
+        assert!(err.contains(cause), "{input:?}: {err}");
+    }
+}
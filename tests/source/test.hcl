@@ -0,0 +1,29 @@
+# Service configuration
+name    = "realme"
+debug   = true
+ratio   = 0.75
+tags    = ["api", "internal"]
+
+server "http" "public" {
+  host = "0.0.0.0"
+  port = 8080
+
+  tls {
+    enabled = false
+  }
+}
+
+server "grpc" "internal" {
+  host = "127.0.0.1"
+  port = 9090
+}
+
+backend {
+  url = "http://a.local"
+}
+
+backend {
+  url = "http://b.local"
+}
+
+upstream = "${var.upstream_host}:443"
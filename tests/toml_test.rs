@@ -174,3 +174,246 @@ fn toml_parse() -> anyhow::Result<()> {
     assert_eq!(my_config, expected);
     Ok(())
 }
+
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct ServerConfig {
+    pub name:    String,
+    pub port:    u16,
+    pub tags:    Vec<String>,
+    pub tls:     Option<Tls>,
+    #[serde(default)]
+    pub routes:  Vec<Route>,
+    pub weights: Vec<f64>,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct Tls {
+    pub cert: String,
+    pub key:  String,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct Route {
+    pub path:    String,
+    pub methods: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct Cluster {
+    pub servers: Vec<ServerConfig>,
+}
+
+#[test]
+fn toml_array_of_tables() -> anyhow::Result<()> {
+    let realme = Realme::builder()
+        .load(Adaptor::new(StringSource::<TomlParser>::new(
+            r#"
+            [[servers]]
+            name = "alpha"
+            port = 8080
+            tags = ["primary", "eu"]
+            weights = [0.5, 1]
+
+            [servers.tls]
+            cert = "/etc/tls/alpha.pem"
+            key = "/etc/tls/alpha.key"
+
+            [[servers.routes]]
+            path = "/api"
+            methods = ["GET", "POST"]
+
+            [[servers.routes]]
+            path = "/health"
+            methods = []
+
+            [[servers]]
+            name = "beta"
+            port = 8081
+            tags = []
+            weights = []
+            "#,
+        )))
+        .build()?;
+
+    let expected = vec![
+        ServerConfig {
+            name:    "alpha".to_string(),
+            port:    8080,
+            tags:    vec!["primary".to_string(), "eu".to_string()],
+            tls:     Some(Tls {
+                cert: "/etc/tls/alpha.pem".to_string(),
+                key:  "/etc/tls/alpha.key".to_string(),
+            }),
+            routes:  vec![
+                Route {
+                    path:    "/api".to_string(),
+                    methods: vec!["GET".to_string(), "POST".to_string()],
+                },
+                Route {
+                    path:    "/health".to_string(),
+                    methods: vec![],
+                },
+            ],
+            weights: vec![0.5, 1.0],
+        },
+        ServerConfig {
+            name:    "beta".to_string(),
+            port:    8081,
+            tags:    vec![],
+            tls:     None,
+            routes:  vec![],
+            weights: vec![],
+        },
+    ];
+
+    let cluster: Cluster = realme.try_deserialize()?;
+    assert_eq!(cluster.servers, expected);
+    assert_eq!(
+        realme.get_as::<Vec<ServerConfig>, _>("servers"),
+        Some(expected)
+    );
+    assert_eq!(
+        realme.get_as::<String, _>("servers[1].name").as_deref(),
+        Some("beta")
+    );
+    Ok(())
+}
+
+const LARGE_NUMBERS: &str = "
+id = 18_446_744_073_709_551_616
+ratio = 1e400
+port = 8080
+";
+
+#[test]
+fn toml_large_numbers_error() {
+    let err = TomlParser::parse(LARGE_NUMBERS)
+        .expect_err("large numbers are rejected");
+    assert!(err.to_string().contains(
+        "number `18_446_744_073_709_551_616` is out of range for a 64-bit \
+         integer"
+    ));
+
+    let err = TomlParser::parse("ratio = 1e400")
+        .expect_err("large numbers are rejected");
+    assert!(
+        err.to_string()
+            .contains("number `1e400` is out of range for a 64-bit float")
+    );
+}
+
+#[test]
+fn toml_large_numbers_string() -> anyhow::Result<()> {
+    let value = LosslessTomlParser::parse(LARGE_NUMBERS)?;
+    assert_eq!(value["id"].as_str(), Some("18_446_744_073_709_551_616"));
+    assert_eq!(value["ratio"].as_str(), Some("1e400"));
+    assert_eq!(value["port"].as_integer(), Some(8080));
+    Ok(())
+}
+
+fn value(realme: &Realme, key: &str) -> Value {
+    realme.get(key).cloned().unwrap_or_default()
+}
+
+const DATETIMES: &str = "
+odt = 1979-05-27T07:32:00-08:00
+ldt = 1979-05-27 07:32:00.5
+ld = 1979-05-27
+lt = 07:32:00
+";
+
+#[test]
+fn toml_datetime_round_trip() -> anyhow::Result<()> {
+    let realme = Realme::builder()
+        .load(Adaptor::new(StringSource::<TomlParser>::new(DATETIMES)))
+        .build()?;
+    for key in ["odt", "ldt", "ld", "lt"] {
+        assert_eq!(
+            realme.get(key).map(Value::kind),
+            Some(ValueKind::Datetime),
+            "{key}"
+        );
+    }
+    assert_eq!(realme.get_string("ldt")?, "1979-05-27T07:32:00.5");
+
+    let toml = realme.try_serialize_to::<TomlParser>()?;
+    assert_eq!(
+        toml,
+        "ld = 1979-05-27\nldt = 1979-05-27T07:32:00.5\nlt = 07:32:00\nodt = \
+         1979-05-27T07:32:00-08:00\n"
+    );
+    let reparsed = Realme::builder()
+        .load(Adaptor::new(StringSource::<TomlParser>::new(toml)))
+        .build()?;
+    assert_eq!(reparsed.get("odt"), realme.get("odt"));
+    Ok(())
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn toml_datetime_chrono() -> anyhow::Result<()> {
+    use chrono::{
+        DateTime,
+        FixedOffset,
+        NaiveDate,
+        NaiveDateTime,
+        NaiveTime,
+    };
+
+    #[derive(Debug, Deserialize)]
+    struct Dates {
+        odt: DateTime<FixedOffset>,
+        ldt: NaiveDateTime,
+        ld:  NaiveDate,
+        lt:  NaiveTime,
+    }
+
+    let realme = Realme::builder()
+        .load(Adaptor::new(StringSource::<TomlParser>::new(DATETIMES)))
+        .build()?;
+    let dates: Dates = realme.try_deserialize()?;
+    assert_eq!(dates.odt.to_rfc3339(), "1979-05-27T07:32:00-08:00");
+    assert_eq!(dates.ldt.to_string(), "1979-05-27 07:32:00.500");
+    assert_eq!(
+        dates.ld,
+        NaiveDate::from_ymd_opt(1979, 5, 27).expect("valid date")
+    );
+    assert_eq!(
+        dates.lt,
+        NaiveTime::from_hms_opt(7, 32, 0).expect("valid time")
+    );
+
+    let odt = DateTime::<FixedOffset>::try_from(value(&realme, "odt"))?;
+    assert_eq!(odt, dates.odt);
+    assert_eq!(Value::from(odt), value(&realme, "odt"));
+    assert_eq!(Value::from(dates.ld), value(&realme, "ld"));
+    assert!(NaiveDateTime::try_from(value(&realme, "odt")).is_err());
+    Ok(())
+}
+
+#[cfg(feature = "time")]
+#[test]
+fn toml_datetime_time() -> anyhow::Result<()> {
+    use time::{
+        Date,
+        Month,
+        OffsetDateTime,
+        PrimitiveDateTime,
+        Time,
+    };
+
+    let realme = Realme::builder()
+        .load(Adaptor::new(StringSource::<TomlParser>::new(DATETIMES)))
+        .build()?;
+    let odt = OffsetDateTime::try_from(value(&realme, "odt"))?;
+    assert_eq!(odt.offset().whole_hours(), -8);
+    let ldt = PrimitiveDateTime::try_from(value(&realme, "ldt"))?;
+    assert_eq!(ldt.millisecond(), 500);
+    let ld = Date::try_from(value(&realme, "ld"))?;
+    assert_eq!(ld, Date::from_calendar_date(1979, Month::May, 27)?);
+    let lt = Time::try_from(value(&realme, "lt"))?;
+    assert_eq!(lt, Time::from_hms(7, 32, 0)?);
+    assert_eq!(Value::from(ld), value(&realme, "ld"));
+    assert_eq!(Value::from(odt), value(&realme, "odt"));
+    Ok(())
+}
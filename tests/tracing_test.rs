@@ -0,0 +1,117 @@
+#![cfg(all(feature = "tracing", feature = "toml"))]
+
+use std::{
+    io::Write,
+    sync::{
+        Arc,
+        Mutex,
+    },
+};
+
+use realme::prelude::*;
+
+/// Writer collecting the formatted events in memory.
+#[derive(Clone, Default)]
+struct Capture(Arc<Mutex<Vec<u8>>>);
+
+impl Write for Capture {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0
+            .lock()
+            .map_err(|e| std::io::Error::other(e.to_string()))?
+            .extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Capture {
+    fn lines(&self) -> Vec<String> {
+        self.0
+            .lock()
+            .map(|buf| {
+                String::from_utf8_lossy(&buf)
+                    .lines()
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+fn capture<T>(f: impl FnOnce() -> T) -> (T, Vec<String>) {
+    let capture = Capture::default();
+    let writer = capture.clone();
+    let subscriber = tracing_subscriber::fmt()
+        .with_max_level(tracing::Level::DEBUG)
+        .with_ansi(false)
+        .with_writer(move || writer.clone())
+        .finish();
+    let result = tracing::subscriber::with_default(subscriber, f);
+    (result, capture.lines())
+}
+
+#[test]
+fn test_override_event_for_contested_key() -> anyhow::Result<()> {
+    let (realme, lines) = capture(|| {
+        Realme::builder()
+            .load(
+                Adaptor::new(StringSource::<TomlParser>::new(
+                    "[server]\nport = 8080\nhost = \"localhost\"\n",
+                ))
+                .name("base")
+                .priority(0),
+            )
+            .load(
+                Adaptor::new(StringSource::<TomlParser>::new(
+                    "[server]\nport = 9090\nhost = \"localhost\"\n",
+                ))
+                .name("override")
+                .priority(1),
+            )
+            .build()
+    });
+    assert_eq!(realme?.get_as::<u16, _>("server.port"), Some(9090));
+
+    let merged: Vec<_> = lines
+        .iter()
+        .filter(|line| line.contains("merging source"))
+        .collect();
+    assert_eq!(merged.len(), 2);
+    assert!(merged[0].contains("source=base"));
+    assert!(merged[1].contains("source=override"));
+
+    let overrides: Vec<_> = lines
+        .iter()
+        .filter(|line| line.contains("overriding key"))
+        .collect();
+    assert_eq!(overrides.len(), 1);
+    assert!(overrides[0].contains("key=\"server.port\""));
+    assert!(overrides[0].contains("source=\"override\""));
+    Ok(())
+}
+
+#[test]
+fn test_substitution_event() -> anyhow::Result<()> {
+    let (realme, lines) = capture(|| {
+        Realme::builder()
+            .load(Adaptor::new(StringSource::<TomlParser>::new(
+                "host = \"localhost\"\nurl = \"http://${host}\"\n",
+            )))
+            .with_key_references()
+            .build()
+    });
+    assert_eq!(
+        realme?.get_as::<String, _>("url").as_deref(),
+        Some("http://localhost")
+    );
+    assert!(lines.iter().any(|line| {
+        line.contains("substituting reference") &&
+            line.contains("key=\"url\"") &&
+            line.contains("reference=\"host\"")
+    }));
+    Ok(())
+}
@@ -0,0 +1,8 @@
+use realme::prelude::*;
+
+fn main() {
+    let _ = key!("server..port");
+    let _ = key!("servers[0.port");
+    let _ = key!("servers[first]");
+    let _ = key!("server port");
+}
@@ -0,0 +1,7 @@
+use realme::prelude::*;
+
+fn main() {
+    let _ = key!("server.port");
+    let _ = key!("servers[0].hosts[-1]");
+    let _ = key!("tls.cert_path");
+}
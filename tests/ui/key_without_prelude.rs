@@ -0,0 +1,4 @@
+fn main() {
+    let key: realme::Expression = realme::key!("servers[0].hosts[-1]");
+    assert_eq!(key, "servers[0].hosts[-1]".parse().expect("valid key"));
+}
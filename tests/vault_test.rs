@@ -0,0 +1,181 @@
+#![cfg(feature = "vault")]
+mod common;
+
+use std::{
+    sync::{
+        Arc,
+        Mutex,
+    },
+    time::Duration,
+};
+
+use common::serve;
+use realme::prelude::*;
+
+const SECRET: &str = r#"{"data": {"data": {"user": "app", "password": "hunter2"}, "metadata": {"version": 3}}, "lease_duration": 0}"#;
+
+const LOGIN: &str = r#"{"auth": {"client_token": "s.login", "lease_duration": 3600, "renewable": true}}"#;
+
+#[test]
+fn vault_source_reads_secret_with_token() -> anyhow::Result<()> {
+    let (addr, requests) = serve(vec![(200, SECRET)])?;
+    let realme = Realme::builder()
+        .load(Adaptor::new(
+            VaultSource::new(addr, "secret", "myapp/db")
+                .token("s.static")
+                .prefix("database.credentials"),
+        ))
+        .build()?;
+    assert_eq!(
+        realme.get_as::<String, _>("database.credentials.password"),
+        Some("hunter2".to_string())
+    );
+    assert_eq!(realme.redacted().get("database"), None);
+
+    let request = requests.recv()?;
+    assert_eq!(request.line, "GET /v1/secret/data/myapp/db HTTP/1.1");
+    assert!(
+        request
+            .headers
+            .contains(&"X-Vault-Token: s.static".to_string())
+    );
+    Ok(())
+}
+
+#[test]
+fn vault_source_logs_in_with_app_role_once() -> anyhow::Result<()> {
+    let (addr, requests) =
+        serve(vec![(200, LOGIN), (200, SECRET), (200, SECRET)])?;
+    let leases = Arc::new(Mutex::new(Vec::new()));
+    let hook_leases = Arc::clone(&leases);
+    let source = VaultSource::new(addr, "secret", "myapp/db")
+        .app_role("role", "secret-id")
+        .on_lease(move |lease| {
+            if let Ok(mut leases) = hook_leases.lock() {
+                leases.push(*lease);
+            }
+        });
+    assert_eq!(source.parse()?.get("user"), Some(&Value::from("app")));
+    source.parse()?;
+
+    let login = requests.recv()?;
+    assert_eq!(login.line, "POST /v1/auth/approle/login HTTP/1.1");
+    let body: serde_json::Value = serde_json::from_str(&login.body)?;
+    assert_eq!(
+        body,
+        serde_json::json!({ "role_id": "role", "secret_id": "secret-id" })
+    );
+    for request in requests.iter().take(2) {
+        assert!(request.line.starts_with("GET /v1/secret/data/myapp/db"));
+        assert!(
+            request
+                .headers
+                .contains(&"X-Vault-Token: s.login".to_string())
+        );
+    }
+    assert_eq!(
+        *leases.lock().map_err(|e| anyhow::anyhow!(e.to_string()))?,
+        vec![VaultLease {
+            kind:      VaultLeaseKind::Login,
+            duration:  Duration::from_hours(1),
+            renewable: true,
+        }]
+    );
+    Ok(())
+}
+
+#[test]
+fn vault_source_renews_expiring_token() -> anyhow::Result<()> {
+    let short_login = r#"{"auth": {"client_token": "s.short", "lease_duration": 30, "renewable": true}}"#;
+    let (addr, requests) = serve(vec![
+        (200, short_login),
+        (200, SECRET),
+        (200, LOGIN),
+        (200, SECRET),
+    ])?;
+    let kinds = Arc::new(Mutex::new(Vec::new()));
+    let hook_kinds = Arc::clone(&kinds);
+    let source = VaultSource::new(addr, "secret", "myapp/db")
+        .app_role("role", "secret-id")
+        .renew_before(Duration::from_mins(1))
+        .on_lease(move |lease| {
+            if let Ok(mut kinds) = hook_kinds.lock() {
+                kinds.push(lease.kind);
+            }
+        });
+    source.parse()?;
+    source.parse()?;
+
+    let lines: Vec<String> = requests
+        .iter()
+        .take(4)
+        .map(|request| request.line)
+        .collect();
+    assert_eq!(lines[2], "POST /v1/auth/token/renew-self HTTP/1.1");
+    assert_eq!(
+        *kinds.lock().map_err(|e| anyhow::anyhow!(e.to_string()))?,
+        vec![VaultLeaseKind::Login, VaultLeaseKind::Renewal]
+    );
+    Ok(())
+}
+
+#[test]
+fn vault_source_logs_in_again_when_token_is_refused() -> anyhow::Result<()> {
+    let (addr, requests) = serve(vec![
+        (200, LOGIN),
+        (200, SECRET),
+        (403, r#"{"errors": ["permission denied"]}"#),
+        (200, LOGIN),
+        (200, SECRET),
+    ])?;
+    let source = VaultSource::new(addr, "secret", "myapp/db")
+        .app_role("role", "secret-id");
+    source.parse()?;
+    assert_eq!(
+        source.parse()?.get("password"),
+        Some(&Value::from("hunter2"))
+    );
+    assert_eq!(requests.iter().take(5).count(), 5);
+    Ok(())
+}
+
+#[test]
+fn vault_source_logs_in_with_kubernetes() -> anyhow::Result<()> {
+    let (addr, requests) = serve(vec![(200, LOGIN), (200, SECRET)])?;
+    let dir = tempfile::tempdir()?;
+    let jwt_path = dir.path().join("token");
+    std::fs::write(&jwt_path, "eyJhbGciOi.jwt\n")?;
+    VaultSource::new(addr, "secret", "myapp/db")
+        .kubernetes("myapp")
+        .kubernetes_jwt_path(&jwt_path)
+        .auth_mount("k8s")
+        .namespace("team")
+        .parse()?;
+
+    let login = requests.recv()?;
+    assert_eq!(login.line, "POST /v1/auth/k8s/login HTTP/1.1");
+    assert!(
+        login
+            .headers
+            .contains(&"X-Vault-Namespace: team".to_string())
+    );
+    let body: serde_json::Value = serde_json::from_str(&login.body)?;
+    assert_eq!(
+        body,
+        serde_json::json!({ "role": "myapp", "jwt": "eyJhbGciOi.jwt" })
+    );
+    Ok(())
+}
+
+#[test]
+fn vault_source_reports_vault_errors() -> anyhow::Result<()> {
+    let (addr, _requests) =
+        serve(vec![(404, r#"{"errors": ["secret not found"]}"#)])?;
+    let err = VaultSource::new(addr, "secret", "missing")
+        .token("s.static")
+        .parse()
+        .expect_err("the secret is missing")
+        .to_string();
+    assert!(err.contains("status 404: secret not found"), "{err}");
+    Ok(())
+}
@@ -62,10 +62,7 @@ fn test_watch_integration() -> anyhow::Result<()> {
         }
     });
 
-    realme
-        .write()
-        .expect("set realme")
-        .set("section.should_not_change", "1")?;
+    realme.update(|realme| realme.set("section.should_not_change", "1"))?;
 
     eprintln!("Second updated configuration: {realme:?}");
 
@@ -99,3 +96,66 @@ fn test_watch_integration() -> anyhow::Result<()> {
     );
     Ok(())
 }
+
+#[test]
+fn test_watch_survives_failed_reload() -> anyhow::Result<()> {
+    let temp_file = NamedTempFile::new()?;
+    std::fs::write(&temp_file, "key = \"initial\"\n")?;
+
+    let realme = Realme::shared_builder()
+        .load(Adaptor::new(FileSource::<TomlParser>::new(
+            temp_file.path(),
+        )))
+        .shared_build()?;
+
+    std::fs::write(&temp_file, "key = \n")?;
+    thread::sleep(Duration::from_secs(2));
+    assert_eq!(
+        realme.current()?.get_as::<String, _>("key").as_deref(),
+        Some("initial")
+    );
+
+    std::fs::write(&temp_file, "key = \"updated\"\n")?;
+    thread::sleep(Duration::from_secs(2));
+    assert_eq!(
+        realme.current()?.get_as::<String, _>("key").as_deref(),
+        Some("updated")
+    );
+    Ok(())
+}
+
+#[test]
+fn test_shared_builder_current() -> anyhow::Result<()> {
+    let temp_file = NamedTempFile::new()?;
+    std::fs::write(&temp_file, "key = \"initial\"\n")?;
+
+    let realme = Realme::shared_builder()
+        .load(Adaptor::new(FileSource::<TomlParser>::new(
+            temp_file.path(),
+        )))
+        .shared_build()?;
+    let before = realme.current()?;
+    assert_eq!(
+        before.get_as::<String, _>("key").as_deref(),
+        Some("initial")
+    );
+    assert!(std::sync::Arc::ptr_eq(&before, &realme.current()?));
+
+    std::fs::write(&temp_file, "key = \"updated\"\n")?;
+    thread::sleep(Duration::from_secs(2));
+
+    let after = realme.current()?;
+    assert_eq!(after.get_as::<String, _>("key").as_deref(), Some("updated"));
+    assert_eq!(
+        before.get_as::<String, _>("key").as_deref(),
+        Some("initial")
+    );
+
+    realme.update(|realme| realme.set("key", "set"))?;
+    assert_eq!(
+        realme.current()?.get_as::<String, _>("key").as_deref(),
+        Some("set")
+    );
+    assert_eq!(after.get_as::<String, _>("key").as_deref(), Some("updated"));
+    Ok(())
+}